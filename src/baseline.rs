@@ -0,0 +1,305 @@
+//! Detecting license changes against a committed baseline.
+//!
+//! A [`Baseline`](struct.Baseline.html) records the license of every
+//! dependency, and whether it has been reviewed, in a `licenses.lock` file:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [[package]]
+//! name = "serde"
+//! version = "1.0.104"
+//! license = "MIT OR Apache-2.0"
+//! reviewed = true
+//! ```
+//!
+//! A fresh scan is then [checked](struct.Baseline.html#method.check) against
+//! it to find new and removed packages, packages that are still awaiting
+//! review and, most importantly, packages whose license changed.
+//!
+//! ```
+//! use linfo::{baseline::{Baseline, Entry}, LicenseField};
+//!
+//! let entry = |version: &str, license: &str| Entry {
+//!     name: "foo".to_owned(),
+//!     version: version.to_owned(),
+//!     license: LicenseField::parse(license).unwrap(),
+//!     reviewed: true,
+//! };
+//!
+//! let baseline = Baseline::new(vec![entry("1.0.0", "MIT OR Apache-2.0")]);
+//!
+//! let reordered = Baseline::new(vec![entry("1.0.1", "Apache-2.0 OR MIT")]);
+//! assert!(baseline.check(&reordered).is_empty());
+//!
+//! let relicensed = Baseline::new(vec![entry("2.0.0", "CC-BY-NC-4.0")]);
+//! let diff = baseline.check(&relicensed);
+//! assert_eq!(diff.relicensed.len(), 1);
+//! assert_eq!(
+//!     diff.to_string(),
+//!     "relicensed: foo 1.0.0 -> 2.0.0 (MIT OR Apache-2.0 -> CC-BY-NC-4.0)\n",
+//! );
+//! ```
+
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::{cargo::Graph, LicenseField};
+
+/// The licenses of a set of packages, as stored in a `licenses.lock` file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The version of the file format.
+    pub version: u32,
+    /// The packages, sorted by name and version.
+    #[serde(default, rename = "package")]
+    pub packages: Vec<Entry>,
+}
+
+/// The license of a package in a [`Baseline`](struct.Baseline.html).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// The name of the package.
+    pub name: String,
+    /// The version of the package.
+    pub version: String,
+    /// The license of the package, or `NOASSERTION` if unknown.
+    pub license: LicenseField,
+    /// Whether the license has been reviewed.
+    #[serde(default)]
+    pub reviewed: bool,
+}
+
+impl fmt::Display for Entry {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} ({})", self.name, self.version, self.license)
+    }
+}
+
+impl Entry {
+    /// Returns whether `self` and `other` have the same license, regardless of
+    /// the order of licenses in their expressions.
+    pub fn same_license(&self, other: &Entry) -> bool {
+        match (&self.license, &other.license) {
+            (LicenseField::Expr(a), LicenseField::Expr(b)) => a.is_equivalent(b),
+            (a, b) => a == b,
+        }
+    }
+}
+
+impl Baseline {
+    /// The current version of the file format.
+    pub const VERSION: u32 = 1;
+
+    /// Creates a baseline of `packages`.
+    pub fn new(mut packages: Vec<Entry>) -> Self {
+        packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        Self { version: Self::VERSION, packages }
+    }
+
+    /// Creates a baseline of the packages [reachable] in `graph`, none of
+    /// which are reviewed.
+    ///
+    /// [reachable]: ../cargo/struct.Graph.html#method.reachable
+    pub fn from_graph(graph: &Graph) -> Self {
+        let packages = graph.reachable().into_iter().map(|node| Entry {
+            name: node.name.clone(),
+            version: node.version.clone(),
+            license: node.license.clone(),
+            reviewed: false,
+        }).collect();
+        Self::new(packages)
+    }
+
+    /// Parses a baseline from the contents of a `licenses.lock` file.
+    #[inline]
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    /// Returns the contents of a `licenses.lock` file.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("baseline is always valid TOML")
+    }
+
+    /// Compares a fresh scan, `current`, against this baseline.
+    ///
+    /// Packages are matched by name, so a new version of a package is only
+    /// reported if its license changed. A package whose license is unchanged
+    /// but not yet reviewed in this baseline is reported as
+    /// [`unreviewed`](struct.Diff.html#structfield.unreviewed).
+    pub fn check<'a>(&'a self, current: &'a Baseline) -> Diff<'a> {
+        let mut diff = Diff::default();
+        for (old, new) in pair(&self.packages, &current.packages) {
+            match (old, new) {
+                (Some(old), Some(new)) => if !old.same_license(new) {
+                    diff.relicensed.push(Relicensed { old, new });
+                } else if !old.reviewed {
+                    diff.unreviewed.push(old);
+                },
+                (Some(old), None) => diff.removed.push(old),
+                (None, Some(new)) => diff.added.push(new),
+                (None, None) => {},
+            }
+        }
+        diff
+    }
+
+    /// Returns `current` with the review status of each package kept from this
+    /// baseline, unless its license changed.
+    pub fn update(&self, current: &Baseline) -> Baseline {
+        let mut packages = Vec::with_capacity(current.packages.len());
+        for (old, new) in pair(&self.packages, &current.packages) {
+            if let Some(new) = new {
+                let reviewed = old.is_some_and(|old| old.reviewed && old.same_license(new));
+                packages.push(Entry { reviewed, ..new.clone() });
+            }
+        }
+        Self::new(packages)
+    }
+}
+
+/// Pairs the entries of `old` and `new` by name, preferring the same version
+/// and otherwise pairing versions in order.
+fn pair<'a>(
+    old: &'a [Entry],
+    new: &'a [Entry],
+) -> Vec<(Option<&'a Entry>, Option<&'a Entry>)> {
+    let mut pairs = Vec::new();
+    let mut names: Vec<&str> = old.iter().chain(new).map(|e| e.name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+
+    for name in names {
+        let mut old: Vec<&Entry> = old.iter().filter(|e| e.name == name).collect();
+        let mut new: Vec<&Entry> = new.iter().filter(|e| e.name == name).collect();
+
+        old.retain(|o| match new.iter().position(|n| n.version == o.version) {
+            Some(i) => {
+                pairs.push((Some(*o), Some(new.remove(i))));
+                false
+            },
+            None => true,
+        });
+
+        let len = old.len().max(new.len());
+        for i in 0..len {
+            pairs.push((old.get(i).copied(), new.get(i).copied()));
+        }
+    }
+    pairs
+}
+
+/// The differences between a [`Baseline`](struct.Baseline.html) and a fresh
+/// scan.
+#[derive(Clone, Debug, Default)]
+pub struct Diff<'a> {
+    /// Packages that are not in the baseline.
+    pub added: Vec<&'a Entry>,
+    /// Packages in the baseline that are no longer present.
+    pub removed: Vec<&'a Entry>,
+    /// Packages in the baseline, with an unchanged license, that have not
+    /// been reviewed.
+    pub unreviewed: Vec<&'a Entry>,
+    /// Packages whose license changed.
+    pub relicensed: Vec<Relicensed<'a>>,
+}
+
+/// A package whose license changed.
+#[derive(Clone, Copy, Debug)]
+pub struct Relicensed<'a> {
+    /// The package as recorded in the baseline.
+    pub old: &'a Entry,
+    /// The package as currently found.
+    pub new: &'a Entry,
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for entry in &self.added {
+            writeln!(f, "added: {}", entry)?;
+        }
+        for entry in &self.removed {
+            writeln!(f, "removed: {}", entry)?;
+        }
+        for entry in &self.unreviewed {
+            writeln!(f, "unreviewed: {}", entry)?;
+        }
+        for Relicensed { old, new } in &self.relicensed {
+            writeln!(
+                f,
+                "relicensed: {} {} -> {} ({} -> {})",
+                new.name,
+                old.version,
+                new.version,
+                old.license,
+                new.license,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Diff<'_> {
+    /// Returns whether there are no differences.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.unreviewed.is_empty()
+            && self.relicensed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, version: &str, license: &str) -> Entry {
+        Entry {
+            name: name.to_owned(),
+            version: version.to_owned(),
+            license: LicenseField::parse(license).unwrap(),
+            reviewed: true,
+        }
+    }
+
+    #[test]
+    fn check() {
+        let baseline = Baseline::new(vec![
+            entry("a", "1.0.0", "MIT"),
+            entry("b", "0.1.0", "MIT OR Apache-2.0"),
+            entry("b", "0.2.0", "MIT OR Apache-2.0"),
+            entry("c", "1.0.0", "NOASSERTION"),
+        ]);
+        let toml = baseline.to_toml();
+        assert_eq!(Baseline::from_toml(&toml).unwrap(), baseline);
+
+        let current = Baseline::new(vec![
+            entry("b", "0.2.0", "Apache-2.0 OR MIT"),
+            entry("b", "0.3.0", "CC-BY-NC-4.0"),
+            entry("c", "1.0.0", "ISC"),
+            entry("d", "1.0.0", "MIT"),
+        ]);
+        let diff = baseline.check(&current);
+        assert_eq!(diff.added, [&current.packages[3]]);
+        assert_eq!(diff.removed, [&baseline.packages[0]]);
+        assert_eq!(diff.relicensed.len(), 2);
+        assert_eq!(diff.relicensed[0].old.version, "0.1.0");
+        assert_eq!(diff.relicensed[0].new.version, "0.3.0");
+        assert!(diff.unreviewed.is_empty());
+
+        let updated = baseline.update(&current);
+        let reviewed: Vec<bool> = updated.packages.iter().map(|e| e.reviewed).collect();
+        assert_eq!(reviewed, [true, false, false, false]);
+
+        let diff = updated.check(&current);
+        let unreviewed: Vec<&Entry> = updated.packages[1..].iter().collect();
+        assert_eq!(diff.unreviewed, unreviewed);
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.relicensed.is_empty());
+        assert_eq!(diff.to_string(), "\
+            unreviewed: b 0.3.0 (CC-BY-NC-4.0)\n\
+            unreviewed: c 1.0.0 (ISC)\n\
+            unreviewed: d 1.0.0 (MIT)\n");
+    }
+}
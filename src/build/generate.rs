@@ -0,0 +1,834 @@
+use std::{
+    collections::BTreeSet,
+    env,
+    error,
+    fmt::{self, Write},
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+use crate::{
+    cargo::{self, Filter, LockPackage, Lockfile, Target},
+    clarify::Clarifications,
+    Expr,
+    License,
+    LicenseField,
+};
+use super::resolve;
+
+/// Writes the license table of the crate being built to
+/// `$OUT_DIR/licenses.rs`.
+///
+/// This is meant to be called from a build script. See
+/// [`Generator::from_env`](struct.Generator.html#method.from_env).
+pub fn generate() -> Result<(), Error> {
+    let out_dir = env::var_os("OUT_DIR").ok_or(Error::Env("OUT_DIR"))?;
+    let generator = Generator::from_env()?;
+    let (source, warnings) = generator.render()?;
+    fs::write(Path::new(&out_dir).join("licenses.rs"), source)?;
+
+    for warning in warnings {
+        println!("cargo:warning={}", warning);
+    }
+    println!("cargo:rerun-if-changed={}", generator.lockfile.display());
+    if let Some(manifest) = &generator.manifest {
+        println!("cargo:rerun-if-changed={}", manifest.display());
+    }
+    Ok(())
+}
+
+/// Generates a license table from a `Cargo.lock` file and the local registry
+/// cache, without accessing the network.
+///
+/// The table includes every registry package that `package` depends on,
+/// transitively. Because `Cargo.lock` does not distinguish between kinds of
+/// dependencies, those that aren't compiled into `package` are left out by
+/// `filter`, according to the manifest of `package` and those of its
+/// dependencies in the registry cache. Without `manifest`, this includes
+/// dev-dependencies, build-dependencies and disabled optional dependencies.
+/// Path dependencies are assumed to be part of the same project and are
+/// skipped, and the licenses of git dependencies are unknown.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    /// The path of the `Cargo.lock` file.
+    pub lockfile: PathBuf,
+    /// The root of the Cargo home directory, which contains `registry/src`.
+    pub cargo_home: PathBuf,
+    /// The name of the package whose dependencies to include.
+    pub package: String,
+    /// The [clarifications](../clarify/index.html) to apply to the licenses
+    /// of dependencies, which are empty by default.
+    pub clarifications: Clarifications,
+    /// The path of the `Cargo.toml` file of `package`, which tells apart the
+    /// kinds of dependencies.
+    pub manifest: Option<PathBuf>,
+    /// The dependencies of `package` to include if `manifest` is set.
+    pub filter: Filter,
+}
+
+impl Generator {
+    /// Creates a generator for the crate being built by the current build
+    /// script.
+    ///
+    /// The lockfile is found in `$CARGO_MANIFEST_DIR` or its closest ancestor
+    /// that contains one, such as the workspace root. Cargo's home directory
+    /// is `$CARGO_HOME`, or `.cargo` within the user's home directory.
+    ///
+    /// Only the normal dependencies for `$TARGET` with the features enabled on
+    /// the crate (`$CARGO_FEATURE_<name>`) are included.
+    pub fn from_env() -> Result<Self, Error> {
+        let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")
+            .ok_or(Error::Env("CARGO_MANIFEST_DIR"))?;
+        let package = env::var("CARGO_PKG_NAME")
+            .map_err(|_| Error::Env("CARGO_PKG_NAME"))?;
+
+        let manifest_dir = PathBuf::from(manifest_dir);
+        let lockfile = manifest_dir.ancestors()
+            .map(|dir| dir.join("Cargo.lock"))
+            .find(|path| path.is_file())
+            .ok_or_else(|| Error::NoLockfile(manifest_dir.clone()))?;
+
+        let cargo_home = match env::var_os("CARGO_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => {
+                let home = env::var_os("HOME")
+                    .or_else(|| env::var_os("USERPROFILE"))
+                    .ok_or(Error::Env("CARGO_HOME"))?;
+                Path::new(&home).join(".cargo")
+            },
+        };
+
+        let manifest = manifest_dir.join("Cargo.toml");
+        let features = resolve::feature_names(&manifest)?
+            .into_iter()
+            .filter(|name| {
+                let var = name.to_ascii_uppercase().replace('-', "_");
+                env::var_os(format!("CARGO_FEATURE_{}", var)).is_some()
+            })
+            .collect();
+        let filter = Filter {
+            target: env::var("TARGET").ok().map(|t| Target::from_triple(&t)),
+            features,
+            // `default` is in `features` if enabled
+            no_default_features: true,
+            ..Filter::default()
+        };
+
+        Ok(Self {
+            lockfile,
+            cargo_home,
+            package,
+            clarifications: Clarifications::default(),
+            manifest: Some(manifest),
+            filter,
+        })
+    }
+
+    /// Returns the Rust source of the license table, which is an expression
+    /// of type `&[linfo::build::Entry]`.
+    ///
+    /// Fails if a clarification no longer applies because its pinned files
+    /// changed, so that it is reviewed again.
+    ///
+    /// If the `license` field of a dependency is invalid, its license is
+    /// detected from its license files instead.
+    #[inline]
+    pub fn source(&self) -> Result<String, Error> {
+        self.render().map(|(source, _)| source)
+    }
+
+    /// Returns the source of the license table and warnings about the
+    /// licenses of dependencies, such as invalid `license` fields.
+    fn render(&self) -> Result<(String, Vec<String>), Error> {
+        let lockfile = Lockfile::from_path(&self.lockfile)?;
+        let registries = self.registries()?;
+
+        let (root, mut packages) = dependencies(&lockfile, &self.package)?;
+        if let Some(manifest) = &self.manifest {
+            let included = resolve::included(&lockfile, root, manifest, &registries, &self.filter)?;
+            packages.retain(|p| included.contains(&(p.name.as_str(), p.version.as_str())));
+        }
+
+        let mut out = String::from("&[\n");
+        let mut warnings = Vec::new();
+        for package in packages {
+            let dir = registry_dir(&registries, package);
+            let Contents { mut expr, files, invalid } = match &dir {
+                Some(dir) => read_package(dir)?,
+                None => Contents { expr: None, files: Vec::new(), invalid: None },
+            };
+
+            let mut stale = Vec::new();
+            let clarified = self.clarifications
+                .clarified(&package.name, &package.version, dir.as_deref(), &mut stale);
+            if let Some(stale) = stale.first() {
+                return Err(Error::Stale(stale.to_string()));
+            }
+            if let Some(clarified) = clarified {
+                expr = Some(clarified.clone());
+            } else if let Some(invalid) = invalid {
+                let fallback = match &expr {
+                    Some(expr) => format!("using {} detected from its license files", expr),
+                    None => "no license detected from its license files".to_owned(),
+                };
+                warnings.push(format!(
+                    "{} {}: {}; {}",
+                    package.name, package.version, invalid, fallback,
+                ));
+            }
+
+            writeln!(out, "    linfo::build::Entry {{").unwrap();
+            writeln!(out, "        name: {:?},", package.name).unwrap();
+            writeln!(out, "        version: {:?},", package.version).unwrap();
+            match expr.as_ref().and_then(static_expr) {
+                Some(expr) => writeln!(out, "        expr: Some({}),", expr),
+                None => writeln!(out, "        expr: None,"),
+            }.unwrap();
+
+            let mut notices = Vec::new();
+            writeln!(out, "        license_files: &[").unwrap();
+            for File { name, text } in &files {
+                if name.to_ascii_uppercase().starts_with("NOTICE") {
+                    notices.push(text.as_str());
+                    continue;
+                }
+                let license = expr.as_ref()
+                    .and_then(|expr| file_license(name, text, expr))
+                    .and_then(static_license);
+                match license {
+                    Some(license) => writeln!(out, "            linfo::build::StaticLicenseFile {{ \
+                        license: Some({}), text: {:?} }},", license, text),
+                    None => writeln!(out, "            linfo::build::StaticLicenseFile {{ \
+                        license: None, text: {:?} }},", text),
+                }.unwrap();
+            }
+            writeln!(out, "        ],").unwrap();
+            if notices.is_empty() {
+                writeln!(out, "        notice: None,")
+            } else {
+                writeln!(out, "        notice: Some({:?}),", notices.join("\n"))
+            }.unwrap();
+            writeln!(out, "    }},").unwrap();
+        }
+        out.push_str("]\n");
+        Ok((out, warnings))
+    }
+
+    /// Writes the Rust source of the license table to `path`.
+    ///
+    /// See [`source`](#method.source).
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.source()?)?;
+        Ok(())
+    }
+
+    /// Returns the directories of extracted registry packages, such as
+    /// `registry/src/index.crates.io-6f17d22bba15001f`.
+    fn registries(&self) -> Result<Vec<PathBuf>, Error> {
+        let src = self.cargo_home.join("registry").join("src");
+        let entries = match fs::read_dir(&src) {
+            Ok(entries) => entries,
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok(Vec::new());
+            },
+            Err(error) => return Err(error.into()),
+        };
+        let mut dirs = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            }
+        }
+        dirs.sort();
+        Ok(dirs)
+    }
+}
+
+/// Returns the directory that the registry package `package` is extracted to,
+/// if any.
+pub(super) fn registry_dir(registries: &[PathBuf], package: &LockPackage) -> Option<PathBuf> {
+    match package.source.as_deref() {
+        Some(source) if source.starts_with("registry+") || source.starts_with("sparse+") => {
+            registries.iter()
+                .map(|r| r.join(format!("{}-{}", package.name, package.version)))
+                .find(|dir| dir.is_dir())
+        },
+        _ => None,
+    }
+}
+
+/// Returns the package `root` and every package with a source that it depends
+/// on, sorted by name and version.
+fn dependencies<'a>(
+    lockfile: &'a Lockfile,
+    root: &str,
+) -> Result<(&'a LockPackage, Vec<&'a LockPackage>), Error> {
+    let root = lockfile.packages.iter()
+        .find(|p| p.name == root && p.source.is_none())
+        .ok_or_else(|| Error::NoPackage(root.to_owned()))?;
+
+    let mut seen = BTreeSet::new();
+    let mut stack = vec![root];
+    let mut packages = Vec::new();
+
+    while let Some(package) = stack.pop() {
+        for dep in &package.dependencies {
+            let dep = match lockfile.find(dep) {
+                Some(dep) => dep,
+                None => continue,
+            };
+            if seen.insert(dep.id()) {
+                if dep.source.is_some() {
+                    packages.push(dep);
+                }
+                stack.push(dep);
+            }
+        }
+    }
+
+    packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    Ok((root, packages))
+}
+
+/// A license or `NOTICE` file of a package.
+struct File {
+    name: String,
+    text: String,
+}
+
+/// The license of a package and its license and `NOTICE` files.
+struct Contents {
+    expr: Option<Expr>,
+    files: Vec<File>,
+    /// The error in the `license` field, if it is invalid.
+    invalid: Option<cargo::LicenseError>,
+}
+
+/// Reads the package extracted at `dir`.
+///
+/// If the `license` field is missing, invalid or `NOASSERTION`, the license is
+/// detected from the first license file that matches a known license. A
+/// package whose `license` field is `NONE` has no license.
+fn read_package(dir: &Path) -> Result<Contents, Error> {
+    let manifest: toml::Value = toml::from_str(&fs::read_to_string(dir.join("Cargo.toml"))?)
+        .map_err(cargo::Error::Toml)?;
+    let package = manifest.get("package");
+    let field = |key: &str| package.and_then(|p| p.get(key)).and_then(|v| v.as_str());
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = match path.file_name().and_then(|n| n.to_str()) {
+            Some(name) => name.to_ascii_uppercase(),
+            None => continue,
+        };
+        let is_license = ["LICENSE", "LICENCE", "COPYING", "NOTICE"].iter()
+            .any(|prefix| name.starts_with(prefix));
+        if is_license && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    if let Some(file) = field("license-file") {
+        let file = dir.join(file);
+        paths.retain(|path| path != &file);
+        paths.insert(0, file);
+    }
+
+    let mut files = Vec::new();
+    for path in &paths {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        match fs::read_to_string(path) {
+            Ok(text) => files.push(File { name: name.to_owned(), text }),
+            // Non-UTF-8 or missing `license-file`
+            Err(_) => continue,
+        }
+    }
+
+    let (expr, invalid) = match field("license").map(cargo::parse_license_field) {
+        Some(Ok(LicenseField::None)) => return Ok(Contents { expr: None, files, invalid: None }),
+        Some(Ok(field)) => (field.into_expr(), None),
+        Some(Err(error)) => (None, Some(error)),
+        None => (None, None),
+    };
+    let expr = expr.or_else(|| {
+        files.iter()
+            .filter(|file| !file.name.to_ascii_uppercase().starts_with("NOTICE"))
+            .find_map(|file| cargo::detect(&file.text))
+    });
+    Ok(Contents { expr, files, invalid })
+}
+
+/// Returns the license of `expr` that the license file `name` is the text of,
+/// such as MIT for `LICENSE-MIT`, or `None` if it can't be told apart.
+fn file_license<'a>(name: &str, text: &str, expr: &'a Expr) -> Option<&'a License> {
+    let name = name.to_ascii_uppercase();
+    let stem = name.split('.').next().unwrap_or_default();
+    let suffix = ["LICENSE", "LICENCE", "COPYING"].iter()
+        .find_map(|prefix| stem.strip_prefix(prefix))
+        .unwrap_or_default()
+        .trim_start_matches(['-', '_']);
+
+    if !suffix.is_empty() {
+        let mut matching = expr.licenses().filter(|l| {
+            let id = l.base().id().to_ascii_uppercase();
+            id == suffix || id.starts_with(&format!("{}-", suffix))
+        });
+        if let (Some(license), None) = (matching.next(), matching.next()) {
+            return Some(license);
+        }
+    }
+    match cargo::detect(text)? {
+        Expr::Single(detected) => expr.licenses().find(|l| l.base() == &detected),
+        _ => None,
+    }
+}
+
+/// Returns the Rust source for a `StaticExpr` equivalent to `expr`.
+fn static_expr(expr: &Expr) -> Option<String> {
+    let list = |exprs: &[Expr]| {
+        let exprs: Vec<String> = exprs.iter().map(static_expr).collect::<Option<_>>()?;
+        Some(exprs.join(", "))
+    };
+    Some(match expr {
+        Expr::Single(l) => format!("linfo::build::StaticExpr::Single({})", static_license(l)?),
+        Expr::Or(or) => format!("linfo::build::StaticExpr::Or(&[{}])", list(or.as_slice())?),
+        Expr::And(and) => format!("linfo::build::StaticExpr::And(&[{}])", list(and.as_slice())?),
+    })
+}
+
+/// Returns the Rust source for a `StaticLicense` equivalent to `license`, or
+/// `None` if it has more than one exception.
+fn static_license(license: &License) -> Option<String> {
+    let (license, exception) = match license {
+        License::With(l, e) => (&**l, format!("Some(linfo::spdx::SpdxException::{:?})", e)),
+        l => (l, "None".to_owned()),
+    };
+    match license {
+        License::Spdx(l) => Some(format!(
+            "linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::{:?}, {})",
+            l, exception,
+        )),
+        License::Ref(l) => Some(format!(
+            "linfo::build::StaticLicense::Ref({:?}, {})",
+            l.id(), exception,
+        )),
+        License::With(..) => None,
+        License::_NonExhaustive(never) => never.consume(),
+    }
+}
+
+/// An error returned when generating a license table.
+#[derive(Debug)]
+pub enum Error {
+    /// The environment variable is not set, likely because this was not called
+    /// from a build script.
+    Env(&'static str),
+    /// No `Cargo.lock` was found in the directory or any of its ancestors.
+    NoLockfile(PathBuf),
+    /// The package is not a path package in the lockfile.
+    NoPackage(String),
+    /// A file could not be read or parsed.
+    Cargo(cargo::Error),
+    /// A clarification no longer applies because its pinned files changed.
+    Stale(String),
+    /// A dependency is inherited from a workspace, but no workspace contains
+    /// the package in the directory.
+    NoWorkspace(PathBuf),
+}
+
+impl From<io::Error> for Error {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        Self::Cargo(error.into())
+    }
+}
+
+impl From<cargo::Error> for Error {
+    #[inline]
+    fn from(error: cargo::Error) -> Self {
+        Self::Cargo(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Env(var) => write!(f, "environment variable `{}` is not set", var),
+            Error::NoLockfile(dir) => {
+                write!(f, "no Cargo.lock found in {} or its ancestors", dir.display())
+            },
+            Error::NoPackage(name) => {
+                write!(f, "package `{}` not found in Cargo.lock", name)
+            },
+            Error::Cargo(error) => error.fmt(f),
+            Error::Stale(stale) => f.write_str(stale),
+            Error::NoWorkspace(dir) => {
+                write!(f, "no workspace found for the package in {}", dir.display())
+            },
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Cargo(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dependencies_of_root() {
+        let lockfile = Lockfile::from_toml(r#"
+            [[package]]
+            name = "app"
+            version = "0.1.0"
+            dependencies = ["serde", "local"]
+
+            [[package]]
+            name = "local"
+            version = "0.1.0"
+            dependencies = ["itoa"]
+
+            [[package]]
+            name = "serde"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+
+            [[package]]
+            name = "itoa"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+
+            [[package]]
+            name = "unrelated"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+        "#).unwrap();
+
+        let names: Vec<&str> = dependencies(&lockfile, "app").unwrap().1
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, ["itoa", "serde"]);
+
+        let expr = Expr::parse("MIT OR Apache-2.0").unwrap();
+        assert_eq!(
+            static_expr(&expr).unwrap(),
+            "linfo::build::StaticExpr::Or(&[\
+                linfo::build::StaticExpr::Single(\
+                    linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::Mit, None)), \
+                linfo::build::StaticExpr::Single(\
+                    linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::Apache2, None))])",
+        );
+
+        let expr = Expr::parse("(Apache-2.0 WITH LLVM-exception OR MIT) AND LicenseRef-Custom").unwrap();
+        let source = static_expr(&expr).unwrap();
+        assert_eq!(
+            source,
+            "linfo::build::StaticExpr::And(&[\
+                linfo::build::StaticExpr::Or(&[\
+                    linfo::build::StaticExpr::Single(linfo::build::StaticLicense::Spdx(\
+                        linfo::SpdxLicense::Apache2, \
+                        Some(linfo::spdx::SpdxException::Llvm))), \
+                    linfo::build::StaticExpr::Single(\
+                        linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::Mit, None))]), \
+                linfo::build::StaticExpr::Single(\
+                    linfo::build::StaticLicense::Ref(\"LicenseRef-Custom\", None))])",
+        );
+    }
+
+    #[test]
+    fn clarify_dependencies() {
+        let dir = env::temp_dir().join(format!("linfo-generate-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let lockfile = dir.join("Cargo.lock");
+        fs::write(&lockfile, r#"
+            [[package]]
+            name = "app"
+            version = "0.1.0"
+            dependencies = ["ring"]
+
+            [[package]]
+            name = "ring"
+            version = "0.16.20"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+        "#).unwrap();
+
+        let mut generator = Generator {
+            lockfile,
+            cargo_home: dir.join("cargo"),
+            package: "app".to_owned(),
+            clarifications: toml::from_str(r#"
+                [[clarify]]
+                name = "ring"
+                version = "0.16"
+                expr = "MIT AND ISC AND OpenSSL"
+            "#).unwrap(),
+            manifest: None,
+            filter: Filter::default(),
+        };
+        let source = generator.source().unwrap();
+        assert!(source.contains("expr: Some(linfo::build::StaticExpr::And(&[\
+            linfo::build::StaticExpr::Single(\
+                linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::Mit, None)), \
+            linfo::build::StaticExpr::Single(\
+                linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::Isc, None)), \
+            linfo::build::StaticExpr::Single(\
+                linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::OpenSsl, None))]))"));
+
+        generator.clarifications.clarifications[0].files = vec![
+            crate::clarify::PinnedFile::new("LICENSE", b"reviewed"),
+        ];
+        match generator.source() {
+            Err(Error::Stale(stale)) => assert!(stale.contains("LICENSE"), "{}", stale),
+            other => panic!("expected stale clarification, got {:?}", other),
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn license_files_and_kinds() {
+        let dir = env::temp_dir().join(format!("linfo-generate-files-{}", std::process::id()));
+        let package = dir.join("cargo/registry/src/index.crates.io-6f17d22bba15001f/dual-1.0.0");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("Cargo.toml"), r#"
+            [package]
+            name = "dual"
+            version = "1.0.0"
+            license = "MIT OR Apache-2.0"
+        "#).unwrap();
+        fs::write(package.join("LICENSE-MIT"), "Permission is hereby granted").unwrap();
+        fs::write(package.join("LICENSE-APACHE"), "Apache License").unwrap();
+        fs::write(package.join("NOTICE"), "Copyright Dual Authors").unwrap();
+
+        let lockfile = dir.join("Cargo.lock");
+        fs::write(&lockfile, r#"
+            [[package]]
+            name = "app"
+            version = "0.1.0"
+            dependencies = ["dual", "dev"]
+
+            [[package]]
+            name = "dual"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+
+            [[package]]
+            name = "dev"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+        "#).unwrap();
+        let manifest = dir.join("app/Cargo.toml");
+        fs::create_dir_all(dir.join("app")).unwrap();
+        fs::write(&manifest, r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+
+            [dependencies]
+            dual = "1"
+
+            [dev-dependencies]
+            dev = "1"
+        "#).unwrap();
+
+        let mut generator = Generator {
+            lockfile,
+            cargo_home: dir.join("cargo"),
+            package: "app".to_owned(),
+            clarifications: Clarifications::default(),
+            manifest: Some(manifest),
+            filter: Filter::default(),
+        };
+        let source = generator.source().unwrap();
+        assert!(!source.contains("\"dev\""), "{}", source);
+        for expected in &[
+            "license: Some(linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::Apache2, None)), \
+             text: \"Apache License\"",
+            "license: Some(linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::Mit, None)), \
+             text: \"Permission is hereby granted\"",
+            "notice: Some(\"Copyright Dual Authors\")",
+        ] {
+            assert!(source.contains(expected), "missing {:?} in:\n{}", expected, source);
+        }
+
+        generator.manifest = None;
+        assert!(generator.source().unwrap().contains("\"dev\""));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn features_and_targets() {
+        let dir = env::temp_dir().join(format!("linfo-generate-features-{}", std::process::id()));
+        let registry = dir.join("cargo/registry/src/index.crates.io-6f17d22bba15001f");
+        let write = |path: PathBuf, contents: &str| {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write(dir.join("Cargo.toml"), r#"
+            [workspace]
+            members = ["app", "local"]
+
+            [workspace.dependencies]
+            local = { path = "local" }
+        "#);
+        write(dir.join("app/Cargo.toml"), r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+
+            [features]
+            default = ["fast"]
+            fast = ["dep:fastdep"]
+            extra = ["helper/extra"]
+
+            [dependencies]
+            fastdep = { version = "1", optional = true }
+            helper = "1"
+            unused = { version = "1", optional = true }
+            local = { workspace = true }
+
+            [target.'cfg(windows)'.dependencies]
+            winapi = "0.3"
+
+            [build-dependencies]
+            cc = "1"
+        "#);
+        write(dir.join("local/Cargo.toml"), r#"
+            [package]
+            name = "local"
+            version = "0.1.0"
+
+            [dependencies]
+            leaf = "1"
+        "#);
+        write(registry.join("helper-1.0.0/Cargo.toml"), r#"
+            [package]
+            name = "helper"
+            version = "1.0.0"
+
+            [features]
+            extra = ["dep:inner"]
+
+            [dependencies]
+            inner = { version = "1", optional = true }
+        "#);
+
+        let registry_package = |name: &str, dependencies: &str| format!(r#"
+            [[package]]
+            name = "{}"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+            dependencies = [{}]
+        "#, name, dependencies);
+        let lockfile = dir.join("Cargo.lock");
+        let mut lock = String::from(r#"
+            [[package]]
+            name = "app"
+            version = "0.1.0"
+            dependencies = ["cc", "fastdep", "helper", "local", "unused", "winapi"]
+
+            [[package]]
+            name = "local"
+            version = "0.1.0"
+            dependencies = ["leaf"]
+        "#);
+        for (name, dependencies) in &[
+            ("cc", ""),
+            ("fastdep", ""),
+            ("helper", "\"inner\""),
+            ("inner", ""),
+            ("leaf", ""),
+            ("unused", ""),
+            ("winapi", ""),
+        ] {
+            lock.push_str(&registry_package(name, dependencies));
+        }
+        fs::write(&lockfile, lock).unwrap();
+
+        let mut generator = Generator {
+            lockfile,
+            cargo_home: dir.join("cargo"),
+            package: "app".to_owned(),
+            clarifications: Clarifications::default(),
+            manifest: Some(dir.join("app/Cargo.toml")),
+            filter: Filter {
+                target: Some(Target::from_triple("x86_64-unknown-linux-gnu")),
+                ..Filter::default()
+            },
+        };
+        let names = |generator: &Generator| -> Vec<String> {
+            generator.source().unwrap()
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("name: "))
+                .map(|name| name.trim_end_matches(',').trim_matches('"').to_owned())
+                .collect()
+        };
+        assert_eq!(names(&generator), ["fastdep", "helper", "leaf"]);
+
+        generator.filter.features = vec!["extra".to_owned()];
+        generator.filter.no_default_features = true;
+        assert_eq!(names(&generator), ["helper", "inner", "leaf"]);
+
+        generator.filter.target = Some(Target::from_triple("x86_64-pc-windows-msvc"));
+        generator.filter.kinds.push(cargo::DependencyKind::Build);
+        assert_eq!(names(&generator), ["cc", "helper", "inner", "leaf", "winapi"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_license_field() {
+        let dir = env::temp_dir().join(format!("linfo-generate-invalid-{}", std::process::id()));
+        let package = dir.join("cargo/registry/src/index.crates.io-6f17d22bba15001f/odd-1.0.0");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("Cargo.toml"), r#"
+            [package]
+            name = "odd"
+            version = "1.0.0"
+            license = "MIT AND Not-A-License"
+        "#).unwrap();
+        fs::write(package.join("LICENSE"), "MIT License\n\nCopyright (c) Odd Authors").unwrap();
+
+        let lockfile = dir.join("Cargo.lock");
+        fs::write(&lockfile, r#"
+            [[package]]
+            name = "app"
+            version = "0.1.0"
+            dependencies = ["odd"]
+
+            [[package]]
+            name = "odd"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+        "#).unwrap();
+
+        let generator = Generator {
+            lockfile,
+            cargo_home: dir.join("cargo"),
+            package: "app".to_owned(),
+            clarifications: Clarifications::default(),
+            manifest: None,
+            filter: Filter::default(),
+        };
+        let (source, warnings) = generator.render().unwrap();
+        assert!(source.contains("expr: Some(linfo::build::StaticExpr::Single(\
+            linfo::build::StaticLicense::Spdx(linfo::SpdxLicense::Mit, None)))"), "{}", source);
+        assert_eq!(warnings, [
+            "odd 1.0.0: invalid license 'MIT AND Not-A-License': \
+             'Not-A-License' is not a known license ID; using MIT detected from its license files",
+        ]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
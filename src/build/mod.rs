@@ -0,0 +1,158 @@
+//! Embedding the licenses of dependencies into a binary.
+//!
+//! A build script generates a table of every dependency's license, which is
+//! then included into the crate and rendered at runtime, e.g. for a
+//! `--licenses` flag.
+//!
+//! In `build.rs` (requires the `cargo` feature in `[build-dependencies]`):
+//!
+//! ```no_run
+//! # #[cfg(feature = "cargo")]
+//! linfo::build::generate().unwrap();
+//! ```
+//!
+//! In the crate:
+//!
+//! ```ignore
+//! use linfo::{build::Entry, bundle::Template};
+//!
+//! static LICENSES: &[Entry] = include!(concat!(env!("OUT_DIR"), "/licenses.rs"));
+//!
+//! fn print_licenses() {
+//!     print!("{}", linfo::build::render(LICENSES, &Template::TEXT));
+//! }
+//! ```
+
+use crate::{
+    bundle::{Bundle, LicenseFile, Package, Template},
+    expr::{And, Or},
+    spdx::{LicenseRef, SpdxException},
+    Expr,
+    License,
+    SpdxLicense,
+};
+
+#[cfg(feature = "cargo")]
+mod generate;
+#[cfg(feature = "cargo")]
+mod resolve;
+
+#[cfg(feature = "cargo")]
+#[doc(inline)]
+pub use self::generate::{generate, Error, Generator};
+
+/// The license of a dependency, as generated by a build script.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The name of the package.
+    pub name: &'static str,
+    /// The version of the package.
+    pub version: &'static str,
+    /// The license of the package, or `None` if it couldn't be determined.
+    pub expr: Option<StaticExpr>,
+    /// The package's license files.
+    pub license_files: &'static [StaticLicenseFile],
+    /// The contents of the package's `NOTICE` files.
+    pub notice: Option<&'static str>,
+}
+
+impl Entry {
+    /// Converts this entry into a package of a [`Bundle`](../bundle/struct.Bundle.html).
+    ///
+    /// Returns `None` if the license of the package is unknown.
+    pub fn to_package(&self) -> Option<Package> {
+        Some(Package {
+            name: self.name.to_owned(),
+            version: self.version.to_owned(),
+            expr: self.expr?.to_expr(),
+            license_files: self.license_files.iter()
+                .map(|file| LicenseFile {
+                    license: file.license.map(StaticLicense::to_license),
+                    text: file.text.to_owned(),
+                })
+                .collect(),
+            notice: self.notice.map(str::to_owned),
+        })
+    }
+}
+
+/// The contents of a license file that can be created in a `static`.
+///
+/// See [`LicenseFile`](../bundle/struct.LicenseFile.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StaticLicenseFile {
+    /// The license this is the text of, or `None` if it applies to every
+    /// license of the package.
+    pub license: Option<StaticLicense>,
+    /// The text of the file.
+    pub text: &'static str,
+}
+
+/// A license expression that can be created in a `static`.
+///
+/// See [`Expr`](../expr/enum.Expr.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StaticExpr {
+    /// Only one license's conditions to follow.
+    Single(StaticLicense),
+    /// The conditions of either of two or more expressions apply.
+    Or(&'static [StaticExpr]),
+    /// The conditions of all of two or more expressions apply.
+    And(&'static [StaticExpr]),
+}
+
+impl StaticExpr {
+    /// Converts this into an [`Expr`](../expr/enum.Expr.html).
+    pub fn to_expr(self) -> Expr {
+        let exprs = |list: &[StaticExpr]| list.iter().map(|e| e.to_expr()).collect();
+        match self {
+            StaticExpr::Single(l) => l.to_license().into(),
+            StaticExpr::Or(list) => Or::new(exprs(list)).into(),
+            StaticExpr::And(list) => And::new(exprs(list)).into(),
+        }
+    }
+}
+
+/// A license that can be created in a `static`, with the exception added to
+/// it via `WITH`, if any.
+///
+/// See [`License`](../enum.License.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StaticLicense {
+    /// A license listed [here](https://spdx.org/licenses).
+    Spdx(SpdxLicense, Option<SpdxException>),
+    /// A user-defined license, identified by its full `LicenseRef-` id.
+    Ref(&'static str, Option<SpdxException>),
+}
+
+impl StaticLicense {
+    /// Converts this into a [`License`](../enum.License.html).
+    pub fn to_license(self) -> License {
+        let (license, exception) = match self {
+            StaticLicense::Spdx(l, e) => (License::Spdx(l), e),
+            StaticLicense::Ref(id, e) => {
+                let l = LicenseRef::parse(id).unwrap_or_else(|_| LicenseRef::new(id));
+                (License::Ref(l), e)
+            },
+        };
+        match exception {
+            Some(e) => License::With(Box::new(license), e),
+            None => license,
+        }
+    }
+}
+
+/// Renders `entries` with `template`, grouping them by license.
+///
+/// Entries whose license is unknown are rendered in the `unknown` section of
+/// `template`.
+pub fn render(entries: &[Entry], template: &Template) -> String {
+    let packages: Vec<Package> = entries.iter()
+        .filter_map(Entry::to_package)
+        .collect();
+    let mut bundle = Bundle::new(&packages);
+    for entry in entries.iter().filter(|e| e.expr.is_none()) {
+        bundle.add_unknown(entry.name, entry.version);
+    }
+    bundle.render(template)
+}
@@ -0,0 +1,358 @@
+//! Determining which packages of a `Cargo.lock` are compiled into a package,
+//! from the manifests of the packages rather than `cargo metadata`.
+
+use std::{
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+use semver::{Version, VersionReq};
+use crate::cargo::{self, DependencyKind, Filter, LockPackage, Lockfile};
+use super::generate::{registry_dir, Error};
+
+/// A dependency declared in a manifest.
+#[derive(Debug)]
+struct Dependency {
+    /// The key of the dependency in its table, which differs from `name` if
+    /// the dependency is renamed.
+    key: String,
+    /// The name of the package.
+    name: String,
+    kind: DependencyKind,
+    /// The platform of a `[target.<platform>.*]` table.
+    target: Option<String>,
+    optional: bool,
+    default_features: bool,
+    features: Vec<String>,
+    /// The version requirement.
+    req: Option<String>,
+    /// The directory of a path dependency.
+    path: Option<PathBuf>,
+}
+
+/// The parts of a `Cargo.toml` that determine the dependencies of a package.
+#[derive(Debug)]
+struct Manifest {
+    features: BTreeMap<String, Vec<String>>,
+    dependencies: Vec<Dependency>,
+}
+
+impl Manifest {
+    /// Reads the manifest at `path`, resolving dependencies inherited from
+    /// the workspace.
+    fn read(path: &Path) -> Result<Self, Error> {
+        let toml = read_toml(path)?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        let features = toml.get("features")
+            .and_then(toml::Value::as_table)
+            .map(|features| {
+                features.iter()
+                    .map(|(name, entries)| (name.clone(), strings(Some(entries))))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut tables = vec![(None, &toml)];
+        if let Some(targets) = toml.get("target").and_then(toml::Value::as_table) {
+            tables.extend(targets.iter().map(|(platform, t)| (Some(platform), t)));
+        }
+
+        let mut workspace = None;
+        let mut dependencies = Vec::new();
+        for (target, table) in tables {
+            for &(name, kind) in &[
+                ("dependencies", DependencyKind::Normal),
+                ("dev-dependencies", DependencyKind::Dev),
+                ("dev_dependencies", DependencyKind::Dev),
+                ("build-dependencies", DependencyKind::Build),
+                ("build_dependencies", DependencyKind::Build),
+            ] {
+                let entries = match table.get(name).and_then(toml::Value::as_table) {
+                    Some(entries) => entries,
+                    None => continue,
+                };
+                for (key, value) in entries {
+                    let inherited = value.get("workspace").and_then(toml::Value::as_bool);
+                    let (base, base_dir) = if inherited == Some(true) {
+                        if workspace.is_none() {
+                            workspace = Some(find_workspace(dir)?);
+                        }
+                        let (root, toml) = workspace.as_ref().unwrap();
+                        let base = toml.get("workspace")
+                            .and_then(|w| w.get("dependencies"))
+                            .and_then(|d| d.get(key));
+                        (base, root.as_path())
+                    } else {
+                        (None, dir)
+                    };
+
+                    // Fields of the member take precedence over the workspace,
+                    // except for `features`, which are combined
+                    let field = |name: &str| {
+                        value.get(name).or_else(|| base.and_then(|b| b.get(name)))
+                    };
+                    let mut features = strings(value.get("features"));
+                    features.extend(strings(base.and_then(|b| b.get("features"))));
+                    let req = value.as_str()
+                        .or_else(|| base.and_then(toml::Value::as_str))
+                        .or_else(|| field("version").and_then(toml::Value::as_str));
+                    let path = match value.get("path").and_then(toml::Value::as_str) {
+                        Some(path) => Some(dir.join(path)),
+                        None => base.and_then(|b| b.get("path"))
+                            .and_then(toml::Value::as_str)
+                            .map(|path| base_dir.join(path)),
+                    };
+                    let default_features = field("default-features")
+                        .or_else(|| field("default_features"))
+                        .and_then(toml::Value::as_bool)
+                        .unwrap_or(true);
+
+                    dependencies.push(Dependency {
+                        key: key.clone(),
+                        name: field("package")
+                            .and_then(toml::Value::as_str)
+                            .unwrap_or(key)
+                            .to_owned(),
+                        kind,
+                        target: target.cloned(),
+                        optional: field("optional").and_then(toml::Value::as_bool).unwrap_or(false),
+                        default_features,
+                        features,
+                        req: req.map(str::to_owned),
+                        path,
+                    });
+                }
+            }
+        }
+
+        Ok(Self { features, dependencies })
+    }
+
+    /// Returns the names of the features of this package, including the
+    /// implicit features of optional dependencies.
+    fn feature_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.features.keys().map(String::as_str).collect();
+        for dep in self.dependencies.iter().filter(|d| d.optional) {
+            if !names.contains(&dep.key.as_str()) {
+                names.push(&dep.key);
+            }
+        }
+        names
+    }
+
+    /// Returns the keys of the optional dependencies enabled by `features`,
+    /// and the features to enable on each dependency.
+    fn activate<'a, I>(&'a self, features: I) -> (HashSet<&'a str>, HashMap<&'a str, Vec<&'a str>>)
+        where I: IntoIterator<Item = &'a str>
+    {
+        let mut activated = HashSet::new();
+        let mut dep_features: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut enabled = HashSet::new();
+        let mut pending: Vec<&str> = features.into_iter().collect();
+
+        while let Some(feature) = pending.pop() {
+            if let Some(dep) = feature.strip_prefix("dep:") {
+                activated.insert(dep);
+            } else if let Some((dep, feature)) = feature.split_once('/') {
+                // `dep?/feature` doesn't enable `dep` by itself
+                match dep.strip_suffix('?') {
+                    Some(dep) => dep_features.entry(dep).or_default().push(feature),
+                    None => {
+                        activated.insert(dep);
+                        dep_features.entry(dep).or_default().push(feature);
+                    },
+                }
+            } else if enabled.insert(feature) {
+                match self.features.get(feature) {
+                    Some(entries) => pending.extend(entries.iter().map(String::as_str)),
+                    // Optional dependencies are implicitly features
+                    None => { activated.insert(feature); },
+                }
+            }
+        }
+
+        (activated, dep_features)
+    }
+}
+
+/// Returns the name and version of every package compiled into `root`, whose
+/// manifest is at `manifest`.
+///
+/// Features are unified across the graph as Cargo does. The manifests of
+/// registry packages are read from `registries`, and those of path packages
+/// from the `path` of the dependency on them. All dependencies of a package
+/// whose manifest is unavailable, such as a git dependency, are included with
+/// all of their features.
+pub(super) fn included<'a>(
+    lockfile: &'a Lockfile,
+    root: &'a LockPackage,
+    manifest: &Path,
+    registries: &[PathBuf],
+    filter: &Filter,
+) -> Result<HashSet<(&'a str, &'a str)>, Error> {
+    let root = lockfile.position(&format!("{} {}", root.name, root.version))
+        .ok_or_else(|| Error::NoPackage(root.name.clone()))?;
+
+    let mut paths: HashMap<usize, PathBuf> = HashMap::new();
+    paths.insert(root, manifest.to_owned());
+    let mut manifests: HashMap<usize, Option<Manifest>> = HashMap::new();
+
+    let mut root_features: Vec<String> = filter.features.clone();
+    if !filter.no_default_features {
+        root_features.push("default".to_owned());
+    }
+    let mut features: HashMap<usize, BTreeSet<String>> = HashMap::new();
+    features.insert(root, root_features.into_iter().collect());
+    let mut all_features: HashSet<usize> = HashSet::new();
+    if filter.all_features {
+        all_features.insert(root);
+    }
+
+    let mut included = HashSet::new();
+    let mut pending = vec![root];
+    while let Some(index) = pending.pop() {
+        let package = &lockfile.packages[index];
+        let manifest = match manifests.entry(index) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let path = match paths.get(&index) {
+                    Some(path) => Some(path.clone()),
+                    None => registry_dir(registries, package).map(|dir| dir.join("Cargo.toml")),
+                };
+                entry.insert(match path {
+                    Some(path) if path.is_file() => Some(Manifest::read(&path)?),
+                    _ => None,
+                })
+            },
+        };
+
+        // Dependencies to enable with the features to request on them
+        let mut requests: Vec<(usize, Vec<String>, bool)> = Vec::new();
+        match manifest {
+            None => {
+                for dep in &package.dependencies {
+                    if let Some(dep) = lockfile.position(dep) {
+                        requests.push((dep, Vec::new(), true));
+                    }
+                }
+            },
+            Some(manifest) => {
+                let requested = &features[&index];
+                let (activated, dep_features) = if all_features.contains(&index) {
+                    manifest.activate(manifest.feature_names())
+                } else {
+                    manifest.activate(requested.iter().map(String::as_str))
+                };
+
+                for dep in &manifest.dependencies {
+                    let kind_admitted = filter.kinds.contains(&dep.kind) &&
+                        (dep.kind != DependencyKind::Dev || index == root);
+                    let target_admitted = match (&filter.target, &dep.target) {
+                        (Some(target), Some(platform)) => target.matches(platform),
+                        _ => true,
+                    };
+                    if !kind_admitted || !target_admitted ||
+                       (dep.optional && !activated.contains(dep.key.as_str())) {
+                        continue;
+                    }
+
+                    let mut dep_request: Vec<String> = dep.features.clone();
+                    if dep.default_features {
+                        dep_request.push("default".to_owned());
+                    }
+                    if let Some(extra) = dep_features.get(dep.key.as_str()) {
+                        dep_request.extend(extra.iter().map(|f| (*f).to_owned()));
+                    }
+
+                    for candidate in candidates(lockfile, package, dep) {
+                        if let Some(path) = &dep.path {
+                            if lockfile.packages[candidate].source.is_none() {
+                                paths.entry(candidate).or_insert_with(|| path.join("Cargo.toml"));
+                            }
+                        }
+                        requests.push((candidate, dep_request.clone(), false));
+                    }
+                }
+            },
+        }
+
+        for (dep, requested, all) in requests {
+            let mut changed = false;
+            if all {
+                changed |= all_features.insert(dep);
+            }
+            let enabled = features.entry(dep).or_default();
+            for feature in requested {
+                changed |= enabled.insert(feature);
+            }
+            let package = &lockfile.packages[dep];
+            changed |= included.insert((package.name.as_str(), package.version.as_str()));
+            if changed {
+                pending.push(dep);
+            }
+        }
+    }
+
+    Ok(included)
+}
+
+/// Returns the feature names of the package whose manifest is at `path`, such
+/// as to tell which are enabled from the environment of a build script.
+pub(super) fn feature_names(path: &Path) -> Result<Vec<String>, Error> {
+    let manifest = Manifest::read(path)?;
+    Ok(manifest.feature_names().into_iter().map(str::to_owned).collect())
+}
+
+/// Returns the packages in the lockfile that `dep` of `package` may refer to.
+///
+/// The version requirement tells apart multiple versions of the same package,
+/// such as a normal dependency and a build-dependency on different versions.
+fn candidates(lockfile: &Lockfile, package: &LockPackage, dep: &Dependency) -> Vec<usize> {
+    let mut candidates: Vec<usize> = package.dependencies.iter()
+        .filter(|d| d.split_whitespace().next() == Some(dep.name.as_str()))
+        .filter_map(|d| lockfile.position(d))
+        .collect();
+    if candidates.len() > 1 {
+        if let Some(req) = dep.req.as_deref().and_then(|r| VersionReq::parse(r).ok()) {
+            let matching: Vec<usize> = candidates.iter()
+                .cloned()
+                .filter(|&i| {
+                    Version::parse(&lockfile.packages[i].version).is_ok_and(|v| req.matches(&v))
+                })
+                .collect();
+            if !matching.is_empty() {
+                candidates = matching;
+            }
+        }
+    }
+    candidates
+}
+
+/// Returns the directory and manifest of the workspace containing `dir`.
+fn find_workspace(dir: &Path) -> Result<(PathBuf, toml::Value), Error> {
+    for dir in dir.ancestors() {
+        let path = dir.join("Cargo.toml");
+        if path.is_file() {
+            let toml = read_toml(&path)?;
+            if toml.get("workspace").is_some() {
+                return Ok((dir.to_owned(), toml));
+            }
+        }
+    }
+    Err(Error::NoWorkspace(dir.to_owned()))
+}
+
+fn read_toml(path: &Path) -> Result<toml::Value, Error> {
+    let toml = toml::from_str(&fs::read_to_string(path)?).map_err(cargo::Error::Toml)?;
+    Ok(toml)
+}
+
+/// Returns the strings in the array `value`.
+fn strings(value: Option<&toml::Value>) -> Vec<String> {
+    value.and_then(toml::Value::as_array)
+        .map(|array| {
+            array.iter().filter_map(toml::Value::as_str).map(str::to_owned).collect()
+        })
+        .unwrap_or_default()
+}
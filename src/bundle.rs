@@ -0,0 +1,489 @@
+//! Third-party license bundles, such as `THIRD-PARTY-LICENSES` files and
+//! "about" pages.
+//!
+//! A [`Bundle`](struct.Bundle.html) groups packages by license and includes
+//! each distinct license text once. Texts are compared ignoring whitespace, so
+//! the same license with different copyright lines is included once per
+//! copyright holder. Apache-2.0 `NOTICE` files are reproduced verbatim.
+//!
+//! ```
+//! use linfo::{bundle::{Bundle, LicenseFile, Package, Template}, Expr};
+//!
+//! let packages = vec![
+//!     Package {
+//!         name: "foo".to_owned(),
+//!         version: "1.0.0".to_owned(),
+//!         expr: Expr::parse("MIT").unwrap(),
+//!         license_files: vec![LicenseFile {
+//!             license: None,
+//!             text: "Copyright (c) Foo\n\nPermission is hereby granted...".to_owned(),
+//!         }],
+//!         notice: None,
+//!     },
+//! ];
+//!
+//! let bundle = Bundle::new(&packages);
+//! let markdown = bundle.render(&Template::MARKDOWN);
+//! assert!(markdown.contains("## MIT License"));
+//! ```
+
+use crate::{Expr, License};
+
+/// A package to include in a [`Bundle`](struct.Bundle.html).
+#[derive(Clone, Debug)]
+pub struct Package {
+    /// The name of the package.
+    pub name: String,
+    /// The version of the package.
+    pub version: String,
+    /// The licenses to attribute the package under.
+    ///
+    /// The package is listed under every license in this expression, so for
+    /// `OR` expressions this should be the chosen branch (see
+    /// [`solve`](../solve/index.html)). Packages with a
+    /// [clarification](../clarify/index.html) should use its license.
+    pub expr: Expr,
+    /// The license files distributed with the package.
+    pub license_files: Vec<LicenseFile>,
+    /// The contents of the package's `NOTICE` file, as required to be
+    /// reproduced by Apache-2.0.
+    pub notice: Option<String>,
+}
+
+/// The contents of a license file.
+#[derive(Clone, Debug)]
+pub struct LicenseFile {
+    /// The license this is the text of, or `None` if it applies to every
+    /// license of the package.
+    pub license: Option<License>,
+    /// The text of the file.
+    pub text: String,
+}
+
+/// Packages grouped by license, with deduplicated license texts.
+#[derive(Clone, Debug)]
+pub struct Bundle<'a> {
+    groups: Vec<Group<'a>>,
+    notices: Vec<Notice<'a>>,
+    unknown: Vec<(&'a str, &'a str)>,
+}
+
+/// The packages under a single license.
+#[derive(Clone, Debug)]
+pub struct Group<'a> {
+    /// The license of the packages.
+    pub license: &'a License,
+    /// Every package under `license`.
+    pub packages: Vec<&'a Package>,
+    /// The distinct texts of `license` found in the packages.
+    pub texts: Vec<Text<'a>>,
+}
+
+/// A distinct license text and the packages it was found in.
+#[derive(Clone, Debug)]
+pub struct Text<'a> {
+    /// The text, as found in the first package.
+    pub text: &'a str,
+    /// The packages containing this text.
+    pub packages: Vec<&'a Package>,
+}
+
+/// The contents of a `NOTICE` file and the packages it was found in.
+#[derive(Clone, Debug)]
+pub struct Notice<'a> {
+    /// The contents of the file, as found in the first package.
+    pub text: &'a str,
+    /// The packages containing this file.
+    pub packages: Vec<&'a Package>,
+}
+
+impl<'a> Bundle<'a> {
+    /// Groups `packages` by license, sorted by license ID.
+    pub fn new(packages: &'a [Package]) -> Self {
+        let mut groups: Vec<Group<'a>> = Vec::new();
+        let mut notices: Vec<Notice<'a>> = Vec::new();
+
+        for package in packages {
+            for license in package.expr.licenses() {
+                let group = match groups.iter().position(|g| g.license == license) {
+                    Some(i) => &mut groups[i],
+                    None => {
+                        groups.push(Group { license, packages: Vec::new(), texts: Vec::new() });
+                        groups.last_mut().unwrap()
+                    },
+                };
+                group.packages.push(package);
+
+                let files = package.license_files.iter().filter(|file| {
+                    file.license.as_ref().map_or(true, |l| l == license)
+                });
+                for file in files {
+                    let text = file.text.as_str();
+                    match group.texts.iter_mut().find(|t| same_text(t.text, text)) {
+                        Some(t) => t.packages.push(package),
+                        None => group.texts.push(Text { text, packages: vec![package] }),
+                    }
+                }
+            }
+
+            if let Some(text) = &package.notice {
+                match notices.iter_mut().find(|n| same_text(n.text, text)) {
+                    Some(n) => n.packages.push(package),
+                    None => notices.push(Notice { text, packages: vec![package] }),
+                }
+            }
+        }
+
+        groups.sort_by_cached_key(|g| g.license.to_string());
+        Self { groups, notices, unknown: Vec::new() }
+    }
+
+    /// Adds a package whose license couldn't be determined, which is listed
+    /// separately from the groups.
+    #[inline]
+    pub fn add_unknown(&mut self, name: &'a str, version: &'a str) {
+        self.unknown.push((name, version));
+    }
+
+    /// Returns the packages grouped by license.
+    #[inline]
+    pub fn groups(&self) -> &[Group<'a>] {
+        &self.groups
+    }
+
+    /// Returns the distinct `NOTICE` files.
+    #[inline]
+    pub fn notices(&self) -> &[Notice<'a>] {
+        &self.notices
+    }
+
+    /// Returns the name and version of each package whose license is unknown.
+    #[inline]
+    pub fn unknown(&self) -> &[(&'a str, &'a str)] {
+        &self.unknown
+    }
+
+    /// Renders this bundle with `template`.
+    pub fn render(&self, template: &Template) -> String {
+        let escape = template.escape;
+        let package = |name: &str, version: &str| fill(template.package, &[
+            ("name", &escape(name)),
+            ("version", &escape(version)),
+        ]);
+
+        let licenses: String = self.groups.iter().map(|group| {
+            let packages: String = group.packages.iter()
+                .map(|p| package(&p.name, &p.version))
+                .collect();
+            let texts: String = group.texts.iter()
+                .map(|t| fill(template.text, &[
+                    ("packages", &escape(&package_list(&t.packages))),
+                    ("text", &escape(t.text.trim_end())),
+                ]))
+                .collect();
+            // A license with an exception is its own group, so its heading
+            // and anchor must include the exception
+            let id = group.license.to_string();
+            let name = match group.license.exception() {
+                Some(e) => format!("{} with {}", group.license.name(), e.name()),
+                None => group.license.name().to_owned(),
+            };
+            fill(template.license, &[
+                ("id", &escape(&id)),
+                ("anchor", &escape(&id.replace(' ', "-"))),
+                ("name", &escape(&name)),
+                ("count", &group.packages.len().to_string()),
+                ("packages", &packages),
+                ("texts", &texts),
+            ])
+        }).collect();
+
+        let notices = if self.notices.is_empty() {
+            String::new()
+        } else {
+            let notices: String = self.notices.iter()
+                .map(|n| fill(template.notice, &[
+                    ("packages", &escape(&package_list(&n.packages))),
+                    ("text", &escape(n.text.trim_end())),
+                ]))
+                .collect();
+            fill(template.notices, &[("notices", &notices)])
+        };
+
+        let unknown = if self.unknown.is_empty() {
+            String::new()
+        } else {
+            let packages: String = self.unknown.iter()
+                .map(|&(name, version)| package(name, version))
+                .collect();
+            fill(template.unknown, &[("packages", &packages)])
+        };
+
+        fill(template.document, &[
+            ("licenses", &licenses),
+            ("unknown", &unknown),
+            ("notices", &notices),
+        ])
+    }
+}
+
+/// Returns whether `a` and `b` are the same text, ignoring whitespace.
+fn same_text(a: &str, b: &str) -> bool {
+    a.split_whitespace().eq(b.split_whitespace())
+}
+
+fn package_list(packages: &[&Package]) -> String {
+    packages.iter()
+        .map(|p| format!("{} {}", p.name, p.version))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Replaces each `{key}` in `template` with its value in `values`.
+///
+/// Unknown keys are left as-is and values are not themselves expanded.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let value = rest.find('}').and_then(|end| {
+            let key = &rest[1..end];
+            values.iter().find(|(k, _)| *k == key).map(|(_, v)| (end, *v))
+        });
+        match value {
+            Some((end, value)) => {
+                out.push_str(value);
+                rest = &rest[(end + 1)..];
+            },
+            None => {
+                out.push('{');
+                rest = &rest[1..];
+            },
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// How a [`Bundle`](struct.Bundle.html) is rendered.
+///
+/// Each field is a string with `{key}` placeholders:
+///
+/// | Field      | Placeholders                                                     |
+/// | ---------- | ---------------------------------------------------------------- |
+/// | `document` | `{licenses}`, `{unknown}`, `{notices}`                           |
+/// | `license`  | `{id}`, `{anchor}`, `{name}`, `{count}`, `{packages}`, `{texts}` |
+/// | `package`  | `{name}`, `{version}`                                            |
+/// | `text`     | `{packages}`, `{text}`                                           |
+/// | `unknown`  | `{packages}`                                                     |
+/// | `notices`  | `{notices}`                                                      |
+/// | `notice`   | `{packages}`, `{text}`                                           |
+///
+/// In `license`, `{id}` is the license with any exception, such as
+/// `GPL-2.0-only WITH Classpath-exception-2.0`, and `{anchor}` is the same
+/// without spaces for use as an HTML `id`.
+///
+/// `{licenses}`, `{packages}` (in `license` and `unknown`), `{texts}`,
+/// `{unknown}` and `{notices}` are the concatenated renderings of the
+/// corresponding fields. `unknown` and `notices` are only rendered if there
+/// are any such packages or notices. Every other value is passed through
+/// `escape`.
+#[derive(Clone, Copy, Debug)]
+pub struct Template<'a> {
+    /// The whole document.
+    pub document: &'a str,
+    /// A license and the packages under it.
+    pub license: &'a str,
+    /// A package under a license.
+    pub package: &'a str,
+    /// A distinct license text.
+    pub text: &'a str,
+    /// The section listing the packages whose license is unknown.
+    pub unknown: &'a str,
+    /// The section containing every `NOTICE` file.
+    pub notices: &'a str,
+    /// A distinct `NOTICE` file.
+    pub notice: &'a str,
+    /// Escapes values for the output format.
+    pub escape: fn(&str) -> String,
+}
+
+impl Template<'static> {
+    /// A plain text `THIRD-PARTY-LICENSES` file.
+    pub const TEXT: Self = Self {
+        document: "THIRD-PARTY LICENSES\n\n{licenses}{unknown}{notices}",
+        license: "\
+================================================================================
+{name} ({id})
+================================================================================
+
+Used by:
+{packages}
+{texts}",
+        package: "  - {name} {version}\n",
+        text: "\
+--------------------------------------------------------------------------------
+Used by: {packages}
+
+{text}
+
+",
+        unknown: "\
+================================================================================
+UNKNOWN LICENSES
+================================================================================
+
+{packages}
+",
+        notices: "\
+================================================================================
+NOTICES
+================================================================================
+
+{notices}",
+        notice: "\
+--------------------------------------------------------------------------------
+{packages}
+
+{text}
+
+",
+        escape: str::to_owned,
+    };
+
+    /// A Markdown document.
+    pub const MARKDOWN: Self = Self {
+        document: "# Third-Party Licenses\n\n{licenses}{unknown}{notices}",
+        license: "## {name}\n\n`{id}`, used by:\n\n{packages}\n{texts}",
+        package: "- {name} {version}\n",
+        text: "Used by {packages}:\n\n```text\n{text}\n```\n\n",
+        unknown: "## Unknown Licenses\n\n{packages}\n",
+        notices: "## Notices\n\n{notices}",
+        notice: "### {packages}\n\n```text\n{text}\n```\n\n",
+        escape: str::to_owned,
+    };
+
+    /// A standalone HTML page.
+    pub const HTML: Self = Self {
+        document: "\
+<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Third-Party Licenses</title>
+</head>
+<body>
+<h1>Third-Party Licenses</h1>
+{licenses}{unknown}{notices}</body>
+</html>
+",
+        license: "\
+<h2 id=\"{anchor}\">{name}</h2>
+<p><code>{id}</code>, used by:</p>
+<ul>
+{packages}</ul>
+{texts}",
+        package: "<li>{name} {version}</li>\n",
+        text: "<p>Used by {packages}:</p>\n<pre>{text}</pre>\n",
+        unknown: "<h2>Unknown Licenses</h2>\n<ul>\n{packages}</ul>\n",
+        notices: "<h2>Notices</h2>\n{notices}",
+        notice: "<h3>{packages}</h3>\n<pre>{text}</pre>\n",
+        escape: escape_html,
+    };
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpdxLicense;
+
+    fn package(name: &str, expr: &str, texts: &[&str], notice: Option<&str>) -> Package {
+        Package {
+            name: name.to_owned(),
+            version: "1.0.0".to_owned(),
+            expr: Expr::parse(expr).unwrap(),
+            license_files: texts.iter()
+                .map(|t| LicenseFile { license: None, text: (*t).to_owned() })
+                .collect(),
+            notice: notice.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn dedup_texts() {
+        let packages = [
+            package("a", "MIT", &["Copyright A\n\nPermission is granted"], None),
+            package("b", "MIT", &["Copyright A\nPermission  is granted\n"], None),
+            package("c", "MIT", &["Copyright C\n\nPermission is granted"], None),
+            package("d", "Apache-2.0", &["Apache License"], Some("D <d@example.com>")),
+        ];
+        let bundle = Bundle::new(&packages);
+
+        let groups = bundle.groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].license, &License::from(SpdxLicense::Apache2));
+        assert_eq!(groups[1].packages.len(), 3);
+        assert_eq!(groups[1].texts.len(), 2);
+        assert_eq!(groups[1].texts[0].packages.len(), 2);
+        assert_eq!(bundle.notices().len(), 1);
+
+        let html = bundle.render(&Template::HTML);
+        assert!(html.contains("<h2 id=\"MIT\">MIT License</h2>"));
+        assert!(html.contains("<pre>D &lt;d@example.com&gt;</pre>"));
+    }
+
+    #[test]
+    fn exceptions() {
+        let packages = [
+            package("a", "GPL-2.0-only", &[], None),
+            package("b", "GPL-2.0-only WITH Classpath-exception-2.0", &[], None),
+        ];
+        let html = Bundle::new(&packages).render(&Template::HTML);
+        assert!(html.contains("<h2 id=\"GPL-2.0-only\">GNU General Public License v2.0 only</h2>"));
+        assert!(html.contains(
+            "<h2 id=\"GPL-2.0-only-WITH-Classpath-exception-2.0\">\
+             GNU General Public License v2.0 only with Classpath exception 2.0</h2>\n\
+             <p><code>GPL-2.0-only WITH Classpath-exception-2.0</code>, used by:</p>"
+        ));
+    }
+
+    #[test]
+    fn unknown_packages() {
+        let packages = [package("a", "MIT", &[], None)];
+        let mut bundle = Bundle::new(&packages);
+        bundle.add_unknown("b&c", "0.1.0");
+
+        let html = bundle.render(&Template::HTML);
+        assert!(html.contains("<h2>Unknown Licenses</h2>\n<ul>\n<li>b&amp;c 0.1.0</li>\n</ul>\n"));
+        assert!(html.ends_with("</html>\n"));
+
+        let text = Bundle::new(&packages).render(&Template::TEXT);
+        assert!(!text.contains("UNKNOWN"));
+    }
+
+    #[test]
+    fn fill_placeholders() {
+        assert_eq!(fill("{a} {b} {c}", &[("a", "1"), ("b", "{a}")]), "1 {a} {c}");
+        assert_eq!(fill("{", &[]), "{");
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{Expr, SpdxLicense};
+
+/// Phrases that identify a license text regardless of its formatting. All
+/// phrases of an entry must be present for it to match.
+///
+/// Texts whose license cannot be told apart from another one (e.g. the GPL
+/// text is the same for `-only` and `-or-later`) are intentionally absent.
+const FINGERPRINTS: &[(&[&str], SpdxLicense)] = &[
+    (&["apache license", "version 2.0, january 2004"], SpdxLicense::Apache2),
+    (&["permission is hereby granted, free of charge, to any person obtaining a copy"], SpdxLicense::Mit),
+    (&["this is free and unencumbered software released into the public domain"], SpdxLicense::Unlicense),
+    (&["boost software license - version 1.0"], SpdxLicense::Bsl1),
+    (&["mozilla public license version 2.0"], SpdxLicense::Mpl2),
+    (&["cc0 1.0 universal"], SpdxLicense::CC01),
+    (&["permission to use, copy, modify, and/or distribute this software for any purpose with or without fee is hereby granted"], SpdxLicense::Isc),
+    (&["this software is provided 'as-is', without any express or implied warranty", "permission is granted to anyone to use this software for any purpose, including commercial applications"], SpdxLicense::Zlib),
+    (&["redistribution and use in source and binary forms", "neither the name of"], SpdxLicense::Bsd3Clause),
+    (&["redistribution and use in source and binary forms"], SpdxLicense::Bsd2Clause),
+];
+
+/// Attempts to detect the license expression of a license file's contents.
+///
+/// Detection is attempted in order of decreasing confidence:
+///
+/// 1. An `SPDX-License-Identifier:` tag.
+/// 2. A first line consisting of only a known license's ID or name.
+/// 3. Well-known phrases of commonly found license texts.
+///
+/// ```
+/// use linfo::{cargo, Expr, SpdxLicense};
+///
+/// let text = "// SPDX-License-Identifier: MIT OR Apache-2.0";
+/// assert_eq!(cargo::detect(text).unwrap(), *"MIT OR Apache-2.0");
+///
+/// let text = "MIT License\n\nCopyright (c) 2019 Nikolai Vazquez";
+/// assert_eq!(cargo::detect(text), Some(Expr::from(SpdxLicense::Mit)));
+/// ```
+pub fn detect(text: &str) -> Option<Expr> {
+    detect_tag(text)
+        .or_else(|| detect_title(text))
+        .or_else(|| detect_fingerprint(text))
+}
+
+fn detect_tag(text: &str) -> Option<Expr> {
+    const TAG: &str = "SPDX-License-Identifier:";
+
+    text.lines()
+        .filter_map(|line| line.find(TAG).map(|i| &line[(i + TAG.len())..]))
+        .map(|rest| rest.trim().trim_end_matches("*/").trim_end_matches("-->"))
+        .find_map(|expr| Expr::parse(expr).ok())
+}
+
+fn detect_title(text: &str) -> Option<Expr> {
+    let title = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+
+    SpdxLicense::all()
+        .find(|l| {
+            l.id().eq_ignore_ascii_case(title) ||
+            l.name().eq_ignore_ascii_case(title)
+        })
+        .map(Expr::from)
+}
+
+fn detect_fingerprint(text: &str) -> Option<Expr> {
+    // Normalize case and whitespace so that line wrapping doesn't matter
+    let text = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+
+    FINGERPRINTS.iter()
+        .find(|(phrases, _)| phrases.iter().all(|p| text.contains(p)))
+        .map(|&(_, l)| l.into())
+}
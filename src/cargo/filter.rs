@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+use super::{
+    DepKindInfo,
+    DependencyKind,
+    Metadata,
+    NodeDep,
+    Package,
+    PackageId,
+    ResolveNode,
+    Target,
+};
+
+/// Selects the dependencies that end up in a build artifact.
+///
+/// The default filter mirrors a plain `cargo build`: only normal dependencies
+/// with default features, for every target.
+///
+/// Features are only resolved for the workspace members. The features of other
+/// packages are taken from the resolve graph of `cargo metadata`, so it should
+/// be run with `--all-features` for any combination of features to be
+/// selectable here.
+///
+/// ```
+/// use linfo::cargo::{DependencyKind, Filter, Target};
+///
+/// let filter = Filter {
+///     target: Some(Target::from_triple("x86_64-unknown-linux-gnu")),
+///     kinds: vec![DependencyKind::Normal],
+///     features: vec!["serde".to_owned()],
+///     ..Filter::default()
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct Filter {
+    /// Only include dependencies that apply to this target, or dependencies
+    /// for all targets if `None`.
+    pub target: Option<Target>,
+    /// The kinds of dependencies to include.
+    ///
+    /// Build dependencies are compiled for the host rather than `target` but
+    /// are still filtered by it, as is done by `cargo metadata --filter-platform`.
+    pub kinds: Vec<DependencyKind>,
+    /// The features enabled on the workspace members, as with `--features`.
+    pub features: Vec<String>,
+    /// Whether all features of the workspace members are enabled, as with
+    /// `--all-features`.
+    pub all_features: bool,
+    /// Whether the `default` feature of the workspace members is disabled, as
+    /// with `--no-default-features`.
+    pub no_default_features: bool,
+}
+
+impl Default for Filter {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            target: None,
+            kinds: vec![DependencyKind::Normal],
+            features: Vec::new(),
+            all_features: false,
+            no_default_features: false,
+        }
+    }
+}
+
+impl Filter {
+    /// Returns the dependencies of `node` that are admitted by this filter.
+    ///
+    /// `positions` is the index of each package of `metadata` by its id.
+    pub(crate) fn dependencies<'a>(
+        &self,
+        metadata: &Metadata,
+        positions: &HashMap<&PackageId, usize>,
+        package: &Package,
+        node: &'a ResolveNode,
+    ) -> Vec<&'a PackageId> {
+        // Cargo prior to 1.41 doesn't report how dependencies are depended on
+        if node.deps.is_empty() {
+            return node.dependencies.iter().collect();
+        }
+
+        let activated = if metadata.workspace_members.contains(&package.id) {
+            Some(self.activated_dependencies(package))
+        } else {
+            None
+        };
+
+        node.deps.iter()
+            .filter(|dep| self.admits(metadata, positions, package, dep, activated.as_ref()))
+            .map(|dep| &dep.pkg)
+            .collect()
+    }
+
+    fn admits_kind(&self, info: &DepKindInfo) -> bool {
+        let target_matches = match (&self.target, &info.target) {
+            (Some(target), Some(platform)) => target.matches(platform),
+            _ => true,
+        };
+        target_matches && self.kinds.contains(&info.kind)
+    }
+
+    /// Returns whether `dep` is admitted, where `activated` is the set of
+    /// optional dependencies enabled on `package` if it is a workspace member.
+    ///
+    /// A dependency that matches no manifest entry is admitted, so that its
+    /// licenses are never left out.
+    fn admits(
+        &self,
+        metadata: &Metadata,
+        positions: &HashMap<&PackageId, usize>,
+        package: &Package,
+        dep: &NodeDep,
+        activated: Option<&HashSet<&str>>,
+    ) -> bool {
+        // `dep.name` is the name of the library target, which may differ from
+        // the package name, such as `md5` for `md-5`
+        let name = positions.get(&dep.pkg).map(|&i| metadata.packages[i].name.as_str());
+
+        dep.dep_kinds.iter()
+            .filter(|info| self.admits_kind(info))
+            .any(|info| {
+                let activated = match activated {
+                    Some(activated) => activated,
+                    None => return true,
+                };
+                let mut entries = package.dependencies.iter()
+                    .filter(|d| match name {
+                        Some(name) => {
+                            d.name == name && (d.rename.is_none() || d.code_name() == dep.name)
+                        },
+                        None => d.code_name() == dep.name,
+                    })
+                    .filter(|d| d.kind == info.kind && d.target == info.target)
+                    .peekable();
+                entries.peek().is_none() || entries.any(|d| {
+                    let key = d.rename.as_ref().unwrap_or(&d.name);
+                    !d.optional || activated.contains(key.as_str())
+                })
+            })
+    }
+
+    /// Returns the manifest keys of the optional dependencies of `package`
+    /// enabled by the selected features.
+    fn activated_dependencies<'a>(&'a self, package: &'a Package) -> HashSet<&'a str> {
+        let mut dependencies = HashSet::new();
+        let mut enabled = HashSet::new();
+        let mut pending: Vec<&str> = Vec::new();
+
+        if self.all_features {
+            pending.extend(package.features.keys().map(String::as_str));
+            pending.extend(package.dependencies.iter()
+                .filter(|d| d.optional)
+                .map(|d| d.rename.as_ref().unwrap_or(&d.name).as_str()));
+        } else {
+            pending.extend(self.features.iter().map(String::as_str));
+            if !self.no_default_features {
+                pending.push("default");
+            }
+        }
+
+        while let Some(feature) = pending.pop() {
+            if let Some(dep) = feature.strip_prefix("dep:") {
+                dependencies.insert(dep);
+            } else if let Some((dep, _)) = feature.split_once('/') {
+                // `dep?/feature` doesn't enable `dep` by itself
+                if !dep.ends_with('?') {
+                    dependencies.insert(dep);
+                }
+            } else if enabled.insert(feature) {
+                match package.features.get(feature) {
+                    Some(entries) => pending.extend(entries.iter().map(String::as_str)),
+                    // Optional dependencies are implicitly features
+                    None => { dependencies.insert(feature); },
+                }
+            }
+        }
+
+        dependencies
+    }
+}
@@ -0,0 +1,507 @@
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    fmt,
+    path::PathBuf,
+    rc::Rc,
+};
+use crate::{
+    clarify::{Clarifications, Stale},
+    spdx::LicenseKind,
+    Expr,
+    License,
+    LicenseField,
+    SpdxLicense,
+};
+use super::{Filter, Metadata, Package, PackageId, ResolveNode};
+
+/// A dependency graph where each package may have a known license.
+///
+/// This can be created from either [`Metadata`](struct.Metadata.html#method.graph)
+/// or a [`Lockfile`](struct.Lockfile.html#method.graph).
+#[derive(Clone, Debug)]
+pub struct Graph {
+    nodes: Vec<Node>,
+    roots: Vec<usize>,
+}
+
+/// A package in a [`Graph`](struct.Graph.html).
+#[derive(Clone, Debug)]
+pub struct Node {
+    /// The opaque identifier of the package.
+    pub id: PackageId,
+    /// The name of the package.
+    pub name: String,
+    /// The version of the package.
+    pub version: String,
+    /// The license of the package, which is `NOASSERTION` if unknown.
+    pub license: LicenseField,
+    /// The indices of the package's dependencies in
+    /// [`Graph::nodes`](struct.Graph.html#method.nodes).
+    pub dependencies: Vec<usize>,
+}
+
+impl fmt::Display for Node {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.version)
+    }
+}
+
+impl Graph {
+    #[inline]
+    pub(crate) fn new(nodes: Vec<Node>, roots: Vec<usize>) -> Self {
+        Self { nodes, roots }
+    }
+
+    /// Creates a dependency graph from the resolve graph of `metadata`, whose
+    /// roots are the workspace members.
+    ///
+    /// Every kind of dependency is included, for every target. See
+    /// [`from_metadata_filtered`](#method.from_metadata_filtered) to only
+    /// include those that end up in a build artifact.
+    ///
+    /// If `metadata` has no resolve graph (i.e. `--no-deps` was passed), the
+    /// graph will not have any edges.
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        Self::from_metadata_with(metadata, |_, _, node| node.dependencies.iter().collect())
+    }
+
+    /// Creates a dependency graph from the resolve graph of `metadata`, only
+    /// including the dependencies admitted by `filter`.
+    ///
+    /// The licenses of the packages [`reachable`](#method.reachable) from the
+    /// roots are then those that end up in the artifact.
+    ///
+    /// ```
+    /// use linfo::cargo::{Filter, Graph, Metadata, Target};
+    ///
+    /// # fn example(metadata: Metadata) {
+    /// let filter = Filter {
+    ///     target: Some(Target::from_triple("x86_64-unknown-linux-gnu")),
+    ///     ..Filter::default()
+    /// };
+    /// let graph = Graph::from_metadata_filtered(&metadata, &filter);
+    ///
+    /// for expr in graph.licenses() {
+    ///     println!("{}", expr);
+    /// }
+    /// # }
+    /// ```
+    pub fn from_metadata_filtered(metadata: &Metadata, filter: &Filter) -> Self {
+        Self::from_metadata_with(metadata, |positions, package, node| {
+            filter.dependencies(metadata, positions, package, node)
+        })
+    }
+
+    fn from_metadata_with<'a, F>(metadata: &'a Metadata, mut dependencies: F) -> Self
+    where
+        F: FnMut(&HashMap<&'a PackageId, usize>, &'a Package, &'a ResolveNode)
+            -> Vec<&'a PackageId>,
+    {
+        let report = metadata.report();
+        let positions = metadata.positions();
+        let position = |id: &PackageId| positions.get(id).copied();
+        let resolve_nodes: HashMap<&PackageId, &ResolveNode> = metadata.resolve.iter()
+            .flat_map(|r| r.nodes.iter())
+            .map(|node| (&node.id, node))
+            .collect();
+
+        let nodes = metadata.packages.iter()
+            .zip(report.packages())
+            .map(|(package, report)| {
+                let dependencies = resolve_nodes.get(&package.id)
+                    .map(|node| {
+                        dependencies(&positions, package, node).into_iter()
+                            .filter_map(position)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Node {
+                    id: package.id.clone(),
+                    name: package.name.clone(),
+                    version: package.version.clone(),
+                    license: report.license.as_ref()
+                        .map_or(LicenseField::NoAssertion, Clone::clone),
+                    dependencies,
+                }
+            })
+            .collect();
+
+        let roots = metadata.workspace_members.iter()
+            .filter_map(position)
+            .collect();
+
+        Self::new(nodes, roots)
+    }
+
+    /// Returns every package in the graph.
+    #[inline]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns the packages that the graph starts from, such as the workspace
+    /// members.
+    pub fn roots(&self) -> impl Iterator<Item = &Node> {
+        self.roots.iter().map(move |&i| &self.nodes[i])
+    }
+
+    /// Returns the packages reachable from the roots, including the roots.
+    pub fn reachable(&self) -> Vec<&Node> {
+        self.shortest_predecessors()
+            .iter()
+            .zip(&self.nodes)
+            .filter(|(p, _)| p.is_some())
+            .map(|(_, node)| node)
+            .collect()
+    }
+
+    /// Returns every distinct license expression of the packages reachable
+    /// from the roots, in order of appearance.
+    ///
+    /// The conditions of all of these expressions apply to the graph as a
+    /// whole. Packages without an expression are not accounted for.
+    pub fn licenses(&self) -> Vec<&Expr> {
+        let mut licenses: Vec<&Expr> = Vec::new();
+        for expr in self.reachable().into_iter().filter_map(|n| n.license.expr()) {
+            if !licenses.contains(&expr) {
+                licenses.push(expr);
+            }
+        }
+        licenses
+    }
+
+    /// Replaces the license of each package that has a clarification, where
+    /// `dir` returns the root directory of a package to read pinned files from.
+    ///
+    /// This is for graphs created from a [`Lockfile`](struct.Lockfile.html).
+    /// Those created from [`Metadata`](struct.Metadata.html) already use the
+    /// licenses of [`Metadata::clarify`](struct.Metadata.html#method.clarify).
+    ///
+    /// Returns the clarifications that no longer apply because their pinned
+    /// files changed. The licenses of those packages are left as is.
+    pub fn clarify<'c, F>(
+        &mut self,
+        clarifications: &'c Clarifications,
+        mut dir: F,
+    ) -> Vec<Stale<'c>>
+        where F: FnMut(&Node) -> Option<PathBuf>
+    {
+        let mut stale = Vec::new();
+        for node in &mut self.nodes {
+            let dir = dir(node);
+            let expr = clarifications
+                .clarified(&node.name, &node.version, dir.as_deref(), &mut stale);
+            if let Some(expr) = expr {
+                node.license = expr.clone().into();
+            }
+        }
+        stale
+    }
+
+    /// Returns every shortest dependency path from the roots to each package
+    /// whose license matches `query`.
+    ///
+    /// Packages that are not reachable from the roots are not included. The
+    /// paths of each [`Match`](struct.Match.html) are walked lazily, since
+    /// there may be exponentially many in graphs with many diamonds.
+    ///
+    /// ```
+    /// use linfo::{cargo::{Graph, Metadata}, SpdxLicense};
+    ///
+    /// # fn example(metadata: Metadata) {
+    /// let graph = Graph::from_metadata(&metadata);
+    /// let explanation = graph.explain(SpdxLicense::Gpl3Only);
+    ///
+    /// if !explanation.matches().is_empty() {
+    ///     println!("{}", explanation);
+    /// }
+    /// # }
+    /// ```
+    pub fn explain<Q: Into<Query>>(&self, query: Q) -> Explanation<'_> {
+        let query = query.into();
+        let predecessors: Rc<[Option<Vec<usize>>]> = self.shortest_predecessors().into();
+
+        let matches = self.nodes.iter()
+            .enumerate()
+            .filter(|(_, node)| {
+                node.license.expr().is_some_and(|e| query.matches(e))
+            })
+            .filter(|&(i, _)| predecessors[i].is_some())
+            .map(|(i, node)| Match {
+                package: node,
+                index: i,
+                nodes: &self.nodes,
+                predecessors: predecessors.clone(),
+            })
+            .collect();
+
+        Explanation { query, matches }
+    }
+
+    /// Returns, for each node reachable from the roots, the nodes that precede
+    /// it on a shortest path from the roots.
+    fn shortest_predecessors(&self) -> Vec<Option<Vec<usize>>> {
+        let mut distance = vec![None; self.nodes.len()];
+        let mut predecessors: Vec<Option<Vec<usize>>> = vec![None; self.nodes.len()];
+        let mut queue = VecDeque::new();
+
+        for &root in &self.roots {
+            if distance[root].is_none() {
+                distance[root] = Some(0);
+                predecessors[root] = Some(Vec::new());
+                queue.push_back(root);
+            }
+        }
+
+        while let Some(i) = queue.pop_front() {
+            let next = distance[i].unwrap_or(0) + 1;
+            for &dep in &self.nodes[i].dependencies {
+                match distance[dep] {
+                    None => {
+                        distance[dep] = Some(next);
+                        predecessors[dep] = Some(vec![i]);
+                        queue.push_back(dep);
+                    },
+                    Some(d) if d == next => {
+                        if let Some(p) = &mut predecessors[dep] {
+                            p.push(i);
+                        }
+                    },
+                    Some(_) => {},
+                }
+            }
+        }
+
+        predecessors
+    }
+}
+
+/// What to search for in the licenses of a [`Graph`](struct.Graph.html).
+///
+/// See [`Graph::explain`](struct.Graph.html#method.explain).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Query {
+    /// Any expression containing this license.
+    License(SpdxLicense),
+    /// Any expression containing a license of this kind.
+    Kind(LicenseKind),
+}
+
+impl From<SpdxLicense> for Query {
+    #[inline]
+    fn from(l: SpdxLicense) -> Self {
+        Self::License(l)
+    }
+}
+
+impl From<LicenseKind> for Query {
+    #[inline]
+    fn from(k: LicenseKind) -> Self {
+        Self::Kind(k)
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Query::License(l) => l.fmt(f),
+            Query::Kind(k) => write!(f, "{} license", k),
+        }
+    }
+}
+
+impl Query {
+    /// Returns whether any license in `expr` matches this query.
+    pub fn matches(&self, expr: &Expr) -> bool {
+        expr.licenses().any(|license| match (self, license.base()) {
+            (Query::License(l), License::Spdx(license)) => l == license,
+            (Query::Kind(k), License::Spdx(license)) => *k == license.kind(),
+            (Query::License(_), License::Ref(_)) => false,
+            (Query::Kind(k), License::Ref(_)) => *k == LicenseKind::Unknown,
+            (_, License::With(..)) => unreachable!("base license has no exception"),
+            (_, License::_NonExhaustive(never)) => never.consume(),
+        })
+    }
+}
+
+/// The dependency paths to each package matching a [`Query`](enum.Query.html).
+///
+/// The [`Display`](https://doc.rust-lang.org/std/fmt/trait.Display.html)
+/// implementation renders each package as an inverted tree of the packages
+/// that depend on it, similar to `cargo tree --invert`:
+///
+/// ```txt
+/// GPL-3.0-only: 1 package
+///
+/// foo 1.0.0 (GPL-3.0-only)
+/// └── bar 2.0.0
+///     └── app 0.1.0
+/// ```
+///
+/// As with `cargo tree`, a package whose dependents were already rendered is
+/// marked with `(*)` rather than rendered again.
+#[derive(Clone, Debug)]
+pub struct Explanation<'a> {
+    query: Query,
+    matches: Vec<Match<'a>>,
+}
+
+impl<'a> Explanation<'a> {
+    /// Returns what was searched for.
+    #[inline]
+    pub fn query(&self) -> Query {
+        self.query
+    }
+
+    /// Returns the packages that matched the query.
+    #[inline]
+    pub fn matches(&self) -> &[Match<'a>] {
+        &self.matches
+    }
+}
+
+impl fmt::Display for Explanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let count = self.matches.len();
+        let plural = if count == 1 { "" } else { "s" };
+        writeln!(f, "{}: {} package{}", self.query, count, plural)?;
+
+        for m in &self.matches {
+            writeln!(f)?;
+            m.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A package matching a [`Query`](enum.Query.html) and how it is reached.
+#[derive(Clone, Debug)]
+pub struct Match<'a> {
+    /// The matching package.
+    pub package: &'a Node,
+    index: usize,
+    nodes: &'a [Node],
+    // Shared by every match of an explanation
+    predecessors: Rc<[Option<Vec<usize>>]>,
+}
+
+impl<'a> Match<'a> {
+    /// Returns the packages that depend on `package` directly on a shortest
+    /// path from the roots, or none if it is a root.
+    pub fn parents(&self) -> impl Iterator<Item = &'a Node> + '_ {
+        self.parents_of(self.index).iter().map(move |&i| &self.nodes[i])
+    }
+
+    /// Returns every shortest path from a root to `package`, inclusive.
+    ///
+    /// Paths are created as they are iterated over, since there may be
+    /// exponentially many.
+    #[inline]
+    pub fn paths(&self) -> Paths<'_> {
+        let mut paths = Paths { m: self, chain: Vec::new(), done: false };
+        paths.descend(self.index);
+        paths
+    }
+
+    fn parents_of(&self, node: usize) -> &[usize] {
+        self.predecessors[node].as_deref().unwrap_or(&[])
+    }
+}
+
+impl fmt::Display for Match<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} ({})", self.package, self.package.license)?;
+
+        // Packages whose parents were already written are marked with `(*)`,
+        // which keeps the tree linear in the size of the graph
+        let tree = Tree { m: self, node: self.index };
+        tree.fmt_children(f, &mut String::new(), &mut BTreeSet::new())
+    }
+}
+
+/// An iterator over every shortest path to a package, created by
+/// [`Match::paths`](struct.Match.html#method.paths).
+#[derive(Clone, Debug)]
+pub struct Paths<'a> {
+    m: &'a Match<'a>,
+    // The path from the package to a root, with the index of the parent taken
+    // at each package
+    chain: Vec<(usize, usize)>,
+    done: bool,
+}
+
+impl Paths<'_> {
+    /// Extends the chain from `node` to a root through first parents.
+    fn descend(&mut self, mut node: usize) {
+        loop {
+            self.chain.push((node, 0));
+            match self.m.parents_of(node).first() {
+                Some(&parent) => node = parent,
+                None => break,
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Paths<'a> {
+    type Item = Vec<&'a Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let m = self.m;
+        let path = self.chain.iter().rev().map(|&(i, _)| &m.nodes[i]).collect();
+
+        // Take the next parent closest to a root, restarting from the first
+        // parent of every package after it
+        self.done = true;
+        while let Some((node, parent)) = self.chain.pop() {
+            if let Some(&next) = m.parents_of(node).get(parent + 1) {
+                self.chain.push((node, parent + 1));
+                self.descend(next);
+                self.done = false;
+                break;
+            }
+        }
+        Some(path)
+    }
+}
+
+struct Tree<'a> {
+    m: &'a Match<'a>,
+    node: usize,
+}
+
+impl Tree<'_> {
+    fn fmt_children(
+        &self,
+        f: &mut fmt::Formatter,
+        prefix: &mut String,
+        seen: &mut BTreeSet<usize>,
+    ) -> fmt::Result {
+        let parents = self.m.parents_of(self.node);
+        for (i, &parent) in parents.iter().enumerate() {
+            let last = i + 1 == parents.len();
+            let repeated = !self.m.parents_of(parent).is_empty() && !seen.insert(parent);
+            writeln!(
+                f,
+                "{}{}{}{}",
+                prefix,
+                if last { "└── " } else { "├── " },
+                self.m.nodes[parent],
+                if repeated { " (*)" } else { "" },
+            )?;
+            if repeated {
+                continue;
+            }
+
+            let old_len = prefix.len();
+            prefix.push_str(if last { "    " } else { "│   " });
+            Tree { m: self.m, node: parent }.fmt_children(f, prefix, seen)?;
+            prefix.truncate(old_len);
+        }
+        Ok(())
+    }
+}
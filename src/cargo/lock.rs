@@ -0,0 +1,130 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+};
+use serde::Deserialize;
+use crate::LicenseField;
+use super::{Error, Graph, Node, PackageId};
+
+/// The contents of a `Cargo.lock` file.
+///
+/// Unlike [`Metadata`](struct.Metadata.html), this does not contain the
+/// license of any package, so licenses must be provided when creating a
+/// [`Graph`](struct.Graph.html) via [`graph`](#method.graph).
+#[derive(Clone, Debug, Deserialize)]
+pub struct Lockfile {
+    /// Every package in the dependency graph.
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockPackage>,
+}
+
+impl Lockfile {
+    /// Parses the contents of a `Cargo.lock` file.
+    pub fn from_toml(toml: &str) -> Result<Self, Error> {
+        toml::from_str(toml).map_err(Error::Toml)
+    }
+
+    /// Parses the `Cargo.lock` file at `path`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    /// Returns the package referred to by `dependency`, which is formatted as
+    /// in [`LockPackage::dependencies`](struct.LockPackage.html#structfield.dependencies).
+    #[inline]
+    pub fn find(&self, dependency: &str) -> Option<&LockPackage> {
+        self.position(dependency).map(|i| &self.packages[i])
+    }
+
+    pub(crate) fn position(&self, dependency: &str) -> Option<usize> {
+        let (name, version) = split_dependency(dependency)?;
+        self.packages.iter().position(|p| {
+            p.name == name && version.map_or(true, |v| p.version == v)
+        })
+    }
+
+    /// Creates a dependency graph where the license of each package is
+    /// determined by `license`, such as an `Option<Expr>` that is
+    /// `NOASSERTION` when `None`.
+    ///
+    /// Packages without a `source` (i.e. path dependencies, including the
+    /// workspace members) are the roots of the graph.
+    pub fn graph<F, L>(&self, mut license: F) -> Graph
+    where
+        F: FnMut(&LockPackage) -> L,
+        L: Into<LicenseField>,
+    {
+        let mut by_name = HashMap::<&str, Vec<usize>>::new();
+        for (i, package) in self.packages.iter().enumerate() {
+            by_name.entry(&package.name).or_default().push(i);
+        }
+        let position = |dependency: &str| {
+            let (name, version) = split_dependency(dependency)?;
+            by_name.get(name)?.iter().copied().find(|&i| {
+                version.map_or(true, |v| self.packages[i].version == v)
+            })
+        };
+
+        let nodes = self.packages.iter().map(|package| {
+            let dependencies = package.dependencies.iter()
+                .filter_map(|dep| position(dep))
+                .collect();
+            Node {
+                id: package.id(),
+                name: package.name.clone(),
+                version: package.version.clone(),
+                license: license(package).into(),
+                dependencies,
+            }
+        }).collect();
+
+        let roots = self.packages.iter()
+            .enumerate()
+            .filter(|(_, p)| p.source.is_none())
+            .map(|(i, _)| i)
+            .collect();
+
+        Graph::new(nodes, roots)
+    }
+}
+
+/// Splits a dependency of a `Cargo.lock` package into its name and, if
+/// present, its version.
+fn split_dependency(dependency: &str) -> Option<(&str, Option<&str>)> {
+    let mut parts = dependency.split_whitespace();
+    let name = parts.next()?;
+    Some((name, parts.next()))
+}
+
+/// A package in a `Cargo.lock` file.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LockPackage {
+    /// The name of this package.
+    pub name: String,
+    /// The version of this package.
+    pub version: String,
+    /// Where this package comes from, or `None` for path dependencies.
+    pub source: Option<String>,
+    /// The checksum of the package's `.crate` file.
+    pub checksum: Option<String>,
+    /// The dependencies of this package, each formatted as `name`,
+    /// `name version`, or `name version (source)`.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+impl LockPackage {
+    /// Returns an identifier for this package, formatted similarly to those in
+    /// the output of `cargo metadata`.
+    pub fn id(&self) -> PackageId {
+        match &self.source {
+            Some(source) => {
+                PackageId(format!("{} {} ({})", self.name, self.version, source))
+            },
+            None => {
+                PackageId(format!("{} {}", self.name, self.version))
+            },
+        }
+    }
+}
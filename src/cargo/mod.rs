@@ -0,0 +1,779 @@
+//! Licenses of Cargo dependency graphs.
+//!
+//! This reads the output of `cargo metadata --format-version 1` and reports on
+//! the license of each package within it.
+//!
+//! ```no_run
+//! use linfo::cargo::Metadata;
+//!
+//! let metadata = Metadata::from_reader(std::io::stdin()).unwrap();
+//! let report = metadata.report();
+//!
+//! for package in report.packages() {
+//!     match &package.license {
+//!         Ok(field) => println!("{} {}: {}", package.name, package.version, field),
+//!         Err(error) => println!("{} {}: {}", package.name, package.version, error),
+//!     }
+//! }
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    error,
+    fmt,
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+use serde::{Deserialize, Deserializer};
+use crate::{
+    clarify::{Clarifications, Stale},
+    Expr,
+    LicenseField,
+};
+
+mod detect;
+mod filter;
+mod graph;
+mod lock;
+mod report;
+mod target;
+
+#[doc(inline)]
+pub use self::{
+    detect::detect,
+    filter::Filter,
+    graph::{Explanation, Graph, Match, Node, Paths, Query},
+    lock::{LockPackage, Lockfile},
+    report::{Aggregate, LicenseError, LicenseSource, PackageReport, Report},
+    target::Target,
+};
+
+/// The output of `cargo metadata --format-version 1`.
+///
+/// Only the fields relevant to licensing are retained.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Metadata {
+    /// Every package in the dependency graph, including workspace members.
+    pub packages: Vec<Package>,
+    /// The packages that are members of the workspace.
+    pub workspace_members: Vec<PackageId>,
+    /// The resolved dependency graph, or `None` if `--no-deps` was passed.
+    pub resolve: Option<Resolve>,
+}
+
+impl Metadata {
+    /// Parses the JSON output of `cargo metadata`.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(Error::Json)
+    }
+
+    /// Parses the JSON output of `cargo metadata` from `reader`, such as
+    /// [`io::stdin`](https://doc.rust-lang.org/std/io/fn.stdin.html).
+    pub fn from_reader<R: io::Read>(reader: R) -> Result<Self, Error> {
+        serde_json::from_reader(io::BufReader::new(reader)).map_err(Error::Json)
+    }
+
+    /// Parses the JSON output of `cargo metadata` stored at `path`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_reader(fs::File::open(path)?)
+    }
+
+    /// Returns the package identified by `id`.
+    pub fn package(&self, id: &PackageId) -> Option<&Package> {
+        self.packages.iter().find(|p| &p.id == id)
+    }
+
+    /// Returns the index of each package in `packages` by its id, for looking
+    /// up many packages.
+    pub(crate) fn positions(&self) -> HashMap<&PackageId, usize> {
+        self.packages.iter().enumerate().map(|(i, p)| (&p.id, i)).collect()
+    }
+
+    /// Reports the license of every package.
+    ///
+    /// Packages that only set `license-file` have that file read and scanned
+    /// via [`detect`](fn.detect.html).
+    pub fn report(&self) -> Report {
+        Report::new(self.packages.iter().map(PackageReport::new).collect())
+    }
+
+    /// Applies `clarifications` to every package, whose pinned files are read
+    /// from the package's directory.
+    ///
+    /// The [`report`](#method.report) and graphs created afterwards use the
+    /// clarified licenses. Returns the clarifications that no longer apply
+    /// because their pinned files changed. The licenses of those packages are
+    /// left as is.
+    pub fn clarify<'c>(&mut self, clarifications: &'c Clarifications) -> Vec<Stale<'c>> {
+        let mut stale = Vec::new();
+        for package in &mut self.packages {
+            let dir = package.manifest_path.parent();
+            package.clarified = clarifications
+                .clarified(&package.name, &package.version, dir, &mut stale)
+                .cloned();
+        }
+        stale
+    }
+
+    /// Creates a dependency graph with the license of each package.
+    ///
+    /// See [`Graph::from_metadata`](struct.Graph.html#method.from_metadata).
+    #[inline]
+    pub fn graph(&self) -> Graph {
+        Graph::from_metadata(self)
+    }
+
+    /// Creates a dependency graph with the license of each package, only
+    /// including the dependencies admitted by `filter`.
+    ///
+    /// See [`Graph::from_metadata_filtered`](struct.Graph.html#method.from_metadata_filtered).
+    #[inline]
+    pub fn filtered_graph(&self, filter: &Filter) -> Graph {
+        Graph::from_metadata_filtered(self, filter)
+    }
+}
+
+/// The resolved dependency graph in the output of `cargo metadata`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Resolve {
+    /// Every package in the dependency graph and its direct dependencies.
+    pub nodes: Vec<ResolveNode>,
+    /// The package in the current directory, if not a virtual workspace.
+    pub root: Option<PackageId>,
+}
+
+/// A package in the resolved dependency graph of `cargo metadata`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ResolveNode {
+    /// The opaque identifier of this package.
+    pub id: PackageId,
+    /// The direct dependencies of this package.
+    pub dependencies: Vec<PackageId>,
+    /// The direct dependencies of this package and how they are depended on.
+    ///
+    /// This is empty for versions of Cargo prior to 1.41.
+    #[serde(default)]
+    pub deps: Vec<NodeDep>,
+    /// The features enabled on this package.
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+/// A dependency of a [`ResolveNode`](struct.ResolveNode.html).
+#[derive(Clone, Debug, Deserialize)]
+pub struct NodeDep {
+    /// The name of the dependency as used in code, which accounts for renames
+    /// and replaces `-` with `_`.
+    pub name: String,
+    /// The opaque identifier of the dependency.
+    pub pkg: PackageId,
+    /// Every way in which the dependency is depended on.
+    pub dep_kinds: Vec<DepKindInfo>,
+}
+
+/// A way in which a [`NodeDep`](struct.NodeDep.html) is depended on.
+#[derive(Clone, Debug, Deserialize)]
+pub struct DepKindInfo {
+    /// The section the dependency is declared in.
+    pub kind: DependencyKind,
+    /// The platform the dependency is specific to, if any.
+    pub target: Option<String>,
+}
+
+/// The manifest section a dependency is declared in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// `[dependencies]`, which are linked into the artifact.
+    Normal,
+    /// `[dev-dependencies]`, which are only used by tests, examples and
+    /// benchmarks.
+    Dev,
+    /// `[build-dependencies]`, which are only used by build scripts.
+    Build,
+}
+
+impl<'de> Deserialize<'de> for DependencyKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        use serde::de::Error;
+
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            None | Some("normal") => Ok(DependencyKind::Normal),
+            Some("dev") => Ok(DependencyKind::Dev),
+            Some("build") => Ok(DependencyKind::Build),
+            Some(other) => Err(D::Error::unknown_variant(other, &["dev", "build"])),
+        }
+    }
+}
+
+/// A package in the output of `cargo metadata`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Package {
+    /// The opaque identifier of this package.
+    pub id: PackageId,
+    /// The name of this package.
+    pub name: String,
+    /// The version of this package.
+    pub version: String,
+    /// The `license` manifest field.
+    pub license: Option<String>,
+    /// The `license-file` manifest field, relative to the manifest.
+    pub license_file: Option<PathBuf>,
+    /// The path to the package's `Cargo.toml`.
+    pub manifest_path: PathBuf,
+    /// The dependencies declared in the package's manifest.
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
+    /// The `[features]` manifest table.
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
+    /// The license concluded by a [clarification](../clarify/index.html), as
+    /// set by [`Metadata::clarify`](struct.Metadata.html#method.clarify).
+    #[serde(skip)]
+    pub clarified: Option<Expr>,
+}
+
+impl Package {
+    /// Returns the path to `license_file`, resolved against the directory of
+    /// the manifest.
+    pub fn license_file_path(&self) -> Option<PathBuf> {
+        let file = self.license_file.as_ref()?;
+        let dir = self.manifest_path.parent().unwrap_or_else(|| Path::new(""));
+        Some(dir.join(file))
+    }
+
+    /// Parses the `license` manifest field, which may be `NONE` or
+    /// `NOASSERTION`.
+    ///
+    /// The deprecated `/` separator is treated as `OR`, as is done by Cargo.
+    pub fn parse_license(&self) -> Option<Result<LicenseField, LicenseError>> {
+        self.license.as_ref().map(|license| parse_license_field(license))
+    }
+}
+
+/// A dependency declared in the manifest of a [`Package`](struct.Package.html).
+#[derive(Clone, Debug, Deserialize)]
+pub struct Dependency {
+    /// The name of the dependency's package.
+    pub name: String,
+    /// The name the dependency is renamed to via `package = "..."`, if any.
+    pub rename: Option<String>,
+    /// The section the dependency is declared in.
+    pub kind: DependencyKind,
+    /// Whether the dependency is only enabled by a feature.
+    pub optional: bool,
+    /// The platform the dependency is specific to, if any.
+    pub target: Option<String>,
+}
+
+impl Dependency {
+    /// Returns the name of the dependency as used in code, which matches
+    /// [`NodeDep::name`](struct.NodeDep.html#structfield.name).
+    pub fn code_name(&self) -> String {
+        self.rename.as_ref().unwrap_or(&self.name).replace('-', "_")
+    }
+}
+
+/// The opaque identifier of a package in the output of `cargo metadata`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(transparent)]
+pub struct PackageId(pub String);
+
+impl fmt::Display for PackageId {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An error returned when reading the output of `cargo metadata` or a
+/// `Cargo.lock` file.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Io(io::Error),
+    /// The input is not valid JSON of the expected format.
+    Json(serde_json::Error),
+    /// The input is not valid TOML of the expected format.
+    Toml(toml::de::Error),
+}
+
+impl From<io::Error> for Error {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "failed to read input: {}", error),
+            Error::Json(error) => write!(f, "invalid metadata: {}", error),
+            Error::Toml(error) => write!(f, "invalid lockfile: {}", error),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::Json(error) => Some(error),
+            Error::Toml(error) => Some(error),
+        }
+    }
+}
+
+/// Parses a manifest `license` field, which may use the deprecated `/`
+/// separator in place of `OR`.
+pub(crate) fn parse_license_field(license: &str) -> Result<LicenseField, LicenseError> {
+    let expr = license.split('/').collect::<Vec<_>>().join(" OR ");
+    LicenseField::parse(expr.as_str()).map_err(|error| LicenseError::Invalid {
+        expr: license.to_owned(),
+        reason: error.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{spdx::LicenseKind, SpdxLicense};
+
+    const METADATA: &str = r#"{
+        "packages": [
+            {
+                "name": "app",
+                "version": "0.1.0",
+                "id": "app 0.1.0 (path+file:///app)",
+                "license": "MIT/Apache-2.0",
+                "license_file": null,
+                "manifest_path": "/app/Cargo.toml"
+            },
+            {
+                "name": "dep",
+                "version": "1.2.3",
+                "id": "dep 1.2.3 (registry+https://github.com/rust-lang/crates.io-index)",
+                "license": "MIT",
+                "license_file": null,
+                "manifest_path": "/registry/dep-1.2.3/Cargo.toml"
+            },
+            {
+                "name": "other",
+                "version": "0.0.1",
+                "id": "other 0.0.1 (registry+https://github.com/rust-lang/crates.io-index)",
+                "license": "Apache-2.0 OR MIT",
+                "license_file": null,
+                "manifest_path": "/registry/other-0.0.1/Cargo.toml"
+            },
+            {
+                "name": "unlicensed",
+                "version": "0.0.1",
+                "id": "unlicensed 0.0.1 (registry+https://github.com/rust-lang/crates.io-index)",
+                "license": null,
+                "license_file": null,
+                "manifest_path": "/registry/unlicensed-0.0.1/Cargo.toml"
+            }
+        ],
+        "workspace_members": ["app 0.1.0 (path+file:///app)"],
+        "target_directory": "/app/target",
+        "version": 1
+    }"#;
+
+    #[test]
+    fn report() {
+        let metadata = Metadata::from_json(METADATA).unwrap();
+        let report = metadata.report();
+
+        let licenses: Vec<_> = report.packages()
+            .iter()
+            .map(|p| p.license.as_ref().ok().map(|e| e.to_string()))
+            .collect();
+        assert_eq!(licenses, [
+            Some("MIT OR Apache-2.0".to_owned()),
+            Some("MIT".to_owned()),
+            Some("Apache-2.0 OR MIT".to_owned()),
+            None,
+        ]);
+
+        match report.packages()[3].license {
+            Err(LicenseError::Missing) => {},
+            ref other => panic!("expected missing license, got {:?}", other),
+        }
+
+        let groups = report.groups();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[1].expr, &Expr::from(SpdxLicense::Mit));
+        assert_eq!(groups[1].packages.len(), 1);
+
+        assert_eq!(report.aggregate().to_string(), "(MIT OR Apache-2.0) AND MIT");
+    }
+
+    #[test]
+    fn report_parenthesized() {
+        let package = |name: &str, license: &str| format!(r#"{{
+            "name": "{0}",
+            "version": "1.0.0",
+            "id": "{0} 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+            "license": "{1}",
+            "license_file": null,
+            "manifest_path": "/registry/{0}-1.0.0/Cargo.toml"
+        }}"#, name, license);
+        let metadata = Metadata::from_json(&format!(
+            r#"{{ "packages": [{}, {}, {}], "workspace_members": [] }}"#,
+            package("either", "(MIT OR Apache-2.0)"),
+            package("ring", "(MIT AND ISC) AND OpenSSL"),
+            package("unicode-ident", "(MIT OR Apache-2.0) AND Unicode-DFS-2016"),
+        )).unwrap();
+        let report = metadata.report();
+
+        let licenses: Vec<_> = report.packages()
+            .iter()
+            .map(|p| p.license.as_ref().ok().map(|e| e.to_string()))
+            .collect();
+        assert_eq!(licenses, [
+            Some("MIT OR Apache-2.0".to_owned()),
+            Some("MIT AND ISC AND OpenSSL".to_owned()),
+            Some("(MIT OR Apache-2.0) AND Unicode-DFS-2016".to_owned()),
+        ]);
+
+        assert_eq!(
+            report.aggregate().to_string(),
+            "(MIT OR Apache-2.0) AND MIT AND ISC AND OpenSSL AND Unicode-DFS-2016",
+        );
+    }
+
+    #[test]
+    fn report_special_values() {
+        let package = |name: &str, license: &str| format!(r#"{{
+            "name": "{0}",
+            "version": "1.0.0",
+            "id": "{0} 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+            "license": "{1}",
+            "license_file": null,
+            "manifest_path": "/registry/{0}-1.0.0/Cargo.toml"
+        }}"#, name, license);
+        let json = |packages: &[String]| format!(
+            r#"{{ "packages": [{}], "workspace_members": [] }}"#,
+            packages.join(", "),
+        );
+        let none = package("none", "NONE");
+        let unknown = package("unknown", "NOASSERTION");
+        let mit = package("mit", "MIT");
+
+        let metadata = Metadata::from_json(&json(&[none.clone(), unknown, mit.clone()])).unwrap();
+        let report = metadata.report();
+        assert_eq!(report.errors().count(), 0);
+        assert_eq!(report.packages()[0].license.as_ref().unwrap(), &LicenseField::None);
+        assert_eq!(report.packages()[1].to_string(), "unknown 1.0.0: NOASSERTION");
+        assert_eq!(report.groups().len(), 1);
+        assert_eq!(report.aggregate(), LicenseField::NoAssertion);
+        assert_eq!(metadata.graph().nodes()[0].license, LicenseField::None);
+
+        let metadata = Metadata::from_json(&json(&[none.clone(), mit])).unwrap();
+        assert_eq!(metadata.report().aggregate(), *"MIT");
+
+        let metadata = Metadata::from_json(&json(&[none])).unwrap();
+        assert_eq!(metadata.report().aggregate(), LicenseField::None);
+    }
+
+    #[test]
+    fn clarify_report() {
+        use crate::clarify::Clarifications;
+
+        let clarifications: Clarifications = toml::from_str(r#"
+            [[clarify]]
+            name = "unlicensed"
+            version = "0.0.*"
+            expr = "MIT"
+
+            [[clarify]]
+            name = "dep"
+            version = "1"
+            expr = "BSD-3-Clause"
+            files = [{ path = "LICENSE", sha256 = "00" }]
+        "#).unwrap();
+
+        let mut metadata = Metadata::from_json(METADATA).unwrap();
+        let stale = metadata.clarify(&clarifications);
+
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].clarification.name, "dep");
+
+        let report = metadata.report();
+        assert_eq!(report.packages()[1].license.as_ref().unwrap(), "MIT");
+
+        let unlicensed = &report.packages()[3];
+        assert_eq!(unlicensed.source, Some(LicenseSource::Clarification));
+        assert_eq!(unlicensed.license.as_ref().unwrap(), "MIT");
+
+        let groups = report.groups();
+        let mit = groups.iter().find(|a| a.expr == &Expr::from(SpdxLicense::Mit)).unwrap();
+        assert!(mit.packages.iter().any(|p| p.name == "unlicensed"));
+
+        let graph = metadata.graph();
+        assert_eq!(graph.nodes()[3].license, *"MIT");
+    }
+
+    #[test]
+    fn detect_license_text() {
+        let apache = "
+                                 Apache License
+                           Version 2.0, January 2004
+                        http://www.apache.org/licenses/
+        ";
+        assert_eq!(detect(apache), Some(SpdxLicense::Apache2.into()));
+
+        let mit = "Copyright (c) 2019
+
+            Permission is hereby granted, free of charge, to any person
+            obtaining a copy of this software";
+        assert_eq!(detect(mit), Some(SpdxLicense::Mit.into()));
+
+        assert_eq!(detect("All rights reserved."), None);
+    }
+
+    #[test]
+    fn explain_lockfile() {
+        const LOCKFILE: &str = r#"
+            [[package]]
+            name = "app"
+            version = "0.1.0"
+            dependencies = ["a", "b 1.0.0"]
+
+            [[package]]
+            name = "a"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+            dependencies = ["gpl"]
+
+            [[package]]
+            name = "b"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+            dependencies = ["a", "gpl 2.0.0 (registry+https://github.com/rust-lang/crates.io-index)"]
+
+            [[package]]
+            name = "gpl"
+            version = "2.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+        "#;
+
+        let lockfile = Lockfile::from_toml(LOCKFILE).unwrap();
+        let graph = lockfile.graph(|package| match package.name.as_str() {
+            "gpl" => Some(SpdxLicense::Gpl3Only.into()),
+            _ => Some(SpdxLicense::Mit.into()),
+        });
+
+        let explanation = graph.explain(SpdxLicense::Gpl3Only);
+        assert_eq!(explanation.matches().len(), 1);
+        assert_eq!(explanation.matches()[0].paths().count(), 2);
+        assert_eq!(explanation.to_string(), "\
+GPL-3.0-only: 1 package
+
+gpl 2.0.0 (GPL-3.0-only)
+├── a 1.0.0
+│   └── app 0.1.0
+└── b 1.0.0
+    └── app 0.1.0
+");
+
+        let explanation = graph.explain(LicenseKind::StrongCopyleft);
+        assert_eq!(explanation.matches().len(), 1);
+
+        let explanation = graph.explain(LicenseKind::NetworkCopyleft);
+        assert!(explanation.matches().is_empty());
+    }
+
+    #[test]
+    fn explain_diamonds() {
+        const LEVELS: usize = 4;
+
+        // Each level has two packages depending on the same package below
+        let mut lockfile = String::from(r#"
+            [[package]]
+            name = "l0"
+            version = "1.0.0"
+            dependencies = ["a1", "b1"]
+        "#);
+        for i in 1..=LEVELS {
+            let next = if i == LEVELS { "gpl".to_owned() } else { format!("l{}", i) };
+            for name in &["a", "b"] {
+                lockfile.push_str(&format!(r#"
+                    [[package]]
+                    name = "{}{}"
+                    version = "1.0.0"
+                    source = "registry+https://github.com/rust-lang/crates.io-index"
+                    dependencies = ["{}"]
+                "#, name, i, next));
+            }
+            if i < LEVELS {
+                lockfile.push_str(&format!(r#"
+                    [[package]]
+                    name = "l{}"
+                    version = "1.0.0"
+                    source = "registry+https://github.com/rust-lang/crates.io-index"
+                    dependencies = ["a{}", "b{}"]
+                "#, i, i + 1, i + 1));
+            }
+        }
+        lockfile.push_str(r#"
+            [[package]]
+            name = "gpl"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+        "#);
+
+        let graph = Lockfile::from_toml(&lockfile).unwrap().graph(|package| {
+            match package.name.as_str() {
+                "gpl" => Some(SpdxLicense::Gpl3Only.into()),
+                _ => Some(SpdxLicense::Mit.into()),
+            }
+        });
+        let explanation = graph.explain(SpdxLicense::Gpl3Only);
+        let m = &explanation.matches()[0];
+        let parents: Vec<&str> = m.parents().map(|n| n.name.as_str()).collect();
+        assert_eq!(parents, ["a4", "b4"]);
+
+        // Every one of the 2^4 chains is walked, each exactly once
+        let names = |path: Vec<&Node>| -> Vec<String> {
+            path.iter().map(|n| n.name.clone()).collect()
+        };
+        let paths: Vec<Vec<String>> = m.paths().map(names).collect();
+        assert_eq!(paths.len(), 1 << LEVELS);
+        assert_eq!(paths[0], ["l0", "a1", "l1", "a2", "l2", "a3", "l3", "a4", "gpl"]);
+        assert_eq!(paths[1], ["l0", "b1", "l1", "a2", "l2", "a3", "l3", "a4", "gpl"]);
+        for (i, path) in paths.iter().enumerate() {
+            assert!(!paths[(i + 1)..].contains(path), "{:?} is repeated", path);
+        }
+
+        assert_eq!(explanation.to_string(), "\
+GPL-3.0-only: 1 package
+
+gpl 1.0.0 (GPL-3.0-only)
+├── a4 1.0.0
+│   └── l3 1.0.0
+│       ├── a3 1.0.0
+│       │   └── l2 1.0.0
+│       │       ├── a2 1.0.0
+│       │       │   └── l1 1.0.0
+│       │       │       ├── a1 1.0.0
+│       │       │       │   └── l0 1.0.0
+│       │       │       └── b1 1.0.0
+│       │       │           └── l0 1.0.0
+│       │       └── b2 1.0.0
+│       │           └── l1 1.0.0 (*)
+│       └── b3 1.0.0
+│           └── l2 1.0.0 (*)
+└── b4 1.0.0
+    └── l3 1.0.0 (*)
+");
+    }
+
+    #[test]
+    fn filtered_graph() {
+        const METADATA: &str = r#"{
+            "packages": [
+                {
+                    "name": "app", "version": "0.1.0", "id": "app",
+                    "license": "MIT", "manifest_path": "/app/Cargo.toml",
+                    "dependencies": [
+                        { "name": "normal", "rename": null, "kind": null, "optional": false, "target": null },
+                        { "name": "opt", "rename": null, "kind": null, "optional": true, "target": null },
+                        { "name": "win", "rename": null, "kind": null, "optional": false, "target": "cfg(windows)" },
+                        { "name": "dev", "rename": null, "kind": "dev", "optional": false, "target": null },
+                        { "name": "build", "rename": null, "kind": "build", "optional": false, "target": null },
+                        { "name": "md-5", "rename": null, "kind": null, "optional": false, "target": null }
+                    ],
+                    "features": { "default": [], "extra": ["dep:opt"] }
+                },
+                { "name": "normal", "version": "1.0.0", "id": "normal", "license": "Apache-2.0", "manifest_path": "/normal/Cargo.toml" },
+                { "name": "opt", "version": "1.0.0", "id": "opt", "license": "MPL-2.0", "manifest_path": "/opt/Cargo.toml" },
+                { "name": "win", "version": "1.0.0", "id": "win", "license": "ISC", "manifest_path": "/win/Cargo.toml" },
+                { "name": "dev", "version": "1.0.0", "id": "dev", "license": "GPL-3.0-only", "manifest_path": "/dev/Cargo.toml" },
+                { "name": "build", "version": "1.0.0", "id": "build", "license": "LGPL-2.1-only", "manifest_path": "/build/Cargo.toml" },
+                { "name": "md-5", "version": "0.10.0", "id": "md-5", "license": "MIT OR Apache-2.0", "manifest_path": "/md-5/Cargo.toml" }
+            ],
+            "workspace_members": ["app"],
+            "resolve": {
+                "nodes": [
+                    {
+                        "id": "app",
+                        "dependencies": ["normal", "opt", "win", "dev", "build", "md-5"],
+                        "deps": [
+                            { "name": "normal", "pkg": "normal", "dep_kinds": [{ "kind": null, "target": null }] },
+                            { "name": "opt", "pkg": "opt", "dep_kinds": [{ "kind": null, "target": null }] },
+                            { "name": "win", "pkg": "win", "dep_kinds": [{ "kind": null, "target": "cfg(windows)" }] },
+                            { "name": "dev", "pkg": "dev", "dep_kinds": [{ "kind": "dev", "target": null }] },
+                            { "name": "build", "pkg": "build", "dep_kinds": [{ "kind": "build", "target": null }] },
+                            { "name": "md5", "pkg": "md-5", "dep_kinds": [{ "kind": null, "target": null }] }
+                        ],
+                        "features": ["default", "extra"]
+                    },
+                    { "id": "normal", "dependencies": [], "deps": [], "features": [] },
+                    { "id": "opt", "dependencies": [], "deps": [], "features": [] },
+                    { "id": "win", "dependencies": [], "deps": [], "features": [] },
+                    { "id": "dev", "dependencies": [], "deps": [], "features": [] },
+                    { "id": "build", "dependencies": [], "deps": [], "features": [] },
+                    { "id": "md-5", "dependencies": [], "deps": [], "features": [] }
+                ],
+                "root": "app"
+            }
+        }"#;
+
+        fn names(graph: &Graph) -> Vec<&str> {
+            graph.reachable().iter().map(|n| n.name.as_str()).collect()
+        }
+
+        let metadata = Metadata::from_json(METADATA).unwrap();
+        assert_eq!(
+            names(&metadata.graph()),
+            ["app", "normal", "opt", "win", "dev", "build", "md-5"],
+        );
+
+        let linux = Filter {
+            target: Some(Target::from_triple("x86_64-unknown-linux-gnu")),
+            ..Filter::default()
+        };
+        let graph = metadata.filtered_graph(&linux);
+        // `md-5` is depended on by its library name `md5`
+        assert_eq!(names(&graph), ["app", "normal", "md-5"]);
+        let licenses: Vec<String> = graph.licenses().iter().map(|e| e.to_string()).collect();
+        assert_eq!(licenses, ["MIT", "Apache-2.0", "MIT OR Apache-2.0"]);
+
+        let windows = Filter {
+            target: Some(Target::from_triple("x86_64-pc-windows-msvc")),
+            kinds: vec![DependencyKind::Normal, DependencyKind::Build],
+            features: vec!["extra".to_owned()],
+            ..Filter::default()
+        };
+        assert_eq!(
+            names(&metadata.filtered_graph(&windows)),
+            ["app", "normal", "opt", "win", "build", "md-5"],
+        );
+    }
+
+    #[test]
+    fn target_cfg() {
+        let linux = Target::from_triple("x86_64-unknown-linux-gnu");
+        assert_eq!(linux.os, "linux");
+        assert_eq!(linux.env, "gnu");
+        assert!(linux.matches("cfg(unix)"));
+        assert!(linux.matches("cfg(any(windows, target_os = \"linux\"))"));
+        assert!(linux.matches("cfg(not(target_pointer_width = \"32\"))"));
+        assert!(linux.matches("x86_64-unknown-linux-gnu"));
+        assert!(!linux.matches("cfg(all(unix, target_arch = \"aarch64\"))"));
+        assert!(!linux.matches("cfg(unix"));
+
+        let android = Target::from_triple("aarch64-linux-android");
+        assert_eq!(android.os, "android");
+        assert!(android.matches("cfg(target_os = \"android\")"));
+
+        let wasm = Target::from_triple("wasm32-unknown-unknown");
+        assert!(wasm.matches("cfg(target_family = \"wasm\")"));
+        assert!(!wasm.matches("cfg(unix)"));
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    error,
+    fmt,
+    fs,
+    io,
+    path::PathBuf,
+};
+use crate::{expr::And, Expr, LicenseField};
+use super::{detect, Package, PackageId};
+
+/// The licenses of every package in a dependency graph.
+///
+/// See [`Metadata::report`](struct.Metadata.html#method.report).
+#[derive(Debug)]
+pub struct Report {
+    packages: Vec<PackageReport>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for package in &self.packages {
+            writeln!(f, "{}", package)?;
+        }
+        Ok(())
+    }
+}
+
+impl Report {
+    #[inline]
+    pub(crate) fn new(packages: Vec<PackageReport>) -> Self {
+        Self { packages }
+    }
+
+    /// Returns the report of each package.
+    #[inline]
+    pub fn packages(&self) -> &[PackageReport] {
+        &self.packages
+    }
+
+    /// Returns the packages whose license could not be determined.
+    pub fn errors(&self) -> impl Iterator<Item = (&PackageReport, &LicenseError)> {
+        self.packages.iter().filter_map(|p| match &p.license {
+            Ok(_) => None,
+            Err(error) => Some((p, error)),
+        })
+    }
+
+    /// Returns the license of the dependency graph as a whole: the `AND` of
+    /// every package's expression.
+    ///
+    /// Equivalent expressions are only included once. Packages in
+    /// [`errors`](#method.errors) and those without a license (`NONE`) are
+    /// left out. The result is `NOASSERTION` if any package's license is
+    /// `NOASSERTION`, since the graph as a whole is then unknown, and `NONE`
+    /// if no package has an expression.
+    pub fn aggregate(&self) -> LicenseField {
+        let unknown = self.packages.iter()
+            .any(|p| p.license.as_ref().is_ok_and(LicenseField::is_no_assertion));
+        if unknown {
+            return LicenseField::NoAssertion;
+        }
+
+        let mut operands: Vec<Expr> = Vec::new();
+        for group in self.groups() {
+            let exprs = match group.expr {
+                Expr::And(and) => and.as_slice(),
+                expr => core::slice::from_ref(expr),
+            };
+            for expr in exprs {
+                if !operands.iter().any(|e| e.is_equivalent(expr)) {
+                    operands.push(expr.clone());
+                }
+            }
+        }
+        match operands.len() {
+            0 => LicenseField::None,
+            1 => LicenseField::Expr(operands.remove(0)),
+            _ => LicenseField::Expr(And::new(operands).into()),
+        }
+    }
+
+    /// Returns every distinct license expression in the dependency graph
+    /// alongside the packages that use it, in order of first appearance.
+    ///
+    /// The conditions of all of these expressions apply to the dependency graph
+    /// as a whole, as in [`aggregate`](#method.aggregate). Packages without an
+    /// expression are left out.
+    pub fn groups(&self) -> Vec<Aggregate<'_>> {
+        let mut aggregate: Vec<Aggregate> = Vec::new();
+
+        for package in &self.packages {
+            let expr = match &package.license {
+                Ok(LicenseField::Expr(expr)) => expr,
+                _ => continue,
+            };
+            match aggregate.iter_mut().find(|a| a.expr == expr) {
+                Some(a) => a.packages.push(package),
+                None => aggregate.push(Aggregate {
+                    expr,
+                    packages: vec![package],
+                }),
+            }
+        }
+
+        aggregate
+    }
+}
+
+/// A license expression and the packages that use it.
+///
+/// See [`Report::groups`](struct.Report.html#method.groups).
+#[derive(Clone, Debug)]
+pub struct Aggregate<'a> {
+    /// The license expression.
+    pub expr: &'a Expr,
+    /// The packages licensed under `expr`.
+    pub packages: Vec<&'a PackageReport>,
+}
+
+/// The license of a single package.
+#[derive(Debug)]
+pub struct PackageReport {
+    /// The opaque identifier of the package.
+    pub id: PackageId,
+    /// The name of the package.
+    pub name: String,
+    /// The version of the package.
+    pub version: String,
+    /// Where the license was read from, if anywhere.
+    pub source: Option<LicenseSource>,
+    /// The license of the package, which is only `NONE` or `NOASSERTION` if
+    /// the `license` field says so.
+    pub license: Result<LicenseField, LicenseError>,
+}
+
+impl fmt::Display for PackageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}: ", self.name, self.version)?;
+        match &self.license {
+            Ok(field) => field.fmt(f),
+            Err(error) => write!(f, "error: {}", error),
+        }
+    }
+}
+
+impl PackageReport {
+    pub(crate) fn new(package: &Package) -> Self {
+        let (source, license) = if let Some(expr) = &package.clarified {
+            (Some(LicenseSource::Clarification), Ok(expr.clone().into()))
+        } else if let Some(license) = package.parse_license() {
+            (Some(LicenseSource::Field), license)
+        } else if let Some(path) = package.license_file_path() {
+            let license = read_license_file(path.clone());
+            (Some(LicenseSource::File(path)), license)
+        } else {
+            (None, Err(LicenseError::Missing))
+        };
+
+        Self {
+            id: package.id.clone(),
+            name: package.name.clone(),
+            version: package.version.clone(),
+            source,
+            license,
+        }
+    }
+}
+
+fn read_license_file(path: PathBuf) -> Result<LicenseField, LicenseError> {
+    match fs::read_to_string(&path) {
+        Ok(text) => detect(&text).map(LicenseField::Expr).ok_or(LicenseError::Undetected(path)),
+        Err(error) => Err(LicenseError::Io { path, error }),
+    }
+}
+
+/// Where a package's license was read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LicenseSource {
+    /// The `license` manifest field.
+    Field,
+    /// The file at the path specified by the `license-file` manifest field.
+    File(PathBuf),
+    /// A [clarification](../clarify/index.html) that overrides the package's
+    /// own license.
+    Clarification,
+}
+
+/// An error returned when a package's license could not be determined.
+#[derive(Debug)]
+pub enum LicenseError {
+    /// Neither `license` nor `license-file` were specified.
+    Missing,
+    /// The `license` field is not a valid license expression.
+    Invalid {
+        /// The value of the `license` field.
+        expr: String,
+        /// Why `expr` is invalid.
+        reason: String,
+    },
+    /// The license file could not be read.
+    Io {
+        /// The path to the license file.
+        path: PathBuf,
+        /// The error that occurred when reading.
+        error: io::Error,
+    },
+    /// The license file's contents do not match a known license.
+    Undetected(PathBuf),
+}
+
+impl fmt::Display for LicenseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LicenseError::Missing => {
+                write!(f, "no license specified")
+            },
+            LicenseError::Invalid { expr, reason } => {
+                write!(f, "invalid license '{}': {}", expr, reason)
+            },
+            LicenseError::Io { path, error } => {
+                write!(f, "failed to read '{}': {}", path.display(), error)
+            },
+            LicenseError::Undetected(path) => {
+                write!(f, "unknown license in '{}'", path.display())
+            },
+        }
+    }
+}
+
+impl error::Error for LicenseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            LicenseError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::fmt;
+
+/// A compilation target against which platform-specific dependencies (e.g.
+/// `[target.'cfg(windows)'.dependencies]`) are evaluated.
+///
+/// The fields correspond to the `cfg` options of the same name.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Target {
+    /// The target triple, e.g. `x86_64-unknown-linux-gnu`.
+    pub triple: String,
+    /// `target_arch`, e.g. `x86_64`.
+    pub arch: String,
+    /// `target_vendor`, e.g. `unknown`.
+    pub vendor: String,
+    /// `target_os`, e.g. `linux`.
+    pub os: String,
+    /// `target_env`, e.g. `gnu`. Empty if not applicable.
+    pub env: String,
+    /// `target_family`, e.g. `unix`. Also set as a bare `cfg` name.
+    pub families: Vec<String>,
+    /// `target_pointer_width`, e.g. `64`.
+    pub pointer_width: String,
+    /// `target_endian`, either `little` or `big`.
+    pub endian: String,
+    /// Additional bare `cfg` names to consider set, such as `debug_assertions`.
+    pub names: Vec<String>,
+}
+
+impl fmt::Display for Target {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.triple.fmt(f)
+    }
+}
+
+impl Target {
+    /// Derives the `cfg` options of `triple` from its components.
+    ///
+    /// This covers the conventions of the targets supported by `rustc`, but
+    /// the fields may be adjusted for targets where it falls short.
+    ///
+    /// ```
+    /// use linfo::cargo::Target;
+    ///
+    /// let target = Target::from_triple("x86_64-pc-windows-msvc");
+    /// assert!(target.matches("cfg(windows)"));
+    /// assert!(target.matches("cfg(all(target_env = \"msvc\", target_pointer_width = \"64\"))"));
+    /// assert!(!target.matches("cfg(unix)"));
+    /// ```
+    pub fn from_triple(triple: &str) -> Self {
+        let parts: Vec<&str> = triple.split('-').collect();
+        let arch_part = parts[0];
+
+        let arch = match arch_part {
+            "i386" | "i586" | "i686" => "x86",
+            a if a.starts_with("arm") || a.starts_with("thumb") => "arm",
+            a if a.starts_with("riscv64") => "riscv64",
+            a if a.starts_with("riscv32") => "riscv32",
+            "powerpc64le" => "powerpc64",
+            "mipsel" => "mips",
+            "mips64el" => "mips64",
+            a => a,
+        };
+
+        const OSES: &[&str] = &[
+            "linux", "windows", "darwin", "macos", "ios", "android", "freebsd",
+            "netbsd", "openbsd", "dragonfly", "solaris", "illumos", "fuchsia",
+            "redox", "wasi", "emscripten", "haiku", "hermit", "none",
+        ];
+        let os_index = parts.iter().skip(1).position(|p| OSES.contains(p)).map(|i| i + 1);
+
+        let os = match os_index.map(|i| parts[i]) {
+            Some("darwin") => "macos",
+            // Android triples are `<arch>-linux-android*`
+            Some("linux") if parts.iter().any(|p| p.starts_with("android")) => "android",
+            Some(os) => os,
+            None => "unknown",
+        };
+
+        let vendor = match os_index {
+            Some(2) | None if parts.len() > 1 => parts[1],
+            _ => "unknown",
+        };
+
+        let env = os_index
+            .and_then(|i| parts.get(i + 1))
+            .map(|env| {
+                ["gnu", "musl", "msvc", "sgx", "uclibc", "newlib"].iter()
+                    .find(|e| env.starts_with(*e))
+                    .copied()
+                    .unwrap_or("")
+            })
+            .unwrap_or("");
+
+        let mut families = Vec::new();
+        match os {
+            "windows" => families.push("windows"),
+            "linux" | "macos" | "ios" | "android" | "freebsd" | "netbsd" |
+            "openbsd" | "dragonfly" | "solaris" | "illumos" | "fuchsia" |
+            "redox" | "haiku" | "emscripten" => families.push("unix"),
+            _ => {},
+        }
+        if arch.starts_with("wasm") {
+            families.push("wasm");
+        }
+
+        let pointer_width = match arch {
+            "x86_64" | "aarch64" | "powerpc64" | "mips64" | "riscv64" |
+            "s390x" | "sparc64" | "wasm64" | "loongarch64" => "64",
+            "msp430" | "avr" => "16",
+            _ => "32",
+        };
+
+        let endian = match arch_part {
+            "powerpc" | "powerpc64" | "mips" | "mips64" | "s390x" | "sparc" |
+            "sparc64" | "sparcv9" => "big",
+            a if a.ends_with("_be") || a.ends_with("eb") => "big",
+            _ => "little",
+        };
+
+        Self {
+            triple: triple.to_owned(),
+            arch: arch.to_owned(),
+            vendor: vendor.to_owned(),
+            os: os.to_owned(),
+            env: env.to_owned(),
+            families: families.into_iter().map(str::to_owned).collect(),
+            pointer_width: pointer_width.to_owned(),
+            endian: endian.to_owned(),
+            names: Vec::new(),
+        }
+    }
+
+    /// Returns whether a dependency's `target`, either a triple or a `cfg(...)`
+    /// expression, applies to this target.
+    ///
+    /// Malformed `cfg` expressions never match.
+    pub fn matches(&self, platform: &str) -> bool {
+        let platform = platform.trim();
+        match platform.strip_prefix("cfg(").and_then(|s| s.strip_suffix(')')) {
+            Some(cfg) => Cfg::parse(cfg).is_some_and(|cfg| cfg.eval(self)),
+            None => platform == self.triple,
+        }
+    }
+
+    fn has_name(&self, name: &str) -> bool {
+        self.families.iter().any(|f| f == name) ||
+        self.names.iter().any(|n| n == name)
+    }
+
+    fn has_key_value(&self, key: &str, value: &str) -> bool {
+        match key {
+            "target_arch" => self.arch == value,
+            "target_vendor" => self.vendor == value,
+            "target_os" => self.os == value,
+            "target_env" => self.env == value,
+            "target_family" => self.families.iter().any(|f| f == value),
+            "target_pointer_width" => self.pointer_width == value,
+            "target_endian" => self.endian == value,
+            _ => false,
+        }
+    }
+}
+
+/// A parsed `cfg` predicate.
+#[derive(Debug)]
+enum Cfg {
+    Name(String),
+    KeyValue(String, String),
+    All(Vec<Cfg>),
+    Any(Vec<Cfg>),
+    Not(Box<Cfg>),
+}
+
+impl Cfg {
+    fn parse(s: &str) -> Option<Self> {
+        let (cfg, rest) = Self::parse_one(s)?;
+        if rest.trim().is_empty() {
+            Some(cfg)
+        } else {
+            None
+        }
+    }
+
+    /// Parses a single predicate and returns the remaining input.
+    fn parse_one(s: &str) -> Option<(Self, &str)> {
+        let s = s.trim_start();
+        let end = s
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(s.len());
+        let (ident, rest) = s.split_at(end);
+        if ident.is_empty() {
+            return None;
+        }
+        let rest = rest.trim_start();
+
+        if let Some(rest) = rest.strip_prefix('(') {
+            let mut list = Vec::new();
+            let mut rest = rest.trim_start();
+            while !rest.starts_with(')') {
+                let (cfg, r) = Self::parse_one(rest)?;
+                list.push(cfg);
+                rest = r.trim_start();
+                if let Some(r) = rest.strip_prefix(',') {
+                    rest = r.trim_start();
+                } else if !rest.starts_with(')') {
+                    return None;
+                }
+            }
+            let rest = &rest[1..];
+
+            let cfg = match ident {
+                "all" => Cfg::All(list),
+                "any" => Cfg::Any(list),
+                "not" if list.len() == 1 => Cfg::Not(Box::new(list.remove(0))),
+                _ => return None,
+            };
+            Some((cfg, rest))
+        } else if let Some(rest) = rest.strip_prefix('=') {
+            let rest = rest.trim_start().strip_prefix('"')?;
+            let end = rest.find('"')?;
+            let cfg = Cfg::KeyValue(ident.to_owned(), rest[..end].to_owned());
+            Some((cfg, &rest[(end + 1)..]))
+        } else {
+            Some((Cfg::Name(ident.to_owned()), rest))
+        }
+    }
+
+    fn eval(&self, target: &Target) -> bool {
+        match self {
+            Cfg::Name(name) => target.has_name(name),
+            Cfg::KeyValue(key, value) => target.has_key_value(key, value),
+            Cfg::All(list) => list.iter().all(|c| c.eval(target)),
+            Cfg::Any(list) => list.iter().any(|c| c.eval(target)),
+            Cfg::Not(cfg) => !cfg.eval(target),
+        }
+    }
+}
@@ -0,0 +1,400 @@
+//! What to do when distributing a release, based on the licenses of its
+//! packages.
+//!
+//! A [`Checklist`](struct.Checklist.html) groups packages by license and lists
+//! the [`Action`](enum.Action.html)s each license requires, according to
+//! [`SpdxLicense::obligations`](../spdx/enum.SpdxLicense.html#method.obligations).
+//!
+//! ```
+//! use linfo::{checklist::{Checklist, Release}, Expr};
+//!
+//! let mit = Expr::parse("MIT").unwrap();
+//! let lgpl = Expr::parse("LGPL-2.1-only").unwrap();
+//!
+//! let packages = vec![("a", &mit), ("b", &mit), ("c", &lgpl)];
+//! let checklist = Checklist::new(packages, Release::Binary).unwrap();
+//! let markdown = checklist.to_markdown();
+//!
+//! assert!(markdown.contains("- [ ] Ship LICENSE text for MIT (2 packages)\n"));
+//! assert!(markdown.contains(
+//!     "- [ ] Offer source for LGPL-2.1-only (1 package, dynamic linking required)\n"
+//! ));
+//! ```
+//!
+//! This is not legal advice.
+
+use core::fmt::{self, Write};
+use crate::{
+    conclusion::InvalidElection,
+    solve::Solution,
+    spdx::Conditions,
+    Expr,
+    License,
+};
+
+/// How a work is distributed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Release {
+    /// Compiled artifacts, such as executables or libraries.
+    Binary,
+    /// The source code itself.
+    Source,
+}
+
+/// Something that must be done to comply with a license.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Include the license text.
+    ShipLicenseText,
+    /// Keep the copyright notices of the packages.
+    KeepCopyright,
+    /// Reproduce the contents of the packages' `NOTICE` files.
+    ReproduceNotice,
+    /// Mark modified files as changed.
+    StateChanges,
+    /// Make the source code of the packages available.
+    OfferSource {
+        /// Whether users must be able to relink the work with a modified
+        /// version of the packages, such as via dynamic linking.
+        relinking: bool,
+    },
+    /// Make the source code available to users interacting with the work
+    /// over a network.
+    OfferSourceToNetworkUsers,
+    /// Review the license, since its obligations are unknown.
+    Review,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Action::ShipLicenseText => "Ship LICENSE text",
+            Action::KeepCopyright => "Keep copyright notices",
+            Action::ReproduceNotice => "Reproduce NOTICE",
+            Action::StateChanges => "State changes to modified files",
+            Action::OfferSource { .. } => "Offer source",
+            Action::OfferSourceToNetworkUsers => "Offer source to network users",
+            Action::Review => "Review terms",
+        })
+    }
+}
+
+impl Action {
+    /// Returns a short note on how to perform this action, if any.
+    #[inline]
+    pub fn note(&self) -> Option<&'static str> {
+        match self {
+            Action::OfferSource { relinking: true } => Some("dynamic linking required"),
+            _ => None,
+        }
+    }
+
+    /// Returns the actions that `license` requires for `release`.
+    pub fn required(license: &License, release: Release) -> Vec<Action> {
+        let obligations = match license.base() {
+            License::Spdx(l) => l.obligations(),
+            _ => None,
+        };
+        let conditions = match obligations {
+            Some(obligations) => obligations.conditions,
+            None => return vec![Action::Review],
+        };
+
+        let mut actions = Vec::new();
+        let mut push = |condition, action| if conditions.contains(condition) {
+            actions.push(action);
+        };
+        push(Conditions::INCLUDE_LICENSE, Action::ShipLicenseText);
+        push(Conditions::INCLUDE_COPYRIGHT, Action::KeepCopyright);
+        push(Conditions::INCLUDE_NOTICE, Action::ReproduceNotice);
+        push(Conditions::STATE_CHANGES, Action::StateChanges);
+        if release == Release::Binary {
+            let relinking = conditions.contains(Conditions::ALLOW_RELINKING);
+            push(Conditions::DISCLOSE_SOURCE, Action::OfferSource { relinking });
+        }
+        push(Conditions::NETWORK_USE_DISCLOSE, Action::OfferSourceToNetworkUsers);
+        actions
+    }
+}
+
+/// The actions required by a single license and the packages it applies to.
+#[derive(Clone, Debug)]
+pub struct Item<'a> {
+    /// The license.
+    pub license: &'a License,
+    /// What must be done for the license.
+    pub actions: Vec<Action>,
+    /// The packages under `license`.
+    pub packages: Vec<&'a str>,
+}
+
+/// The actions required to distribute a set of packages, grouped by license.
+#[derive(Clone, Debug)]
+pub struct Checklist<'a> {
+    items: Vec<Item<'a>>,
+}
+
+impl<'a> Checklist<'a> {
+    /// Creates a checklist for `packages`, sorted by license ID.
+    ///
+    /// Every license of an `AND` expression applies. An expression containing
+    /// an `OR` must first be resolved to one of its
+    /// [choices](../expr/enum.Expr.html#method.choices), such as via
+    /// [`solve`](../solve/index.html) and
+    /// [`from_solution`](#method.from_solution), so it is an error here.
+    /// Use [`with_elections`](#method.with_elections) to resolve them
+    /// per package instead.
+    pub fn new<I>(packages: I, release: Release) -> Result<Self, UnresolvedOr<'a>>
+        where I: IntoIterator<Item = (&'a str, &'a Expr)>
+    {
+        let packages = packages.into_iter().map(|(package, expr)| (package, expr, None));
+        Self::with_elections(packages, release).map_err(|err| match err {
+            ElectionError::Unresolved(err) => err,
+            ElectionError::Invalid { .. } => unreachable!("no license was elected"),
+        })
+    }
+
+    /// Creates a checklist for `packages`, each with the license elected from
+    /// its `OR` expression, if any, sorted by license ID.
+    ///
+    /// As with [`Conclusion::validate`](../conclusion/struct.Conclusion.html#method.validate),
+    /// an elected license must be one of the operands of an `OR` expression.
+    /// Packages without an election are treated as in [`new`](#method.new).
+    ///
+    /// ```
+    /// use linfo::{checklist::{Checklist, Release}, Expr, License};
+    ///
+    /// let dual = Expr::parse("MIT OR Apache-2.0").unwrap();
+    /// let mit = License::parse("MIT").unwrap();
+    ///
+    /// let checklist = Checklist::with_elections(
+    ///     vec![("a", &dual, Some(&mit))],
+    ///     Release::Binary,
+    /// ).unwrap();
+    /// assert_eq!(checklist.items()[0].license, &mit);
+    /// ```
+    pub fn with_elections<I>(packages: I, release: Release) -> Result<Self, ElectionError<'a>>
+        where I: IntoIterator<Item = (&'a str, &'a Expr, Option<&'a License>)>
+    {
+        let mut checklist = Self { items: Vec::new() };
+        for (package, expr, election) in packages {
+            let invalid = |error| ElectionError::Invalid { package, expr, error };
+            match (expr, election) {
+                (Expr::Or(or), Some(license)) => {
+                    if !or.as_slice().iter().any(|e| e == license) {
+                        return Err(invalid(InvalidElection::NotInExpr));
+                    }
+                    checklist.add(package, license, release);
+                },
+                (_, Some(_)) => return Err(invalid(InvalidElection::NoChoice)),
+                (_, None) => {
+                    let mut choices = expr.choices();
+                    if choices.len() != 1 {
+                        return Err(ElectionError::Unresolved(UnresolvedOr { package, expr }));
+                    }
+                    for license in choices.remove(0) {
+                        checklist.add(package, license, release);
+                    }
+                },
+            }
+        }
+        checklist.items.sort_by_key(|item| item.license.to_string());
+        Ok(checklist)
+    }
+
+    /// Creates a checklist for the licenses chosen in `solution`.
+    pub fn from_solution(solution: &Solution<'a>, release: Release) -> Self {
+        let mut checklist = Self { items: Vec::new() };
+        for choice in solution.choices() {
+            for &license in &choice.licenses {
+                checklist.add(choice.package, license, release);
+            }
+        }
+        checklist.items.sort_by_key(|item| item.license.to_string());
+        checklist
+    }
+
+    fn add(&mut self, package: &'a str, license: &'a License, release: Release) {
+        match self.items.iter_mut().find(|item| item.license == license) {
+            Some(item) => if !item.packages.contains(&package) {
+                item.packages.push(package);
+            },
+            None => self.items.push(Item {
+                license,
+                actions: Action::required(license, release),
+                packages: vec![package],
+            }),
+        }
+    }
+
+    /// Returns the actions required by each license.
+    #[inline]
+    pub fn items(&self) -> &[Item<'a>] {
+        &self.items
+    }
+
+    /// Renders this checklist as a Markdown task list with a section for each
+    /// license.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# License obligations\n");
+        for item in &self.items {
+            let count = item.packages.len();
+            let packages = if count == 1 { "package" } else { "packages" };
+
+            let _ = write!(out, "\n## {}\n\n", item.license);
+            for action in &item.actions {
+                let _ = write!(
+                    out,
+                    "- [ ] {} for {} ({} {}",
+                    action,
+                    item.license,
+                    count,
+                    packages,
+                );
+                if let Some(note) = action.note() {
+                    let _ = write!(out, ", {}", note);
+                }
+                out.push_str(")\n");
+            }
+            if item.actions.is_empty() {
+                out.push_str("No obligations.\n");
+            }
+            let _ = writeln!(out, "\nPackages: {}", item.packages.join(", "));
+        }
+        out
+    }
+}
+
+/// An error returned by [`Checklist::new`](struct.Checklist.html#method.new)
+/// when a package's license is an `OR` expression that has not been resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnresolvedOr<'a> {
+    /// The package.
+    pub package: &'a str,
+    /// The package's expression.
+    pub expr: &'a Expr,
+}
+
+impl fmt::Display for UnresolvedOr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no license chosen for {} under {}", self.package, self.expr)
+    }
+}
+
+impl std::error::Error for UnresolvedOr<'_> {}
+
+/// An error returned by
+/// [`Checklist::with_elections`](struct.Checklist.html#method.with_elections).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElectionError<'a> {
+    /// A package without an election has an `OR` expression.
+    Unresolved(UnresolvedOr<'a>),
+    /// A package's election does not fit its expression.
+    Invalid {
+        /// The package.
+        package: &'a str,
+        /// The package's expression.
+        expr: &'a Expr,
+        /// Why the election does not fit.
+        error: InvalidElection,
+    },
+}
+
+impl fmt::Display for ElectionError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ElectionError::Unresolved(err) => err.fmt(f),
+            ElectionError::Invalid { package, expr, error } => {
+                write!(f, "invalid election for {} under {}: {}", package, expr, error)
+            },
+        }
+    }
+}
+
+impl std::error::Error for ElectionError<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpdxLicense;
+
+    #[test]
+    fn release_kinds() {
+        let lgpl = License::from(SpdxLicense::Lgpl2_1Only);
+        assert!(Action::required(&lgpl, Release::Binary)
+            .contains(&Action::OfferSource { relinking: true }));
+        assert!(!Action::required(&lgpl, Release::Source)
+            .iter()
+            .any(|a| matches!(a, Action::OfferSource { .. })));
+
+        let apache = License::from(SpdxLicense::Apache2);
+        assert!(Action::required(&apache, Release::Binary).contains(&Action::ReproduceNotice));
+
+        let custom = License::parse("LicenseRef-Custom").unwrap();
+        assert_eq!(Action::required(&custom, Release::Source), [Action::Review]);
+    }
+
+    #[test]
+    fn group_by_license() {
+        let a = Expr::parse("MIT AND Apache-2.0").unwrap();
+        let b = Expr::parse("Apache-2.0").unwrap();
+        let checklist = Checklist::new(vec![("a", &a), ("b", &b), ("b", &b)], Release::Binary)
+            .unwrap();
+
+        let items = checklist.items();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].license, &SpdxLicense::Apache2.into());
+        assert_eq!(items[0].packages, ["a", "b"]);
+        assert!(checklist
+            .to_markdown()
+            .contains("- [ ] Reproduce NOTICE for Apache-2.0 (2 packages)\n"));
+    }
+
+    #[test]
+    fn unresolved_or() {
+        let mit = Expr::parse("MIT").unwrap();
+        let or = Expr::parse("MIT OR GPL-3.0-only").unwrap();
+        let err = Checklist::new(vec![("a", &mit), ("b", &or)], Release::Binary).unwrap_err();
+        assert_eq!(err, UnresolvedOr { package: "b", expr: &or });
+        assert_eq!(err.to_string(), "no license chosen for b under MIT OR GPL-3.0-only");
+    }
+
+    #[test]
+    fn elections() {
+        let or = Expr::parse("MIT OR GPL-3.0-only").unwrap();
+        let and = Expr::parse("MIT AND ISC").unwrap();
+        let mit = License::from(SpdxLicense::Mit);
+        let apache = License::from(SpdxLicense::Apache2);
+
+        let checklist = Checklist::with_elections(
+            vec![("a", &or, Some(&mit)), ("b", &and, None)],
+            Release::Binary,
+        ).unwrap();
+        let items = checklist.items();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].license, &SpdxLicense::Isc.into());
+        assert_eq!(items[1].license, &mit);
+        assert_eq!(items[1].packages, ["a", "b"]);
+
+        let err = Checklist::with_elections(vec![("a", &or, Some(&apache))], Release::Binary)
+            .unwrap_err();
+        assert_eq!(err, ElectionError::Invalid {
+            package: "a",
+            expr: &or,
+            error: InvalidElection::NotInExpr,
+        });
+        assert_eq!(
+            err.to_string(),
+            "invalid election for a under MIT OR GPL-3.0-only: \
+             elected license is not in the concluded expression",
+        );
+
+        let err = Checklist::with_elections(vec![("b", &and, Some(&mit))], Release::Binary)
+            .unwrap_err();
+        assert!(matches!(err, ElectionError::Invalid { error: InvalidElection::NoChoice, .. }));
+
+        let err = Checklist::with_elections(vec![("a", &or, None)], Release::Binary)
+            .unwrap_err();
+        assert_eq!(err, ElectionError::Unresolved(UnresolvedOr { package: "a", expr: &or }));
+    }
+}
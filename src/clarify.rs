@@ -0,0 +1,286 @@
+//! Overriding the license of packages whose license field is missing or wrong.
+//!
+//! A [`Clarification`](struct.Clarification.html) replaces the license of a
+//! package for a range of versions. It is pinned to the SHA-256 of the
+//! package's license files, so that when a newer release changes them, the
+//! clarification stops applying and is reported as [stale] for re-review.
+//!
+//! Clarifications are loaded with any `serde` format, such as a TOML file
+//! with a `[[clarify]]` table for each:
+//!
+//! ```toml
+//! [[clarify]]
+//! name = "ring"
+//! version = ">=0.16, <0.18"
+//! expr = "MIT AND ISC AND OpenSSL"
+//! reason = "The license field is missing."
+//!
+//! [[clarify.files]]
+//! path = "LICENSE"
+//! sha256 = "c1e3b7d3e9b4f0c4e1e7ad5c6b9b1b9f0c9d8a84b8b9d1a8b46a1f8d4e2e6c10"
+//! ```
+//!
+//! They can also be created directly:
+//!
+//! ```
+//! use linfo::{clarify::*, Expr};
+//!
+//! let clarifications = Clarifications {
+//!     clarifications: vec![Clarification {
+//!         name: "ring".to_owned(),
+//!         version: ">=0.16, <0.18".parse().unwrap(),
+//!         expr: Expr::parse("MIT AND ISC AND OpenSSL").unwrap(),
+//!         files: vec![PinnedFile::new("LICENSE", b"Copyright (c) 2015-2016 the fiat-crypto authors")],
+//!         reason: Some("The license field is missing.".to_owned()),
+//!     }],
+//! };
+//!
+//! // The license file changed since it was reviewed.
+//! let outcome = clarifications.apply("ring", "0.16.20", |_| Ok(b"changed".to_vec()));
+//! match outcome {
+//!     Outcome::Stale(stale) => assert_eq!(stale.files[0].path, "LICENSE"),
+//!     _ => unreachable!(),
+//! }
+//!
+//! // Other versions are not clarified.
+//! let outcome = clarifications.apply("ring", "0.18.0", |_| unreachable!());
+//! assert!(outcome.expr().is_none());
+//! ```
+//!
+//! Clarifications apply to everything derived from a
+//! [`Metadata`](../cargo/struct.Metadata.html) after
+//! [`Metadata::clarify`](../cargo/struct.Metadata.html#method.clarify), such
+//! as its reports, graphs and the SBOMs created from them. Build scripts apply
+//! them via [`Generator::clarifications`].
+//!
+//! [stale]: enum.Outcome.html#variant.Stale
+//! [`Generator::clarifications`]: ../build/struct.Generator.html#structfield.clarifications
+
+use std::{fmt, fs, io, path::Path};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::Expr;
+
+/// A set of clarifications, as found in a TOML file with a `[[clarify]]`
+/// entry for each.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Clarifications {
+    /// The clarifications, where the first to match a package is used.
+    #[serde(default, rename = "clarify")]
+    pub clarifications: Vec<Clarification>,
+}
+
+/// An override of the license of a package.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Clarification {
+    /// The name of the package.
+    pub name: String,
+    /// The versions of the package this applies to.
+    pub version: VersionReq,
+    /// The license of the package.
+    pub expr: Expr,
+    /// The license files that `expr` was concluded from.
+    #[serde(default)]
+    pub files: Vec<PinnedFile>,
+    /// Why the clarification was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// A file whose contents a [`Clarification`](struct.Clarification.html)
+/// depends on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedFile {
+    /// The path of the file, relative to the package's root directory.
+    pub path: String,
+    /// The lowercase hexadecimal SHA-256 of the file's contents.
+    pub sha256: String,
+}
+
+impl PinnedFile {
+    /// Creates a pin of `path` to its current `contents`.
+    #[inline]
+    pub fn new(path: impl Into<String>, contents: &[u8]) -> Self {
+        Self { path: path.into(), sha256: sha256(contents) }
+    }
+
+    /// Returns whether `contents` are those that were pinned.
+    #[inline]
+    pub fn matches(&self, contents: &[u8]) -> bool {
+        self.sha256.eq_ignore_ascii_case(&sha256(contents))
+    }
+}
+
+/// The result of applying [`Clarifications`](struct.Clarifications.html) to
+/// a package.
+#[derive(Clone, Debug)]
+pub enum Outcome<'a> {
+    /// No clarification exists for the package.
+    Unclarified,
+    /// The package's license is clarified to be `expr`.
+    Clarified(&'a Clarification),
+    /// A clarification exists, but the pinned files changed.
+    Stale(Stale<'a>),
+}
+
+impl<'a> Outcome<'a> {
+    /// Returns the clarified license, if the clarification applies.
+    #[inline]
+    pub fn expr(&self) -> Option<&'a Expr> {
+        match self {
+            Outcome::Clarified(c) => Some(&c.expr),
+            _ => None,
+        }
+    }
+}
+
+/// A clarification that no longer applies because the files it is pinned to
+/// changed or can no longer be read.
+#[derive(Clone, Debug)]
+pub struct Stale<'a> {
+    /// The clarification to re-review.
+    pub clarification: &'a Clarification,
+    /// The pinned files that changed.
+    pub files: Vec<&'a PinnedFile>,
+}
+
+impl fmt::Display for Stale<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "clarification of {} {} needs review: ",
+            self.clarification.name,
+            self.clarification.version,
+        )?;
+        for (i, file) in self.files.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(&file.path)?;
+        }
+        f.write_str(" changed")
+    }
+}
+
+impl Clarifications {
+    /// Returns the first clarification for `version` of the package `name`.
+    ///
+    /// Versions that aren't valid SemVer never match.
+    pub fn find(&self, name: &str, version: &str) -> Option<&Clarification> {
+        let version = Version::parse(version).ok()?;
+        self.clarifications.iter()
+            .find(|c| c.name == name && c.version.matches(&version))
+    }
+
+    /// Applies the clarification for `version` of the package `name`, where
+    /// `read` returns the contents of a pinned file by its path.
+    pub fn apply<F>(&self, name: &str, version: &str, read: F) -> Outcome<'_>
+        where F: FnMut(&str) -> io::Result<Vec<u8>>
+    {
+        match self.find(name, version) {
+            Some(clarification) => clarification.check(read),
+            None => Outcome::Unclarified,
+        }
+    }
+
+    /// Applies the clarification for `version` of the package `name`, whose
+    /// pinned files are read from `dir`.
+    pub fn apply_dir(&self, name: &str, version: &str, dir: &Path) -> Outcome<'_> {
+        self.apply(name, version, |path| fs::read(dir.join(path)))
+    }
+
+    /// Returns the clarified license of `version` of the package `name`, whose
+    /// pinned files are read from `dir`, if any.
+    ///
+    /// A clarification that no longer applies is added to `stale`. Without a
+    /// directory, pinned files are treated as changed.
+    #[cfg(feature = "cargo")]
+    pub(crate) fn clarified<'c>(
+        &'c self,
+        name: &str,
+        version: &str,
+        dir: Option<&Path>,
+        stale: &mut Vec<Stale<'c>>,
+    ) -> Option<&'c Expr> {
+        let outcome = match dir {
+            Some(dir) => self.apply_dir(name, version, dir),
+            None => self.apply(name, version, |_| Err(io::ErrorKind::NotFound.into())),
+        };
+        match outcome {
+            Outcome::Unclarified => None,
+            Outcome::Clarified(clarification) => Some(&clarification.expr),
+            Outcome::Stale(s) => {
+                stale.push(s);
+                None
+            },
+        }
+    }
+}
+
+impl Clarification {
+    /// Checks the pinned files, where `read` returns the contents of a file by
+    /// its path. Files that can't be read are treated as changed.
+    pub fn check<F>(&self, mut read: F) -> Outcome<'_>
+        where F: FnMut(&str) -> io::Result<Vec<u8>>
+    {
+        let files: Vec<&PinnedFile> = self.files.iter()
+            .filter(|file| !read(&file.path).is_ok_and(|contents| file.matches(&contents)))
+            .collect();
+        if files.is_empty() {
+            Outcome::Clarified(self)
+        } else {
+            Outcome::Stale(Stale { clarification: self, files })
+        }
+    }
+}
+
+/// Returns the lowercase hexadecimal SHA-256 of `contents`.
+fn sha256(contents: &[u8]) -> String {
+    Sha256::digest(contents).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply() {
+        let license = b"Permission is hereby granted...";
+        let clarifications = Clarifications {
+            clarifications: vec![Clarification {
+                name: "foo".to_owned(),
+                version: VersionReq::parse("^1.2").unwrap(),
+                expr: Expr::parse("MIT").unwrap(),
+                files: vec![PinnedFile::new("LICENSE", license)],
+                reason: None,
+            }],
+        };
+
+        let read = |path: &str| match path {
+            "LICENSE" => Ok(license.to_vec()),
+            _ => Err(io::ErrorKind::NotFound.into()),
+        };
+        let outcome = clarifications.apply("foo", "1.3.0", read);
+        assert_eq!(outcome.expr(), Some(&Expr::parse("MIT").unwrap()));
+
+        let outcome = clarifications.apply("foo", "1.1.0", read);
+        assert!(matches!(outcome, Outcome::Unclarified));
+        let outcome = clarifications.apply("bar", "1.3.0", read);
+        assert!(matches!(outcome, Outcome::Unclarified));
+
+        let read = |_: &str| Err(io::ErrorKind::NotFound.into());
+        match clarifications.apply("foo", "1.2.0", read) {
+            Outcome::Stale(stale) => assert_eq!(stale.files.len(), 1),
+            outcome => panic!("expected stale clarification, got {:?}", outcome),
+        }
+    }
+
+    #[test]
+    fn sha256_hex() {
+        assert_eq!(
+            sha256(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Compatibility of licenses combined into a single work.
+//!
+//! Only well-known conflicts are reported, which mostly concern the GNU GPL
+//! family. Licenses are otherwise assumed to be compatible, so this should be
+//! paired with a [`Policy`](../policy/struct.Policy.html) to reject licenses
+//! that have not been reviewed.
+//!
+//! This is not legal advice.
+
+use crate::{spdx::LicenseKind, License, SpdxLicense};
+
+/// Returns whether works licensed under `a` and `b` can be combined into a
+/// single work, such as a statically linked binary.
+///
+/// ```
+/// use linfo::{compat, SpdxLicense};
+///
+/// assert!(compat::are_compatible_spdx(SpdxLicense::Apache2, SpdxLicense::Gpl3Only));
+/// assert!(!compat::are_compatible_spdx(SpdxLicense::Apache2, SpdxLicense::Gpl2Only));
+/// assert!(compat::are_compatible_spdx(SpdxLicense::Gpl2OrLater, SpdxLicense::Gpl3Only));
+/// ```
+#[inline]
+pub fn are_compatible(a: &License, b: &License) -> bool {
+    match (a, b) {
+        (License::Spdx(a), License::Spdx(b)) => are_compatible_spdx(*a, *b),
+        (License::_NonExhaustive(never), _) |
+        (_, License::_NonExhaustive(never)) => never.consume(),
+        // Exceptions only grant additional permissions
+        (License::With(a, _), b) | (b, License::With(a, _)) => are_compatible(a, b),
+        // Nothing is known about user-defined licenses
+        (License::Ref(_), _) | (_, License::Ref(_)) => true,
+    }
+}
+
+/// Returns whether works licensed under `a` and `b` can be combined into a
+/// single work, such as a statically linked binary.
+///
+/// See [`are_compatible`](fn.are_compatible.html).
+pub fn are_compatible_spdx(a: SpdxLicense, b: SpdxLicense) -> bool {
+    if a == b {
+        return true;
+    }
+    match (gpl_versions(a), gpl_versions(b)) {
+        (Some(a), Some(b)) => a & b != 0,
+        (Some(gpl), None) => is_gpl_compatible(a, gpl, b),
+        (None, Some(gpl)) => is_gpl_compatible(b, gpl, a),
+        (None, None) => {
+            // Two distinct strong copyleft licenses each require the combined
+            // work to be licensed under their own terms.
+            !(is_strong_copyleft(a) && is_strong_copyleft(b))
+        },
+    }
+}
+
+/// Returns whether `other` can be combined with `gpl`, which may be licensed
+/// under `versions` of the GNU GPL.
+fn is_gpl_compatible(gpl: SpdxLicense, versions: u8, other: SpdxLicense) -> bool {
+    // The LGPL only requires the library itself to remain under its terms, so
+    // it only conflicts with licenses that require the same of the whole work.
+    if gpl.kind() == LicenseKind::WeakCopyleft && !is_strong_copyleft(other) {
+        return true;
+    }
+    versions & gpl_compatible_versions(other) != 0
+}
+
+const GPL1: u8 = 0b001;
+const GPL2: u8 = 0b010;
+const GPL3: u8 = 0b100;
+
+fn is_strong_copyleft(l: SpdxLicense) -> bool {
+    l.kind() >= LicenseKind::StrongCopyleft && l.kind() <= LicenseKind::NetworkCopyleft
+}
+
+/// Returns the versions of the GNU GPL that a combined work may be licensed
+/// under if it includes `l`, or `None` if `l` is not of the GPL family.
+fn gpl_versions(l: SpdxLicense) -> Option<u8> {
+    use SpdxLicense::*;
+
+    let versions = match l {
+        Gpl1Only => GPL1,
+        Gpl1OrLater => GPL1 | GPL2 | GPL3,
+        Gpl2Only => GPL2,
+        Gpl2OrLater => GPL2 | GPL3,
+        Gpl3Only | Gpl3OrLater => GPL3,
+        // Section 13 of the GPLv3 and AGPLv3 permits combining the two
+        Agpl3Only | Agpl3OrLater => GPL3,
+        // The LGPLv2.x may be converted to the GPLv2 or later
+        Lgpl2Only | Lgpl2OrLater | Lgpl2_1Only | Lgpl2_1OrLater => GPL2 | GPL3,
+        Lgpl3Only | Lgpl3OrLater => GPL3,
+        _ => return None,
+    };
+    Some(versions)
+}
+
+/// Returns the versions of the GNU GPL that `l` is compatible with, as listed
+/// by the Free Software Foundation.
+fn gpl_compatible_versions(l: SpdxLicense) -> u8 {
+    use SpdxLicense::*;
+
+    match l {
+        // Permissive licenses that the FSF lists as GPL-incompatible
+        Afl1_1 | Afl1_2 | Afl2 | Afl2_1 | Afl3 |
+        Apache1 | Apache1_1 |
+        Bsd4Clause | Bsd4ClauseUc |
+        MsPl |
+        OpenSsl |
+        Php3 | Php3_01 |
+        Zpl1_1 => 0,
+
+        Apache2 | Ecl2 | Ftl => GPL3,
+
+        Mpl2 | Sleepycat | Cecill2 | Cecill2_1 | Eupl1_2 => GPL2 | GPL3,
+        Eupl1_1 => GPL2,
+        CcBySa4 => GPL3,
+
+        _ => match l.kind() {
+            LicenseKind::PublicDomain | LicenseKind::Permissive => {
+                GPL1 | GPL2 | GPL3
+            },
+            // Other copyleft licenses conflict with the GPL's requirement
+            // that the whole work be licensed under the GPL.
+            LicenseKind::WeakCopyleft |
+            LicenseKind::StrongCopyleft |
+            LicenseKind::NetworkCopyleft |
+            LicenseKind::Restricted => 0,
+            // Only report known conflicts
+            LicenseKind::Unknown => GPL1 | GPL2 | GPL3,
+        },
+    }
+}
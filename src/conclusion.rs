@@ -0,0 +1,364 @@
+//! Recording the license concluded for a package after review.
+//!
+//! The license a package declares is not always the one that applies. A
+//! [`Conclusion`](struct.Conclusion.html) pairs the declared expression with
+//! the one concluded after review and, for `OR` expressions, the
+//! [`Election`](struct.Election.html) of which license to follow.
+//!
+//! With the `serde` feature, conclusions can be kept in version control,
+//! such as in a table keyed by package:
+//!
+//! ```toml
+//! [conclusions."rand 0.7.3"]
+//! declared = "MIT OR Apache-2.0"
+//! concluded = "MIT OR Apache-2.0"
+//!
+//! [conclusions."rand 0.7.3".election]
+//! license = "MIT"
+//! decided-by = "Jane Doe <jane@example.com>"
+//! rationale = "Fewer attribution requirements."
+//! timestamp = "2020-01-01T00:00:00Z"
+//! ```
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+use crate::{Expr, License};
+
+/// The license concluded for a package, as opposed to the one it declares.
+///
+/// ```
+/// use linfo::{conclusion::{Conclusion, Election}, Expr, SpdxLicense};
+///
+/// let declared = Expr::parse("MIT OR Apache-2.0").unwrap();
+/// let mut conclusion = Conclusion::new(declared);
+/// conclusion.election = Some(Election {
+///     license: SpdxLicense::Mit.into(),
+///     decided_by: "Jane Doe <jane@example.com>".to_owned(),
+///     rationale: "Fewer attribution requirements.".to_owned(),
+///     timestamp: "2020-01-01T00:00:00Z".parse().unwrap(),
+/// });
+///
+/// assert!(conclusion.validate().is_ok());
+/// assert_eq!(conclusion.effective(), Ok(Expr::from(SpdxLicense::Mit)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub struct Conclusion {
+    /// The license the package declares, such as in its manifest.
+    pub declared: Expr,
+    /// The license concluded after review.
+    pub concluded: Expr,
+    /// Which license of an `OR` expression in `concluded` to follow.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub election: Option<Election>,
+}
+
+/// A decision to follow one license of an `OR` expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub struct Election {
+    /// The license that was chosen.
+    pub license: License,
+    /// Who made the decision.
+    pub decided_by: String,
+    /// Why the license was chosen.
+    pub rationale: String,
+    /// When the decision was made.
+    pub timestamp: Timestamp,
+}
+
+/// An [RFC 3339](https://tools.ietf.org/html/rfc3339) timestamp, such as
+/// `2020-01-01T00:00:00Z`.
+///
+/// The original text is kept as is, since only its validity is checked.
+///
+/// ```
+/// use linfo::conclusion::Timestamp;
+///
+/// let timestamp = Timestamp::parse("2020-02-29T12:30:00.5+01:00").unwrap();
+/// assert_eq!(timestamp.as_str(), "2020-02-29T12:30:00.5+01:00");
+///
+/// assert!(Timestamp::parse("2021-02-29T12:30:00Z").is_err());
+/// assert!(Timestamp::parse("yesterday").is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Timestamp(String);
+
+impl Timestamp {
+    /// Parses `s` as an RFC 3339 `date-time`.
+    pub fn parse(s: &str) -> Result<Self, InvalidTimestamp> {
+        if is_rfc3339(s.as_bytes()) {
+            Ok(Timestamp(s.to_owned()))
+        } else {
+            Err(InvalidTimestamp)
+        }
+    }
+
+    /// Returns the timestamp as it was written.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Timestamp {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = InvalidTimestamp;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Timestamp {
+    type Error = InvalidTimestamp;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        if is_rfc3339(s.as_bytes()) {
+            Ok(Timestamp(s))
+        } else {
+            Err(InvalidTimestamp)
+        }
+    }
+}
+
+impl From<Timestamp> for String {
+    #[inline]
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+/// Returns whether `s` is `full-date "T" full-time`, as in section 5.6 of
+/// RFC 3339.
+fn is_rfc3339(s: &[u8]) -> bool {
+    fn number(s: &[u8], range: std::ops::Range<usize>) -> Option<u32> {
+        let digits = s.get(range)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        Some(digits.iter().fold(0, |n, &d| n * 10 + u32::from(d - b'0')))
+    }
+
+    let date = || -> Option<()> {
+        let year = number(s, 0..4)?;
+        let month = number(s, 5..7)?;
+        let day = number(s, 8..10)?;
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => return None,
+        };
+        if s[4] != b'-' || s[7] != b'-' || day == 0 || day > days {
+            return None;
+        }
+        if !matches!(s.get(10)?, b'T' | b't') {
+            return None;
+        }
+
+        let hour = number(s, 11..13)?;
+        let minute = number(s, 14..16)?;
+        let second = number(s, 17..19)?;
+        if s[13] != b':' || s[16] != b':' || hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+
+        let mut rest = &s[19..];
+        if let Some(fraction) = rest.strip_prefix(b".") {
+            let digits = fraction.iter().take_while(|d| d.is_ascii_digit()).count();
+            if digits == 0 {
+                return None;
+            }
+            rest = &fraction[digits..];
+        }
+        match rest {
+            [b'Z'] | [b'z'] => Some(()),
+            [b'+' | b'-', ..] if rest.len() == 6 && rest[3] == b':' => {
+                let hour = number(rest, 1..3)?;
+                let minute = number(rest, 4..6)?;
+                if hour > 23 || minute > 59 {
+                    return None;
+                }
+                Some(())
+            },
+            _ => None,
+        }
+    };
+    date().is_some()
+}
+
+/// An error returned when a [`Timestamp`](struct.Timestamp.html) is not in
+/// RFC 3339 format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidTimestamp;
+
+impl fmt::Display for InvalidTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("timestamp is not in RFC 3339 format")
+    }
+}
+
+impl std::error::Error for InvalidTimestamp {}
+
+impl Conclusion {
+    /// Creates a conclusion that agrees with the `declared` license.
+    #[inline]
+    pub fn new(declared: Expr) -> Self {
+        Self {
+            concluded: declared.clone(),
+            declared,
+            election: None,
+        }
+    }
+
+    /// Returns whether the concluded license differs from the declared one.
+    #[inline]
+    pub fn is_overridden(&self) -> bool {
+        self.declared != self.concluded
+    }
+
+    /// Returns the license to follow: the elected license if any, otherwise
+    /// the concluded expression.
+    ///
+    /// Fails if the election does not fit the concluded expression, as checked
+    /// by [`validate`](#method.validate).
+    pub fn effective(&self) -> Result<Expr, InvalidElection> {
+        self.validate()?;
+        Ok(match &self.election {
+            Some(election) => election.license.clone().into(),
+            None => self.concluded.clone(),
+        })
+    }
+
+    /// Checks that the election, if any, is one of the licenses of an `OR`
+    /// expression in `concluded`.
+    pub fn validate(&self) -> Result<(), InvalidElection> {
+        let election = match &self.election {
+            Some(election) => election,
+            None => return Ok(()),
+        };
+        match &self.concluded {
+            Expr::Or(or) if or.as_slice().iter().any(|e| e == &election.license) => Ok(()),
+            Expr::Or(_) => Err(InvalidElection::NotInExpr),
+            _ => Err(InvalidElection::NoChoice),
+        }
+    }
+}
+
+/// An error returned when an [`Election`](struct.Election.html) does not fit
+/// the concluded expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidElection {
+    /// The concluded expression is not an `OR` expression, so there is nothing
+    /// to choose from.
+    NoChoice,
+    /// The elected license is not one of those in the concluded expression.
+    NotInExpr,
+}
+
+impl fmt::Display for InvalidElection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidElection::NoChoice => {
+                f.write_str("concluded license is not an OR expression")
+            },
+            InvalidElection::NotInExpr => {
+                f.write_str("elected license is not in the concluded expression")
+            },
+        }
+    }
+}
+
+impl std::error::Error for InvalidElection {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpdxLicense;
+
+    fn election(license: SpdxLicense) -> Election {
+        Election {
+            license: license.into(),
+            decided_by: "Jane Doe".to_owned(),
+            rationale: "Reviewed".to_owned(),
+            timestamp: Timestamp::parse("2020-01-01T00:00:00Z").unwrap(),
+        }
+    }
+
+    #[test]
+    fn validate() {
+        let mut conclusion = Conclusion::new(Expr::parse("MIT OR Apache-2.0").unwrap());
+        conclusion.election = Some(election(SpdxLicense::Gpl3Only));
+        assert_eq!(conclusion.validate(), Err(InvalidElection::NotInExpr));
+        assert_eq!(conclusion.effective(), Err(InvalidElection::NotInExpr));
+
+        conclusion.concluded = SpdxLicense::Mit.into();
+        assert!(conclusion.is_overridden());
+        conclusion.election = Some(election(SpdxLicense::Mit));
+        assert_eq!(conclusion.validate(), Err(InvalidElection::NoChoice));
+        assert_eq!(conclusion.effective(), Err(InvalidElection::NoChoice));
+
+        conclusion.election = None;
+        assert_eq!(conclusion.validate(), Ok(()));
+        assert_eq!(conclusion.effective(), Ok(Expr::from(SpdxLicense::Mit)));
+    }
+
+    #[test]
+    fn timestamp() {
+        let valid = [
+            "2020-01-01T00:00:00Z",
+            "1996-12-19T16:39:57-08:00",
+            "1990-12-31t23:59:60z",
+            "2000-02-29T00:00:00.123456+23:59",
+        ];
+        for s in &valid {
+            assert_eq!(Timestamp::parse(s).map(|t| t.to_string()).as_deref(), Ok(*s));
+        }
+
+        let invalid = [
+            "",
+            "2020-01-01",
+            "2020-01-01 00:00:00Z",
+            "2020-01-01T00:00:00",
+            "2020-13-01T00:00:00Z",
+            "1900-02-29T00:00:00Z",
+            "2020-01-01T24:00:00Z",
+            "2020-01-01T00:00:00.Z",
+            "2020-01-01T00:00:00+0100",
+            "2020-01-01T00:00:00Z ",
+        ];
+        for s in &invalid {
+            assert_eq!(Timestamp::parse(s), Err(InvalidTimestamp), "{}", s);
+        }
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn serde() {
+        let mut conclusion = Conclusion::new(Expr::parse("MIT OR Apache-2.0").unwrap());
+        conclusion.election = Some(election(SpdxLicense::Apache2));
+
+        let json = serde_json::to_value(&conclusion).unwrap();
+        assert_eq!(json["election"]["decided-by"], "Jane Doe");
+        assert_eq!(json["election"]["license"], "Apache-2.0");
+        assert_eq!(serde_json::from_value::<Conclusion>(json.clone()).unwrap(), conclusion);
+
+        let mut json = json;
+        json["election"]["timestamp"] = "last week".into();
+        let err = serde_json::from_value::<Conclusion>(json).unwrap_err();
+        assert!(err.to_string().contains("RFC 3339"));
+    }
+}
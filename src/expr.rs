@@ -4,28 +4,42 @@ use core::{
     convert::{TryFrom, TryInto},
     fmt,
 };
-use crate::{License, SpdxLicense, ParseError};
+use alloc::{vec, vec::Vec};
+use crate::{
+    spdx::{LicenseKind, ListVersion, SpdxException},
+    License,
+    SpdxLicense,
+    ParseError,
+};
 
 /// A license expression.
 ///
 /// Grammar (note the padded spacing):
 ///
 /// ```txt
-/// Single = License
+/// Expr = Or
+///
+/// Or = And
+///    | And " OR " Or
 ///
-/// Or = License " OR " License
-///    | License " OR " Or
+/// And = Term
+///     | Term " AND " And
 ///
-/// And = License " AND " License
-///     | License " AND " And
+/// Term = License
+///      | "(" Expr ")"
 /// ```
+///
+/// As in SPDX, `AND` binds tighter than `OR`, so `MIT OR ISC AND OpenSSL` is
+/// the same as `MIT OR (ISC AND OpenSSL)`. Parenthesized operands using the
+/// same operator are flattened into the enclosing list, so
+/// `(MIT OR ISC) OR Apache-2.0` is the same as `MIT OR ISC OR Apache-2.0`.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Expr {
     /// Only one license's conditions to follow.
     Single(License),
-    /// The conditions of either of two or more licenses apply.
+    /// The conditions of either of two or more expressions apply.
     Or(Or),
-    /// The conditions of all of two or more licenses apply.
+    /// The conditions of all of two or more expressions apply.
     And(And),
 }
 
@@ -71,50 +85,88 @@ impl fmt::Display for Expr {
 impl<'a> TryFrom<&'a str> for Expr {
     type Error = ParseError<'a>;
 
+    #[inline]
     fn try_from(s: &'a str) -> Result<Self, Self::Error> {
-        use core::{iter, str::Split};
-
-        fn handle_iter<'a>(
-            a: &'a str,
-            b: &'a str,
-            iter: Split<'a, &str>,
-        ) -> Vec<License> {
-            iter::once(a)
-                .chain(iter::once(b))
-                .chain(iter)
-                .map(str::trim)
-                .map(License::try_from)
-                .flatten()
-                .collect()
+        parse(s, false)
+    }
+}
+
+/// Returns the parts of `s` separated by `sep` outside of parentheses.
+fn split<'a>(s: &'a str, sep: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && s[i..].starts_with(sep) => {
+                parts.push(&s[start..i]);
+                start = i + sep.len();
+            },
+            _ => {},
         }
+    }
+    parts.push(&s[start..]);
+    parts
+}
 
-        let s = s.trim();
-        let mut iter_or = s.split(" OR ");
+/// Returns `s` without the parentheses enclosing all of it, if any.
+fn unwrap_parens(s: &str) -> &str {
+    let s = s.trim();
+    if !s.starts_with('(') || !s.ends_with(')') {
+        return s;
+    }
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => continue,
+        }
+        // The opening parenthesis is closed before the end
+        if depth == 0 && i != s.len() - 1 {
+            return s;
+        }
+    }
+    unwrap_parens(&s[1..(s.len() - 1)])
+}
 
-        match (iter_or.next(), iter_or.next()) {
-            (None, _) => {
-                Err(ParseError::Empty)
-            },
-            (Some(s), None) => {
-                let mut iter_and = s.split(" AND ");
-
-                match (iter_and.next(), iter_and.next()) {
-                    (None, _) => {
-                        Err(ParseError::Empty)
-                    },
-                    (Some(s), None) => {
-                        s.try_into().map(Self::Single)
-                    },
-                    (Some(l1), Some(l2)) => {
-                        Ok(And(handle_iter(l1, l2, iter_and)).into())
-                    },
-                }
-            },
-            (Some(l1), Some(l2)) => {
-                Ok(Or(handle_iter(l1, l2, iter_or)).into())
-            },
+/// Parses `s`, leaving operands with unknown ids out of `OR` and `AND` lists
+/// unless `strict`.
+fn parse(s: &str, strict: bool) -> Result<Expr, ParseError<'_>> {
+    let s = unwrap_parens(s);
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    // `OR` has the lowest precedence, so it's split on first
+    for &(sep, is_or) in &[(" OR ", true), (" AND ", false)] {
+        let parts = split(s, sep);
+        if parts.len() == 1 {
+            continue;
         }
+        let mut exprs = Vec::with_capacity(parts.len());
+        let mut unknown = None;
+        for part in parts {
+            match parse(part, strict) {
+                Ok(Expr::Or(or)) if is_or => exprs.extend(or.into_vec()),
+                Ok(Expr::And(and)) if !is_or => exprs.extend(and.into_vec()),
+                Ok(expr) => exprs.push(expr),
+                Err(error @ ParseError::UnknownLicenseId(_)) |
+                Err(error @ ParseError::UnknownExceptionId(_)) if !strict => {
+                    unknown.get_or_insert(error);
+                },
+                Err(error) => return Err(error),
+            }
+        }
+        return match (exprs.len(), unknown) {
+            (0, Some(error)) => Err(error),
+            (1, _) => Ok(exprs.remove(0)),
+            _ if is_or => Ok(Or::new(exprs).into()),
+            _ => Ok(And::new(exprs).into()),
+        };
     }
+    License::try_from(s).map(Expr::Single)
 }
 
 impl PartialEq<License> for Expr {
@@ -129,25 +181,9 @@ impl PartialEq<License> for Expr {
 }
 
 impl PartialEq<str> for Expr {
+    #[inline]
     fn eq(&self, s: &str) -> bool {
-        let (list, sep) = match self {
-            Expr::Single(l) => return l.id() == s,
-            Expr::Or(or) => {
-                (or.as_slice(), " OR ")
-            },
-            Expr::And(and) => {
-                (and.as_slice(), " AND ")
-            },
-        };
-        let mut iter = s.trim().split(sep).map(str::trim);
-        for license in list {
-            match iter.next() {
-                None => return false,
-                Some(next) if next != license.id() => return false,
-                _ => {},
-            }
-        }
-        iter.next().is_none()
+        Expr::parse(s).is_ok_and(|e| &e == self)
     }
 }
 
@@ -161,6 +197,17 @@ impl PartialEq<Expr> for str {
 impl Expr {
     /// Attempts to parse `input` and returns a
     /// [`ParseError`](struct.ParseError.html) on error.
+    ///
+    /// Operands of an `OR` or `AND` list with an unknown license or exception
+    /// id are left out of the expression, which is only an error if none are
+    /// known. Use [`parse_strict`](#method.parse_strict) to reject them.
+    ///
+    /// ```
+    /// use linfo::Expr;
+    ///
+    /// let expr = Expr::parse("MIT OR Not-A-License").unwrap();
+    /// assert_eq!(expr.to_string(), "MIT");
+    /// ```
     #[inline]
     pub fn parse<'i, I>(input: I) -> Result<Self, ParseError<'i>>
         where I: TryInto<Self, Error = ParseError<'i>> + 'i
@@ -168,75 +215,515 @@ impl Expr {
         input.try_into()
     }
 
-    /// Returns the underlying slice of licenses.
+    /// Parses `input` like [`parse`](#method.parse), but every license in an
+    /// `OR` or `AND` list must be known. An unknown id is an error rather than
+    /// being left out of the expression.
+    ///
+    /// ```
+    /// use linfo::{Expr, ParseError};
+    ///
+    /// assert_eq!(
+    ///     Expr::parse_strict("MIT OR Not-A-License"),
+    ///     Err(ParseError::UnknownLicenseId("Not-A-License")),
+    /// );
+    /// ```
     #[inline]
-    pub fn as_slice(&self) -> &[License] {
+    pub fn parse_strict(input: &str) -> Result<Self, ParseError<'_>> {
+        parse(input, true)
+    }
+
+    /// Parses `input` like [`parse_strict`](#method.parse_strict), but only accepts SPDX
+    /// licenses that are [valid] in `version` of the license list.
+    ///
+    /// This is useful for validating an SPDX document against the
+    /// `LicenseListVersion` it claims.
+    ///
+    /// ```
+    /// use linfo::{spdx::ListVersion, Expr, ParseError};
+    ///
+    /// let v3_5 = ListVersion::new(3, 5);
+    /// assert!(Expr::parse_in("MIT OR Apache-2.0", v3_5).is_ok());
+    /// assert_eq!(
+    ///     Expr::parse_in("MIT OR BlueOak-1.0.0", v3_5),
+    ///     Err(ParseError::UnknownLicenseId("BlueOak-1.0.0")),
+    /// );
+    /// ```
+    ///
+    /// [valid]: spdx/enum.SpdxLicense.html#method.is_valid_in
+    pub fn parse_in(input: &str, version: ListVersion) -> Result<Self, ParseError<'_>> {
+        let expr = Expr::parse_strict(input)?;
+        let invalid = expr.licenses().find_map(|l| match l.base() {
+            License::Spdx(l) if !l.is_valid_in(version) => Some(l.id()),
+            _ => None,
+        });
+        match invalid {
+            Some(id) => {
+                let start = input.find(id).unwrap_or(0);
+                Err(ParseError::UnknownLicenseId(&input[start..(start + id.len())]))
+            },
+            None => Ok(expr),
+        }
+    }
+
+    /// Returns every license in this expression, from left to right.
+    ///
+    /// ```
+    /// use linfo::Expr;
+    ///
+    /// let expr = Expr::parse("(MIT OR Apache-2.0) AND Unicode-DFS-2016").unwrap();
+    /// let ids: Vec<&str> = expr.licenses().map(|l| l.id()).collect();
+    /// assert_eq!(ids, ["MIT", "Apache-2.0", "Unicode-DFS-2016"]);
+    /// ```
+    #[inline]
+    pub fn licenses(&self) -> Licenses<'_> {
+        Licenses { stack: vec![self] }
+    }
+
+    /// Returns the sets of licenses that may be chosen to follow this
+    /// expression.
+    ///
+    /// Each operand of an `OR` is a separate choice, while an `AND` requires
+    /// a choice from each of its operands.
+    ///
+    /// ```
+    /// use linfo::Expr;
+    ///
+    /// let expr = Expr::parse("(MIT OR Apache-2.0) AND Unicode-DFS-2016").unwrap();
+    /// let choices: Vec<Vec<&str>> = expr.choices().iter()
+    ///     .map(|choice| choice.iter().map(|l| l.id()).collect())
+    ///     .collect();
+    /// assert_eq!(choices, [["MIT", "Unicode-DFS-2016"], ["Apache-2.0", "Unicode-DFS-2016"]]);
+    /// ```
+    pub fn choices(&self) -> Vec<Vec<&License>> {
         match self {
-            Expr::Single(l) => core::slice::from_ref(l),
-            Expr::Or(or) => or.as_slice(),
-            Expr::And(and) => and.as_slice(),
+            Expr::Single(l) => vec![vec![l]],
+            Expr::Or(or) => or.0.iter().flat_map(Expr::choices).collect(),
+            Expr::And(and) => {
+                let mut choices = vec![Vec::new()];
+                for operand in &and.0 {
+                    let operand = operand.choices();
+                    choices = choices.iter().flat_map(|choice| {
+                        operand.iter().map(move |licenses| {
+                            let mut choice = choice.clone();
+                            for &l in licenses {
+                                if !choice.contains(&l) {
+                                    choice.push(l);
+                                }
+                            }
+                            choice
+                        })
+                    }).collect();
+                }
+                choices
+            },
+        }
+    }
+
+    /// Returns whether `self` and `other` have the same meaning, regardless of
+    /// the order of their licenses and any duplicates.
+    ///
+    /// ```
+    /// use linfo::Expr;
+    ///
+    /// let a = Expr::parse("MIT OR Apache-2.0").unwrap();
+    /// let b = Expr::parse("Apache-2.0 OR MIT").unwrap();
+    /// let c = Expr::parse("Apache-2.0 AND MIT").unwrap();
+    ///
+    /// assert!(a.is_equivalent(&b));
+    /// assert!(!a.is_equivalent(&c));
+    /// ```
+    pub fn is_equivalent(&self, other: &Expr) -> bool {
+        fn same(a: &[&License], b: &[&License]) -> bool {
+            a.iter().all(|l| b.contains(l)) && b.iter().all(|l| a.contains(l))
+        }
+
+        // A choice that includes every license of another is never needed
+        fn minimal<'a>(choices: &'a [Vec<&License>]) -> Vec<&'a [&'a License]> {
+            choices.iter()
+                .filter(|c| !choices.iter().any(|d| {
+                    d.len() < c.len() && d.iter().all(|l| c.contains(l))
+                }))
+                .map(Vec::as_slice)
+                .collect()
+        }
+
+        let (a, b) = (self.choices(), other.choices());
+        let (a, b) = (minimal(&a), minimal(&b));
+        a.iter().all(|c| b.iter().any(|d| same(c, d))) &&
+        b.iter().all(|d| a.iter().any(|c| same(c, d)))
+    }
+
+    /// Returns what changed from `self` to `other`, such as when a dependency
+    /// is upgraded.
+    ///
+    /// ```
+    /// use linfo::Expr;
+    ///
+    /// let old = Expr::parse("MIT OR Apache-2.0").unwrap();
+    /// let new = Expr::parse("Apache-2.0").unwrap();
+    ///
+    /// let diff = old.diff(&new);
+    /// let mit = old.licenses().next().unwrap();
+    /// assert_eq!(diff.removed, [mit]);
+    /// assert_eq!(diff.choices_removed, diff.removed);
+    /// assert_eq!(diff.to_string(), "removed MIT; no longer a choice: MIT; now required: Apache-2.0");
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a Expr) -> Diff<'a> {
+        fn difference<'a>(a: &[&'a License], b: &[&License]) -> Vec<&'a License> {
+            let mut licenses: Vec<&License> = Vec::new();
+            for &license in a {
+                if !b.contains(&license) && !licenses.contains(&license) {
+                    licenses.push(license);
+                }
+            }
+            licenses
+        }
+
+        // The licenses that must be followed regardless of choice
+        fn required<'a>(choices: &[Vec<&'a License>]) -> Vec<&'a License> {
+            let (first, rest) = match choices.split_first() {
+                Some(split) => split,
+                None => return Vec::new(),
+            };
+            first.iter()
+                .filter(|l| rest.iter().all(|c| c.contains(l)))
+                .cloned()
+                .collect()
+        }
+
+        // A lone conjunction is a single option that isn't any of its licenses
+        fn options<'a>(choices: &[Vec<&'a License>]) -> Vec<&'a License> {
+            match choices {
+                [choice] if choice.len() > 1 => Vec::new(),
+                choices => choices.iter().flatten().cloned().collect(),
+            }
+        }
+
+        let (old, new) = (self.choices(), other.choices());
+        let choices_removed = if old.len() > 1 {
+            difference(&options(&old), &options(&new))
+        } else {
+            Vec::new()
+        };
+
+        let (old_licenses, new_licenses): (Vec<_>, Vec<_>) =
+            (self.licenses().collect(), other.licenses().collect());
+
+        Diff {
+            added: difference(&new_licenses, &old_licenses),
+            removed: difference(&old_licenses, &new_licenses),
+            choices_removed,
+            required_added: difference(&required(&new), &required(&old)),
+            strongest: (self.strongest_kind(), other.strongest_kind()),
         }
     }
+
+    /// Returns the most restrictive kind of any license in this expression.
+    ///
+    /// This is the worst case regardless of choice. For the kind of the best
+    /// choice, use [`any_choice`](#method.any_choice).
+    #[inline]
+    pub fn strongest_kind(&self) -> LicenseKind {
+        self.licenses().map(License::kind).max().unwrap_or(LicenseKind::Unknown)
+    }
+
+    /// Returns whether `f` holds for every license of at least one choice.
+    ///
+    /// See [`choices`](#method.choices).
+    ///
+    /// ```
+    /// use linfo::{spdx::LicenseKind, Expr};
+    ///
+    /// let expr = Expr::parse("MIT OR GPL-3.0-only").unwrap();
+    /// assert!(expr.any_choice(|l| l.kind() <= LicenseKind::Permissive));
+    /// assert!(!expr.every_choice(|l| l.kind() <= LicenseKind::Permissive));
+    ///
+    /// let expr = Expr::parse("MIT AND GPL-3.0-only").unwrap();
+    /// assert!(!expr.any_choice(|l| l.kind() <= LicenseKind::Permissive));
+    /// ```
+    pub fn any_choice<F>(&self, mut f: F) -> bool
+        where F: FnMut(&License) -> bool
+    {
+        fn any_choice<F>(expr: &Expr, f: &mut F) -> bool
+            where F: FnMut(&License) -> bool
+        {
+            match expr {
+                Expr::Single(l) => f(l),
+                Expr::Or(or) => or.0.iter().any(|e| any_choice(e, f)),
+                Expr::And(and) => and.0.iter().all(|e| any_choice(e, f)),
+            }
+        }
+        any_choice(self, &mut f)
+    }
+
+    /// Returns whether `f` holds for every license of every choice, and thus
+    /// regardless of which choice is made.
+    ///
+    /// See [`any_choice`](#method.any_choice).
+    #[inline]
+    pub fn every_choice<F>(&self, f: F) -> bool
+        where F: FnMut(&License) -> bool
+    {
+        self.licenses().all(f)
+    }
+
+    /// Returns whether this expression can be followed using only licenses
+    /// that are [libre](spdx/enum.SpdxLicense.html#method.is_libre).
+    ///
+    /// Use [`every_choice`](#method.every_choice) to check that every choice is
+    /// libre.
+    #[inline]
+    pub fn is_libre(&self) -> bool {
+        self.any_choice(License::is_libre)
+    }
+
+    /// Returns whether this expression can be followed using only licenses
+    /// that are [OSI approved](spdx/enum.SpdxLicense.html#method.is_osi_approved).
+    ///
+    /// Use [`every_choice`](#method.every_choice) to check that every choice is
+    /// OSI approved.
+    ///
+    /// ```
+    /// use linfo::{Expr, License};
+    ///
+    /// let expr = Expr::parse("MIT OR CC-BY-NC-4.0").unwrap();
+    /// assert!(expr.is_osi_approved());
+    /// assert!(!expr.every_choice(License::is_osi_approved));
+    /// ```
+    #[inline]
+    pub fn is_osi_approved(&self) -> bool {
+        self.any_choice(License::is_osi_approved)
+    }
+
+    /// Returns the exceptions in this expression that are added to licenses
+    /// they are not [designed for], such as `MIT WITH Classpath-exception-2.0`.
+    ///
+    /// Exceptions added to user-defined licenses are not reported.
+    ///
+    /// ```
+    /// use linfo::Expr;
+    ///
+    /// let expr = Expr::parse(
+    ///     "MIT WITH Classpath-exception-2.0 OR GPL-2.0-only WITH Classpath-exception-2.0",
+    /// ).unwrap();
+    /// let misapplied = expr.misapplied_exceptions();
+    ///
+    /// assert_eq!(misapplied.len(), 1);
+    /// assert_eq!(misapplied[0].to_string(), "Classpath-exception-2.0 is not designed for MIT");
+    /// ```
+    ///
+    /// [designed for]: spdx/enum.SpdxException.html#method.applies_to
+    pub fn misapplied_exceptions(&self) -> Vec<MisappliedException<'_>> {
+        self.licenses().filter_map(|license| match license {
+            License::With(base, exception) => match base.base() {
+                License::Spdx(l) if !exception.applies_to().contains(l) => {
+                    Some(MisappliedException { license, exception: *exception })
+                },
+                _ => None,
+            },
+            _ => None,
+        }).collect()
+    }
+
+    /// Returns whether this expression can be followed using only [Creative
+    /// Commons](spdx/enum.SpdxLicense.html#method.is_creative_commons)
+    /// licenses.
+    #[inline]
+    pub fn is_creative_commons(&self) -> bool {
+        self.any_choice(License::is_creative_commons)
+    }
 }
 
-/// A set of licenses separated by `OR`. For projects that are dual/n-ary
+/// A set of expressions separated by `OR`. For projects that are dual/n-ary
 /// licensed.
 ///
 /// See [`Expr::Or`](enum.Expr.html#variant.Or).
 ///
-/// Instances of this type always have two or more.
+/// Instances of this type always have two or more, none of which is an `OR`.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct Or(Vec<License>);
+pub struct Or(Vec<Expr>);
 
 impl Or {
-    /// Returns the underlying slice of licenses.
     #[inline]
-    pub fn as_slice(&self) -> &[License] {
+    pub(crate) fn new(exprs: Vec<Expr>) -> Self {
+        Or(exprs)
+    }
+
+    #[inline]
+    pub(crate) fn into_vec(self) -> Vec<Expr> {
+        self.0
+    }
+
+    /// Returns the underlying slice of expressions.
+    #[inline]
+    pub fn as_slice(&self) -> &[Expr] {
         self.0.as_slice()
     }
 }
 
-/// A set of licenses separated by `AND`. For projects that require restrictions
-/// of multiple licenses.
+/// A set of expressions separated by `AND`. For projects that require
+/// restrictions of multiple licenses.
 ///
 /// See [`Expr::And`](enum.Expr.html#variant.And).
+///
+/// Instances of this type always have two or more, none of which is an `AND`.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct And(Vec<License>);
+pub struct And(Vec<Expr>);
 
 impl And {
-    /// Returns the underlying slice of licenses.
     #[inline]
-    pub fn as_slice(&self) -> &[License] {
+    pub(crate) fn new(exprs: Vec<Expr>) -> Self {
+        And(exprs)
+    }
+
+    #[inline]
+    pub(crate) fn into_vec(self) -> Vec<Expr> {
+        self.0
+    }
+
+    /// Returns the underlying slice of expressions.
+    #[inline]
+    pub fn as_slice(&self) -> &[Expr] {
         self.0.as_slice()
     }
 }
 
-mod impl_display {
-    use super::*;
+/// An iterator over the licenses of an expression.
+///
+/// See [`Expr::licenses`](enum.Expr.html#method.licenses).
+#[derive(Clone, Debug)]
+pub struct Licenses<'a> {
+    stack: Vec<&'a Expr>,
+}
 
-    type Iter<'a> = core::slice::Iter<'a, License>;
+impl<'a> Iterator for Licenses<'a> {
+    type Item = &'a License;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let exprs = match self.stack.pop()? {
+                Expr::Single(l) => return Some(l),
+                Expr::Or(or) => or.as_slice(),
+                Expr::And(and) => and.as_slice(),
+            };
+            self.stack.extend(exprs.iter().rev());
+        }
+    }
+}
 
-    fn display_iter(mut iter: Iter, sep: &str, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(first) = iter.next() {
-            fmt::Display::fmt(&first, f)?;
-        } else {
-            return Ok(());
+/// The changes between two expressions.
+///
+/// See [`Expr::diff`](enum.Expr.html#method.diff).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diff<'a> {
+    /// Licenses that newly appear in the expression.
+    pub added: Vec<&'a License>,
+    /// Licenses that no longer appear in the expression.
+    pub removed: Vec<&'a License>,
+    /// Licenses of an `OR` expression that can no longer be chosen.
+    pub choices_removed: Vec<&'a License>,
+    /// Licenses whose conditions must now be followed regardless of choice.
+    pub required_added: Vec<&'a License>,
+    /// The most restrictive kind of license before and after.
+    pub strongest: (LicenseKind, LicenseKind),
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = 0;
+        let mut part = |f: &mut fmt::Formatter, label: &str, licenses: &[&License]| {
+            if licenses.is_empty() {
+                return Ok(());
+            }
+            if parts != 0 {
+                f.write_str("; ")?;
+            }
+            parts += 1;
+            f.write_str(label)?;
+            for (i, license) in licenses.iter().enumerate() {
+                f.write_str(if i == 0 { " " } else { ", " })?;
+                license.fmt(f)?;
+            }
+            Ok(())
+        };
+        part(f, "added", &self.added)?;
+        part(f, "removed", &self.removed)?;
+        part(f, "no longer a choice:", &self.choices_removed)?;
+        part(f, "now required:", &self.required_added)?;
+
+        let (old, new) = self.strongest;
+        if old != new {
+            if parts != 0 {
+                f.write_str("; ")?;
+            }
+            parts += 1;
+            write!(f, "strongest kind {} -> {}", old, new)?;
         }
-        for next in iter {
-            write!(f, "{}{}", sep, next)?;
+        if parts == 0 {
+            f.write_str("no changes")?;
+        }
+        Ok(())
+    }
+}
+
+impl Diff<'_> {
+    /// Returns whether nothing changed in meaning.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() &&
+        self.removed.is_empty() &&
+        self.choices_removed.is_empty() &&
+        self.required_added.is_empty() &&
+        self.strongest.0 == self.strongest.1
+    }
+}
+
+/// An exception added to a license that it is not designed for.
+///
+/// See [`Expr::misapplied_exceptions`](enum.Expr.html#method.misapplied_exceptions).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MisappliedException<'a> {
+    /// The license with the exception.
+    pub license: &'a License,
+    /// The exception.
+    pub exception: SpdxException,
+}
+
+impl fmt::Display for MisappliedException<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let base = match self.license {
+            License::With(base, _) => base.base(),
+            license => license,
+        };
+        write!(f, "{} is not designed for {}", self.exception, base)
+    }
+}
+
+mod impl_display {
+    use super::*;
+
+    fn display_list(list: &[Expr], sep: &str, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, expr) in list.iter().enumerate() {
+            if i != 0 {
+                f.write_str(sep)?;
+            }
+            match expr {
+                Expr::Single(l) => fmt::Display::fmt(l, f)?,
+                expr => write!(f, "({})", expr)?,
+            }
         }
         Ok(())
     }
 
     impl fmt::Display for Or {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            display_iter(self.0.iter(), " OR ", f)
+            display_list(&self.0, " OR ", f)
         }
     }
 
     impl fmt::Display for And {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            display_iter(self.0.iter(), " AND ", f)
+            display_list(&self.0, " AND ", f)
         }
     }
 }
@@ -244,6 +731,7 @@ mod impl_display {
 #[cfg(feature = "serde")]
 mod serde {
     use core::fmt;
+    use alloc::string::ToString;
     use serde::{
         ser::{Serialize, Serializer},
         de::{self, Deserialize, Deserializer, Visitor},
@@ -288,6 +776,7 @@ mod serde {
 
 #[cfg(test)]
 mod tests {
+    use alloc::{format, string::ToString};
     use super::*;
 
     #[test]
@@ -317,4 +806,147 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn expr_with_exception() {
+        let expr = "GPL-2.0-only WITH Classpath-exception-2.0 OR MIT";
+        let e = Expr::parse(expr).unwrap();
+        assert_eq!(&e, expr);
+        assert_eq!(e.to_string(), expr);
+        assert_eq!(e.licenses().next().unwrap().base(), &License::Spdx(SpdxLicense::Gpl2Only));
+
+        assert_eq!(
+            Expr::parse("MIT WITH Not-An-Exception"),
+            Err(ParseError::UnknownExceptionId("Not-An-Exception")),
+        );
+    }
+
+    #[test]
+    fn unknown_ids_in_lists() {
+        let parse = |s| Expr::parse(s).unwrap().to_string();
+
+        assert_eq!(parse("MIT OR Not-A-License"), "MIT");
+        assert_eq!(parse("Not-A-License AND Apache-2.0 AND ISC"), "Apache-2.0 AND ISC");
+        assert_eq!(parse("MIT OR Apache-2.0 WITH Not-An-Exception"), "MIT");
+        assert_eq!(parse("MIT OR (ISC AND Not-A-License)"), "MIT OR ISC");
+        assert_eq!(
+            Expr::parse("Not-A-License OR Not-Either"),
+            Err(ParseError::UnknownLicenseId("Not-A-License")),
+        );
+
+        assert_eq!(
+            Expr::parse_strict("MIT OR Not-A-License"),
+            Err(ParseError::UnknownLicenseId("Not-A-License")),
+        );
+        assert_eq!(
+            Expr::parse_strict("Not-A-License AND Apache-2.0"),
+            Err(ParseError::UnknownLicenseId("Not-A-License")),
+        );
+        assert_eq!(
+            Expr::parse_strict("MIT OR Apache-2.0 WITH Not-An-Exception"),
+            Err(ParseError::UnknownExceptionId("Not-An-Exception")),
+        );
+        assert_eq!(
+            Expr::parse_strict("MIT OR (ISC AND Not-A-License)"),
+            Err(ParseError::UnknownLicenseId("Not-A-License")),
+        );
+    }
+
+    #[test]
+    fn parenthesized() {
+        let parse = |s| Expr::parse(s).unwrap().to_string();
+
+        assert_eq!(parse("(MIT)"), "MIT");
+        assert_eq!(parse(" ((MIT OR Apache-2.0)) "), "MIT OR Apache-2.0");
+        assert_eq!(parse("(MIT OR ISC) OR Apache-2.0"), "MIT OR ISC OR Apache-2.0");
+        assert_eq!(parse("MIT AND (ISC AND (OpenSSL))"), "MIT AND ISC AND OpenSSL");
+        assert_eq!(
+            parse("(GPL-2.0-only WITH Classpath-exception-2.0) OR MIT"),
+            "GPL-2.0-only WITH Classpath-exception-2.0 OR MIT",
+        );
+
+        assert_eq!(
+            parse("(MIT OR Apache-2.0) AND Unicode-DFS-2016"),
+            "(MIT OR Apache-2.0) AND Unicode-DFS-2016",
+        );
+        assert_eq!(parse("MIT OR ISC AND OpenSSL"), "MIT OR (ISC AND OpenSSL)");
+        assert_eq!(
+            parse("((MIT AND ISC) OR Apache-2.0) AND (OpenSSL AND Zlib)"),
+            "((MIT AND ISC) OR Apache-2.0) AND OpenSSL AND Zlib",
+        );
+        assert_eq!(Expr::parse("MIT OR ()"), Err(ParseError::Empty));
+        assert_eq!(
+            Expr::parse("(MIT OR ISC"),
+            Err(ParseError::UnknownLicenseId("(MIT OR ISC")),
+        );
+    }
+
+    #[test]
+    fn choices() {
+        let parse = |s| Expr::parse(s).unwrap();
+
+        let single = parse("GPL-3.0-only WITH Classpath-exception-2.0");
+        assert!(single.is_libre() && single.is_osi_approved());
+        assert!(!single.is_creative_commons());
+
+        let or = parse("CC-BY-4.0 OR LicenseRef-Custom");
+        assert!(or.is_creative_commons());
+        assert!(!or.every_choice(License::is_creative_commons));
+
+        let and = parse("CC-BY-4.0 AND LicenseRef-Custom");
+        assert!(!and.is_creative_commons());
+        assert!(and.any_choice(|l| l.id().starts_with("CC-") || l.id().contains("Ref")));
+
+        let nested = parse("(CC-BY-4.0 OR MIT) AND (CC0-1.0 OR LicenseRef-Custom)");
+        assert_eq!(nested.choices().len(), 4);
+        assert!(nested.is_creative_commons());
+        assert!(!parse("(CC-BY-4.0 OR MIT) AND LicenseRef-Custom").is_creative_commons());
+
+        assert!(parse("MIT OR (Apache-2.0 AND MIT)").is_equivalent(&parse("MIT")));
+        assert!(parse("(MIT OR ISC) AND Zlib").is_equivalent(&parse("Zlib AND MIT OR ISC AND Zlib")));
+        assert!(!parse("(MIT OR ISC) AND Zlib").is_equivalent(&parse("MIT OR ISC AND Zlib")));
+    }
+
+    #[test]
+    fn misapplied_exceptions() {
+        let expr = Expr::parse(
+            "Apache-2.0 WITH LLVM-exception AND \
+             LicenseRef-Custom WITH Classpath-exception-2.0 AND \
+             LGPL-2.1-only WITH GCC-exception-3.1",
+        ).unwrap();
+        let misapplied = expr.misapplied_exceptions();
+        assert_eq!(misapplied.len(), 1);
+        assert_eq!(misapplied[0].license, expr.licenses().nth(2).unwrap());
+        assert_eq!(misapplied[0].exception, SpdxException::Gcc3_1);
+    }
+
+    #[test]
+    fn expr_diff() {
+        let parse = |s| Expr::parse(s).unwrap();
+
+        let (old, new) = (parse("MIT"), parse("MIT AND Unicode-DFS-2016"));
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, [new.licenses().nth(1).unwrap()]);
+        assert!(diff.removed.is_empty() && diff.choices_removed.is_empty());
+        assert_eq!(diff.required_added, diff.added);
+        assert_eq!(diff.to_string(), "added Unicode-DFS-2016; now required: Unicode-DFS-2016");
+
+        let (old, new) = (parse("MIT OR GPL-3.0-only"), parse("GPL-3.0-only AND MIT"));
+        let diff = old.diff(&new);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.choices_removed.len(), 2);
+        assert_eq!(diff.required_added.len(), 2);
+
+        let (old, new) = (parse("MIT OR Apache-2.0"), parse("Apache-2.0 OR MIT OR MIT"));
+        assert!(old.diff(&new).is_empty());
+        assert_eq!(old.diff(&new).to_string(), "no changes");
+
+        let (old, new) = (parse("MIT OR Apache-2.0"), parse("(MIT OR Apache-2.0) AND Unicode-DFS-2016"));
+        let diff = old.diff(&new);
+        assert!(diff.choices_removed.is_empty());
+        assert_eq!(diff.to_string(), "added Unicode-DFS-2016; now required: Unicode-DFS-2016");
+
+        let (old, new) = (parse("MIT"), parse("AGPL-3.0-only"));
+        assert_eq!(old.diff(&new).strongest, (LicenseKind::Permissive, LicenseKind::NetworkCopyleft));
+    }
 }
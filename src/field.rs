@@ -0,0 +1,219 @@
+//! License fields that may hold no license.
+
+use core::{
+    convert::{TryFrom, TryInto},
+    fmt,
+};
+use crate::{spdx::ListVersion, Expr, ParseError, SpdxLicense};
+
+/// The value of a license field in a manifest or SBOM, which may hold the
+/// special SPDX values `NONE` and `NOASSERTION` instead of an expression.
+///
+/// ```
+/// use linfo::{Expr, LicenseField};
+///
+/// assert_eq!(LicenseField::parse("NOASSERTION"), Ok(LicenseField::NoAssertion));
+/// assert_eq!(LicenseField::parse("NONE"), Ok(LicenseField::None));
+///
+/// let field = LicenseField::parse("MIT OR Apache-2.0").unwrap();
+/// assert_eq!(field.expr(), Some(&Expr::parse("MIT OR Apache-2.0").unwrap()));
+///
+/// // A package without a license field makes no assertion.
+/// assert_eq!(LicenseField::from(None), LicenseField::NoAssertion);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LicenseField {
+    /// There is no license (`NONE`).
+    None,
+    /// The license was not determined (`NOASSERTION`).
+    NoAssertion,
+    /// A license expression.
+    Expr(Expr),
+}
+
+impl From<SpdxLicense> for LicenseField {
+    #[inline]
+    fn from(l: SpdxLicense) -> Self {
+        LicenseField::Expr(l.into())
+    }
+}
+
+impl From<Expr> for LicenseField {
+    #[inline]
+    fn from(expr: Expr) -> Self {
+        LicenseField::Expr(expr)
+    }
+}
+
+impl From<Option<Expr>> for LicenseField {
+    #[inline]
+    fn from(expr: Option<Expr>) -> Self {
+        expr.map_or(LicenseField::NoAssertion, LicenseField::Expr)
+    }
+}
+
+impl fmt::Display for LicenseField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LicenseField::None => f.write_str("NONE"),
+            LicenseField::NoAssertion => f.write_str("NOASSERTION"),
+            LicenseField::Expr(expr) => expr.fmt(f),
+        }
+    }
+}
+
+impl PartialEq<str> for LicenseField {
+    #[inline]
+    fn eq(&self, s: &str) -> bool {
+        match (self, s.trim()) {
+            (LicenseField::None, "NONE") => true,
+            (LicenseField::NoAssertion, "NOASSERTION") => true,
+            (LicenseField::Expr(expr), s) => expr == s,
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<LicenseField> for str {
+    #[inline]
+    fn eq(&self, field: &LicenseField) -> bool {
+        field == self
+    }
+}
+
+impl<'a> TryFrom<&'a str> for LicenseField {
+    type Error = ParseError<'a>;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        match s.trim() {
+            "NONE" => Ok(LicenseField::None),
+            "NOASSERTION" => Ok(LicenseField::NoAssertion),
+            s => Expr::parse_strict(s).map(LicenseField::Expr),
+        }
+    }
+}
+
+impl LicenseField {
+    /// Attempts to parse `input` and returns a
+    /// [`ParseError`](enum.ParseError.html) on error.
+    ///
+    /// Unknown ids are errors, as with
+    /// [`Expr::parse_strict`](enum.Expr.html#method.parse_strict).
+    #[inline]
+    pub fn parse<'i, I>(input: I) -> Result<Self, ParseError<'i>>
+        where I: TryInto<Self, Error = ParseError<'i>> + 'i
+    {
+        input.try_into()
+    }
+
+    /// Parses `input` like [`parse`](#method.parse), but only accepts SPDX
+    /// licenses that are valid in `version` of the license list. See
+    /// [`Expr::parse_in`](enum.Expr.html#method.parse_in).
+    pub fn parse_in(input: &str, version: ListVersion) -> Result<Self, ParseError<'_>> {
+        match input.trim() {
+            "NONE" => Ok(LicenseField::None),
+            "NOASSERTION" => Ok(LicenseField::NoAssertion),
+            s => Expr::parse_in(s, version).map(LicenseField::Expr),
+        }
+    }
+
+    /// Returns the expression if this is one.
+    #[inline]
+    pub fn expr(&self) -> Option<&Expr> {
+        match self {
+            LicenseField::Expr(expr) => Some(expr),
+            _ => None,
+        }
+    }
+
+    /// Returns the expression if this is one.
+    #[inline]
+    pub fn into_expr(self) -> Option<Expr> {
+        match self {
+            LicenseField::Expr(expr) => Some(expr),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this is `NONE`.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        matches!(self, LicenseField::None)
+    }
+
+    /// Returns whether this is `NOASSERTION`.
+    #[inline]
+    pub fn is_no_assertion(&self) -> bool {
+        matches!(self, LicenseField::NoAssertion)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use core::fmt;
+    use serde::{
+        ser::{Serialize, Serializer},
+        de::{self, Deserialize, Deserializer, Visitor},
+    };
+    use super::LicenseField;
+
+    struct FieldVisitor;
+
+    impl<'de> Visitor<'de> for FieldVisitor {
+        type Value = LicenseField;
+
+        #[inline]
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a license expression, NONE, or NOASSERTION")
+        }
+
+        #[inline]
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where E: de::Error,
+        {
+            LicenseField::parse(v).map_err(E::custom)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for LicenseField {
+        #[inline]
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>
+        {
+            deserializer.deserialize_str(FieldVisitor)
+        }
+    }
+
+    impl Serialize for LicenseField {
+        #[inline]
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            s.collect_str(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use super::*;
+
+    #[test]
+    fn parse_field() {
+        let cases = [
+            (" NONE ", LicenseField::None),
+            ("NOASSERTION", LicenseField::NoAssertion),
+            ("MIT", SpdxLicense::Mit.into()),
+        ];
+        for (s, field) in cases.iter().cloned() {
+            assert_eq!(LicenseField::parse(s), Ok(field.clone()));
+            assert_eq!(LicenseField::parse(field.to_string().as_str()), Ok(field));
+        }
+        assert_eq!(LicenseField::parse(""), Err(ParseError::Empty));
+        assert!(LicenseField::parse("none").is_err());
+
+        assert_eq!(LicenseField::None, *"NONE");
+        assert_eq!(LicenseField::from(SpdxLicense::Mit), *"MIT");
+        assert_ne!(LicenseField::NoAssertion, *"NONE");
+    }
+}
@@ -6,31 +6,77 @@
 #[cfg(feature = "std")]
 extern crate std as core;
 
+#[cfg(feature = "std")]
+extern crate std as alloc;
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
 use core::{
     convert::{TryFrom, TryInto},
     fmt,
 };
+use alloc::boxed::Box;
 
 #[macro_use]
 mod macros;
+mod field;
 mod util;
 
+pub mod compat;
 pub mod expr;
 pub mod spdx;
 
+#[cfg(feature = "std")]
+pub mod build;
+
+#[cfg(feature = "std")]
+pub mod bundle;
+
+#[cfg(feature = "std")]
+pub mod checklist;
+
+#[cfg(feature = "std")]
+pub mod conclusion;
+
+#[cfg(feature = "std")]
+pub mod linkage;
+
+#[cfg(feature = "std")]
+pub mod policy;
+
+#[cfg(feature = "std")]
+pub mod solve;
+
+#[cfg(feature = "cargo")]
+pub mod baseline;
+
+#[cfg(feature = "cargo")]
+pub mod cargo;
+
+#[cfg(all(feature = "std", feature = "serde"))]
+pub mod clarify;
+
+#[cfg(feature = "sbom")]
+pub mod sbom;
+
 #[doc(inline)]
 pub use self::{
     expr::Expr,
+    field::LicenseField,
     spdx::SpdxLicense,
 };
 
 /// A known license.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(untagged))]
 pub enum License {
     /// A commonly found license listed [here](https://spdx.org/licenses).
     Spdx(SpdxLicense),
+    /// A user-defined license, such as those in SPDX documents.
+    Ref(spdx::LicenseRef),
+    /// A license with an exception that grants additional permissions, such
+    /// as `GPL-2.0-only WITH Classpath-exception-2.0`.
+    With(Box<License>, spdx::SpdxException),
     // TODO: Replace with `#[non_exhaustive]` when stable
     #[doc(hidden)]
     _NonExhaustive(util::Never),
@@ -43,10 +89,19 @@ impl From<SpdxLicense> for License {
     }
 }
 
+impl From<spdx::LicenseRef> for License {
+    #[inline]
+    fn from(l: spdx::LicenseRef) -> Self {
+        Self::Ref(l)
+    }
+}
+
 impl fmt::Display for License {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Spdx(l) => l.fmt(f),
+            Self::Ref(l) => l.fmt(f),
+            Self::With(l, e) => write!(f, "{} WITH {}", l, e),
             Self::_NonExhaustive(never) => never.consume(),
         }
     }
@@ -57,8 +112,15 @@ impl<'a> TryFrom<&'a str> for License {
 
     fn try_from(s: &'a str) -> Result<Self, Self::Error> {
         let s = s.trim();
-        // Ok while SPDX is the only variant
-        SpdxLicense::parse(s).map(|l| l.into())
+        if let Some(with) = s.find(" WITH ") {
+            let license = License::try_from(&s[..with])?;
+            let exception = spdx::SpdxException::parse(s[(with + 6)..].trim())?;
+            Ok(License::With(Box::new(license), exception))
+        } else if s.starts_with("LicenseRef-") || s.starts_with("DocumentRef-") {
+            spdx::LicenseRef::parse(s).map(License::Ref)
+        } else {
+            SpdxLicense::parse(s).map(|l| l.into())
+        }
     }
 }
 
@@ -71,15 +133,167 @@ impl License {
         input.try_into()
     }
 
+    /// Parses `input` like [`parse`](#method.parse), but only accepts SPDX
+    /// licenses that are valid in `version` of the license list. See
+    /// [`Expr::parse_in`](enum.Expr.html#method.parse_in).
+    pub fn parse_in(input: &str, version: spdx::ListVersion) -> Result<Self, ParseError<'_>> {
+        let license = License::parse(input)?;
+        match license.base() {
+            License::Spdx(l) if !l.is_valid_in(version) => {
+                let start = input.find(l.id()).unwrap_or(0);
+                Err(ParseError::UnknownLicenseId(&input[start..(start + l.id().len())]))
+            },
+            _ => Ok(license),
+        }
+    }
+
     /// Returns the string identifier of this license. This is usually the same
     /// string used to parse the license.
+    ///
+    /// For a license with an exception, this is the identifier of the license
+    /// alone.
     #[inline]
     pub fn id(&self) -> &str {
         match self {
             License::Spdx(l) => l.id(),
+            License::Ref(l) => l.id(),
+            License::With(l, _) => l.id(),
             License::_NonExhaustive(never) => never.consume(),
         }
     }
+
+    /// Returns the full name of this license, or the identifier of a
+    /// user-defined license.
+    #[inline]
+    pub fn name(&self) -> &str {
+        match self {
+            License::Spdx(l) => l.name(),
+            License::Ref(l) => l.id(),
+            License::With(l, _) => l.name(),
+            License::_NonExhaustive(never) => never.consume(),
+        }
+    }
+}
+
+impl License {
+    /// Returns this license without its exception, if any.
+    ///
+    /// ```
+    /// use linfo::{License, SpdxLicense};
+    ///
+    /// let l = License::parse("GPL-2.0-only WITH Classpath-exception-2.0").unwrap();
+    /// assert_eq!(l.base(), &License::Spdx(SpdxLicense::Gpl2Only));
+    /// assert_eq!(l.to_string(), "GPL-2.0-only WITH Classpath-exception-2.0");
+    /// ```
+    #[inline]
+    pub fn base(&self) -> &License {
+        match self {
+            License::With(l, _) => l.base(),
+            l => l,
+        }
+    }
+
+    /// Returns the exception added to this license, if any.
+    #[inline]
+    pub fn exception(&self) -> Option<spdx::SpdxException> {
+        match self {
+            License::With(_, e) => Some(*e),
+            _ => None,
+        }
+    }
+
+    /// Returns the broad category of this license.
+    ///
+    /// Nothing is known about user-defined licenses, so they are
+    /// [`Unknown`](spdx/enum.LicenseKind.html#variant.Unknown).
+    #[inline]
+    pub fn kind(&self) -> spdx::LicenseKind {
+        match self.base() {
+            License::Spdx(l) => l.kind(),
+            _ => spdx::LicenseKind::Unknown,
+        }
+    }
+
+    /// Returns whether this license is [libre], ignoring any exception.
+    ///
+    /// User-defined licenses are never considered libre.
+    ///
+    /// [libre]: spdx/enum.SpdxLicense.html#method.is_libre
+    #[inline]
+    pub fn is_libre(&self) -> bool {
+        match self.base() {
+            License::Spdx(l) => l.is_libre(),
+            _ => false,
+        }
+    }
+
+    /// Returns whether this license is [OSI approved], ignoring any exception.
+    ///
+    /// User-defined licenses are never considered OSI approved.
+    ///
+    /// [OSI approved]: spdx/enum.SpdxLicense.html#method.is_osi_approved
+    #[inline]
+    pub fn is_osi_approved(&self) -> bool {
+        match self.base() {
+            License::Spdx(l) => l.is_osi_approved(),
+            _ => false,
+        }
+    }
+
+    /// Returns whether this is a [Creative Commons] license.
+    ///
+    /// [Creative Commons]: spdx/enum.SpdxLicense.html#method.is_creative_commons
+    #[inline]
+    pub fn is_creative_commons(&self) -> bool {
+        match self.base() {
+            License::Spdx(l) => l.is_creative_commons(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use serde::{
+        ser::{Serialize, Serializer},
+        de::{self, Deserialize, Deserializer, Visitor},
+    };
+    use super::*;
+
+    struct LicenseVisitor;
+
+    impl<'de> Visitor<'de> for LicenseVisitor {
+        type Value = License;
+
+        #[inline]
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a license string")
+        }
+
+        #[inline]
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where E: de::Error,
+        {
+            License::parse(v).map_err(E::custom)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for License {
+        #[inline]
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>
+        {
+            deserializer.deserialize_str(LicenseVisitor)
+        }
+    }
+
+    impl Serialize for License {
+        #[inline]
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            s.collect_str(self)
+        }
+    }
 }
 
 /// An error returned when attempting to parse a [`License`](enum.License.html).
@@ -89,6 +303,8 @@ pub enum ParseError<'a> {
     Empty,
     /// An error returned when a license name is unknown.
     UnknownLicenseId(&'a str),
+    /// An error returned when an exception name is unknown.
+    UnknownExceptionId(&'a str),
 }
 
 impl fmt::Display for ParseError<'_> {
@@ -100,6 +316,9 @@ impl fmt::Display for ParseError<'_> {
             ParseError::UnknownLicenseId(id) => {
                 write!(f, "'{}' is not a known license ID", id)
             },
+            ParseError::UnknownExceptionId(id) => {
+                write!(f, "'{}' is not a known exception ID", id)
+            },
         }
     }
 }
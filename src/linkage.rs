@@ -0,0 +1,343 @@
+//! How the copyleft licenses of dependencies apply to a product, depending on
+//! how they are combined with it.
+//!
+//! Each dependency is given a [`Linkage`](enum.Linkage.html), which decides
+//! whether its license [propagates] to the product and which
+//! [`Obligation`](enum.Obligation.html)s it triggers. A license that
+//! propagates conflicts with the product's outbound license unless the latter
+//! is a compatible copyleft license.
+//!
+//! ```
+//! use linfo::{linkage::{self, Linkage, Obligation}, Expr};
+//!
+//! let lgpl = Expr::parse("LGPL-2.1-only").unwrap();
+//! let gpl = Expr::parse("GPL-3.0-only").unwrap();
+//! let outbound = Expr::parse("MIT").unwrap();
+//!
+//! let analysis = linkage::analyze(vec![
+//!     ("a", &lgpl, Linkage::Static),
+//!     ("b", &gpl, Linkage::SeparateProcess),
+//! ], &outbound);
+//!
+//! assert!(analysis.conflicts().next().is_none());
+//! assert_eq!(analysis.findings[0].obligations, [
+//!     Obligation::DiscloseSource,
+//!     Obligation::AllowRelinking,
+//! ]);
+//!
+//! let analysis = linkage::analyze(vec![("b", &gpl, Linkage::Static)], &outbound);
+//! assert_eq!(
+//!     analysis.conflicts().next().unwrap().to_string(),
+//!     "b: GPL-3.0-only via static linking propagates to the product, which is \
+//!      not licensed under it; requires: license product under it, offer \
+//!      product source",
+//! );
+//! ```
+//!
+//! Licenses that are not copyleft, including those of an unknown kind, are not
+//! reported. Like [`compat`](../compat/index.html), this should be paired with
+//! a [`Policy`](../policy/struct.Policy.html).
+//!
+//! This is not legal advice.
+//!
+//! [propagates]: struct.Finding.html#structfield.propagates
+
+use core::fmt;
+use crate::{
+    compat,
+    spdx::{LicenseKind, SpdxException},
+    Expr,
+    License,
+    SpdxLicense,
+};
+
+/// How a dependency is combined with the product.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Linkage {
+    /// Linked into the same binary. This is the default for Rust crates.
+    Static,
+    /// Loaded from a shared library at runtime.
+    Dynamic,
+    /// Copied into the product's own source code.
+    SourceIncluded,
+    /// Run as a separate program that the product communicates with.
+    SeparateProcess,
+}
+
+impl fmt::Display for Linkage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Linkage::Static => "static linking",
+            Linkage::Dynamic => "dynamic linking",
+            Linkage::SourceIncluded => "source inclusion",
+            Linkage::SeparateProcess => "separate process",
+        })
+    }
+}
+
+/// How far the terms of a copyleft license extend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Scope {
+    /// Only the licensed files themselves, such as with the MPL.
+    File,
+    /// The library, which users must be able to replace with a modified
+    /// version, such as with the LGPL.
+    Library,
+    /// The whole work, such as with the GPL.
+    Work,
+    /// The whole work, including when it is only used over a network, such as
+    /// with the AGPL.
+    Network,
+}
+
+impl Scope {
+    /// Returns the scope of `license`, or `None` if it is not copyleft.
+    pub fn of(license: SpdxLicense) -> Option<Scope> {
+        use SpdxLicense::*;
+
+        match license {
+            Lgpl2Only | Lgpl2OrLater |
+            Lgpl2_1Only | Lgpl2_1OrLater |
+            Lgpl3Only | Lgpl3OrLater => Some(Scope::Library),
+            _ => match license.kind() {
+                LicenseKind::WeakCopyleft => Some(Scope::File),
+                LicenseKind::StrongCopyleft => Some(Scope::Work),
+                LicenseKind::NetworkCopyleft => Some(Scope::Network),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Something a copyleft license requires when distributing the product.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Obligation {
+    /// Offer the source code of the dependency, including any changes made to
+    /// it.
+    DiscloseSource,
+    /// Let users relink the product with a modified version of the dependency,
+    /// such as by linking dynamically or providing object files.
+    AllowRelinking,
+    /// License the product as a whole under the dependency's license.
+    LicenseProduct,
+    /// Offer the source code of the product as a whole.
+    DiscloseProductSource,
+    /// Offer the source code to users interacting with the product over a
+    /// network.
+    NetworkDisclose,
+}
+
+impl fmt::Display for Obligation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Obligation::DiscloseSource => "offer source",
+            Obligation::AllowRelinking => "allow relinking",
+            Obligation::LicenseProduct => "license product under it",
+            Obligation::DiscloseProductSource => "offer product source",
+            Obligation::NetworkDisclose => "offer source to network users",
+        })
+    }
+}
+
+/// How the copyleft license of a dependency applies to the product.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding<'a> {
+    /// The name of the dependency.
+    pub package: &'a str,
+    /// The copyleft license.
+    pub license: &'a License,
+    /// How far `license` extends.
+    pub scope: Scope,
+    /// How the dependency is combined with the product.
+    pub linkage: Linkage,
+    /// Whether `license` extends to the product as a whole.
+    pub propagates: bool,
+    /// Whether `license` propagates but the outbound license does not satisfy
+    /// it.
+    pub conflict: bool,
+    /// What must be done when distributing the product.
+    pub obligations: Vec<Obligation>,
+}
+
+impl fmt::Display for Finding<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} via {}", self.package, self.license, self.linkage)?;
+        if self.conflict {
+            f.write_str(" propagates to the product, which is not licensed under it")?;
+        } else if self.propagates {
+            f.write_str(" propagates to the product")?;
+        }
+        for (i, obligation) in self.obligations.iter().enumerate() {
+            f.write_str(if i == 0 { "; requires: " } else { ", " })?;
+            obligation.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Finding<'a> {
+    fn new(
+        package: &'a str,
+        license: &'a License,
+        linkage: Linkage,
+        outbound: &Expr,
+    ) -> Option<Self> {
+        use Obligation::*;
+
+        let spdx = match license.base() {
+            License::Spdx(l) => *l,
+            _ => return None,
+        };
+        let scope = Scope::of(spdx)?;
+
+        // Linking exceptions only apply to linking, not to copying the source.
+        let linkage_allowed = linkage == Linkage::SeparateProcess || (
+            linkage != Linkage::SourceIncluded &&
+            license.exception().is_some_and(is_linking_exception)
+        );
+
+        let (propagates, obligations) = match (scope, linkage) {
+            (Scope::Network, _) if linkage_allowed => {
+                (false, vec![DiscloseSource, NetworkDisclose])
+            },
+            (_, _) if linkage_allowed => (false, vec![DiscloseSource]),
+            (Scope::File, _) => (false, vec![DiscloseSource]),
+            (Scope::Library, Linkage::Static) => (false, vec![DiscloseSource, AllowRelinking]),
+            (Scope::Library, Linkage::Dynamic) => (false, vec![DiscloseSource]),
+            (Scope::Network, _) => {
+                (true, vec![LicenseProduct, DiscloseProductSource, NetworkDisclose])
+            },
+            _ => (true, vec![LicenseProduct, DiscloseProductSource]),
+        };
+
+        Some(Finding {
+            package,
+            license,
+            scope,
+            linkage,
+            propagates,
+            conflict: propagates && !satisfies(outbound, spdx),
+            obligations,
+        })
+    }
+
+    /// Used to pick the least demanding choice of an expression.
+    fn cost(&self) -> (bool, bool, usize) {
+        (self.conflict, self.propagates, self.obligations.len())
+    }
+}
+
+/// The result of [`analyze`](fn.analyze.html).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Analysis<'a> {
+    /// How each copyleft license applies, in the order of dependencies.
+    pub findings: Vec<Finding<'a>>,
+}
+
+impl<'a> Analysis<'a> {
+    /// Returns the licenses that propagate to the product.
+    pub fn propagated(&self) -> impl Iterator<Item = &Finding<'a>> {
+        self.findings.iter().filter(|f| f.propagates)
+    }
+
+    /// Returns the licenses that propagate to the product but are not
+    /// satisfied by its outbound license.
+    pub fn conflicts(&self) -> impl Iterator<Item = &Finding<'a>> {
+        self.findings.iter().filter(|f| f.conflict)
+    }
+}
+
+/// Returns how the copyleft licenses of `dependencies` apply to a product
+/// licensed under `outbound`.
+///
+/// The least demanding choice of each `OR` is assumed to be taken.
+pub fn analyze<'a, I>(dependencies: I, outbound: &Expr) -> Analysis<'a>
+    where I: IntoIterator<Item = (&'a str, &'a Expr, Linkage)>
+{
+    let mut analysis = Analysis::default();
+    for (package, expr, linkage) in dependencies {
+        let finding = |l| Finding::new(package, l, linkage, outbound);
+        let choice = expr.choices().into_iter()
+            .map(|licenses| licenses.into_iter().filter_map(finding).collect::<Vec<_>>())
+            .min_by_key(|findings| {
+                findings.iter().map(Finding::cost).fold((false, false, 0), |a, b| {
+                    (a.0 || b.0, a.1 || b.1, a.2 + b.2)
+                })
+            });
+        analysis.findings.extend(choice.into_iter().flatten());
+    }
+    analysis
+}
+
+/// Returns whether a product licensed under `outbound` satisfies the terms of
+/// the copyleft `license` applying to it as a whole.
+fn satisfies(outbound: &Expr, license: SpdxLicense) -> bool {
+    let satisfies = |out: &License| match out.base() {
+        License::Spdx(out) => {
+            out.kind().is_copyleft() &&
+            out.kind() >= license.kind() &&
+            compat::are_compatible_spdx(*out, license)
+        },
+        _ => false,
+    };
+    // Recipients may make any choice
+    outbound.choices().iter().all(|choice| choice.iter().any(|l| satisfies(l)))
+}
+
+/// Unlike [`SpdxException::grants_linking`], this excludes exceptions for the
+/// output of tools, such as Autoconf and Bison, which don't permit linking
+/// with the tools themselves.
+fn is_linking_exception(e: SpdxException) -> bool {
+    use SpdxException::*;
+
+    matches!(e, Classpath2 | Gcc2 | Gcc3_1 | OcamlLgplLinking | WxWindows3_1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn propagation() {
+        let parse = |s| Expr::parse(s).unwrap();
+        let gpl = parse("GPL-2.0-or-later");
+        let lgpl = parse("LGPL-3.0-only");
+        let mpl = parse("MPL-2.0");
+        let choice = parse("MIT OR GPL-3.0-only");
+        let classpath = parse("GPL-2.0-only WITH Classpath-exception-2.0");
+        let deps = vec![
+            ("gpl", &gpl, Linkage::Dynamic),
+            ("lgpl", &lgpl, Linkage::SourceIncluded),
+            ("mpl", &mpl, Linkage::Static),
+            ("choice", &choice, Linkage::Static),
+            ("classpath", &classpath, Linkage::Static),
+        ];
+
+        let analysis = analyze(deps.clone(), &parse("GPL-3.0-only"));
+        let packages: Vec<&str> = analysis.findings.iter().map(|f| f.package).collect();
+        assert_eq!(packages, ["gpl", "lgpl", "mpl", "classpath"]);
+        let propagated: Vec<&str> = analysis.propagated().map(|f| f.package).collect();
+        assert_eq!(propagated, ["gpl", "lgpl"]);
+        assert!(analysis.conflicts().next().is_none());
+
+        let conflicts: Vec<&str> = analyze(deps.clone(), &parse("GPL-2.0-only"))
+            .conflicts()
+            .map(|f| f.package)
+            .collect();
+        assert_eq!(conflicts, ["lgpl"]);
+
+        let conflicts = analyze(deps, &parse("Apache-2.0 OR GPL-3.0-only")).conflicts().count();
+        assert_eq!(conflicts, 2);
+    }
+
+    #[test]
+    fn tool_exceptions() {
+        let bison = Expr::parse("GPL-3.0-or-later WITH Bison-exception-2.2").unwrap();
+        let mit = Expr::from(SpdxLicense::Mit);
+        let analysis = analyze(vec![("bison", &bison, Linkage::Static)], &mit);
+        assert_eq!(analysis.propagated().count(), 1);
+        assert_eq!(analysis.conflicts().count(), 1);
+    }
+}
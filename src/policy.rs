@@ -0,0 +1,133 @@
+//! License policies.
+
+use crate::{
+    spdx::{BlueOakRating, LicenseKind, LicenseRef},
+    Expr,
+    License,
+    LicenseField,
+    SpdxLicense,
+};
+
+/// A set of rules for which licenses are acceptable.
+///
+/// A license is allowed if it is not in [`deny`](#structfield.deny), and it is
+/// either in [`allow`](#structfield.allow), its [kind] is in
+/// [`allow_kinds`](#structfield.allow_kinds), or its [Blue Oak rating] is at
+/// least [`min_blue_oak_rating`](#structfield.min_blue_oak_rating).
+///
+/// User-defined licenses have no kind or rating, so they are only allowed if
+/// they are in [`allow_refs`](#structfield.allow_refs).
+///
+/// Packages without a license (`NONE`) or whose license is unknown
+/// (`NOASSERTION`) are only allowed if [`allow_none`](#structfield.allow_none)
+/// or [`allow_no_assertion`](#structfield.allow_no_assertion) are set.
+///
+/// ```
+/// use linfo::{policy::Policy, spdx::{BlueOakRating, LicenseKind}, SpdxLicense};
+///
+/// let policy = Policy {
+///     allow: vec![SpdxLicense::Mpl2],
+///     allow_kinds: vec![LicenseKind::PublicDomain, LicenseKind::Permissive],
+///     deny: vec![SpdxLicense::Wtfpl],
+///     ..Policy::default()
+/// };
+///
+/// assert!(policy.allows_spdx(SpdxLicense::Mit));
+/// assert!(policy.allows_spdx(SpdxLicense::Mpl2));
+/// assert!(!policy.allows_spdx(SpdxLicense::Wtfpl));
+/// assert!(!policy.allows_spdx(SpdxLicense::Gpl3Only));
+///
+/// let policy = Policy {
+///     min_blue_oak_rating: Some(BlueOakRating::Silver),
+///     ..Policy::default()
+/// };
+///
+/// assert!(policy.allows_spdx(SpdxLicense::Apache2));
+/// assert!(!policy.allows_spdx(SpdxLicense::Bsd4Clause));
+/// ```
+///
+/// [kind]: ../spdx/enum.SpdxLicense.html#method.kind
+/// [Blue Oak rating]: ../spdx/enum.SpdxLicense.html#method.blue_oak_rating
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case"))]
+pub struct Policy {
+    /// Licenses that are allowed regardless of their kind.
+    pub allow: Vec<SpdxLicense>,
+    /// Kinds of licenses that are allowed.
+    pub allow_kinds: Vec<LicenseKind>,
+    /// Licenses that are never allowed.
+    pub deny: Vec<SpdxLicense>,
+    /// The lowest Blue Oak rating of licenses that are allowed regardless of
+    /// their kind.
+    pub min_blue_oak_rating: Option<BlueOakRating>,
+    /// User-defined licenses that have been reviewed and are allowed.
+    pub allow_refs: Vec<LicenseRef>,
+    /// Whether a license field of `NONE` is allowed.
+    pub allow_none: bool,
+    /// Whether a license field of `NOASSERTION` is allowed.
+    pub allow_no_assertion: bool,
+}
+
+impl Policy {
+    /// Returns whether `license` is allowed by this policy.
+    ///
+    /// User-defined licenses are only allowed if they are in
+    /// [`allow_refs`](#structfield.allow_refs).
+    #[inline]
+    pub fn allows(&self, license: &License) -> bool {
+        match license {
+            License::Spdx(l) => self.allows_spdx(*l),
+            // User-defined licenses must be reviewed individually
+            License::Ref(l) => self.allow_refs.contains(l),
+            // Exceptions only grant additional permissions
+            License::With(l, _) => self.allows(l),
+            License::_NonExhaustive(never) => never.consume(),
+        }
+    }
+
+    /// Returns whether `expr` is allowed by this policy, which is when every
+    /// license of at least one of its
+    /// [choices](../expr/enum.Expr.html#method.choices) is allowed.
+    pub fn allows_expr(&self, expr: &Expr) -> bool {
+        expr.choices().iter().any(|choice| choice.iter().all(|l| self.allows(l)))
+    }
+
+    /// Returns whether the license `field` is allowed by this policy.
+    ///
+    /// ```
+    /// use linfo::{policy::Policy, spdx::LicenseKind, LicenseField};
+    ///
+    /// let policy = Policy {
+    ///     allow_kinds: vec![LicenseKind::Permissive],
+    ///     ..Policy::default()
+    /// };
+    ///
+    /// let field = LicenseField::parse("MIT OR GPL-3.0-only").unwrap();
+    /// assert!(policy.allows_field(&field));
+    /// assert!(!policy.allows_field(&LicenseField::NoAssertion));
+    ///
+    /// let policy = Policy { allow_no_assertion: true, ..policy };
+    /// assert!(policy.allows_field(&LicenseField::NoAssertion));
+    /// assert!(!policy.allows_field(&LicenseField::None));
+    /// ```
+    pub fn allows_field(&self, field: &LicenseField) -> bool {
+        match field {
+            LicenseField::None => self.allow_none,
+            LicenseField::NoAssertion => self.allow_no_assertion,
+            LicenseField::Expr(expr) => self.allows_expr(expr),
+        }
+    }
+
+    /// Returns whether `license` is allowed by this policy.
+    pub fn allows_spdx(&self, license: SpdxLicense) -> bool {
+        if self.deny.contains(&license) {
+            return false;
+        }
+        self.allow.contains(&license) ||
+            self.allow_kinds.contains(&license.kind()) ||
+            self.min_blue_oak_rating.is_some_and(|min| {
+                license.blue_oak_rating().is_some_and(|rating| rating >= min)
+            })
+    }
+}
@@ -0,0 +1,75 @@
+use serde_json::{json, Map, Value};
+use super::*;
+
+/// Returns `licenses` as the JSON `licenses` array.
+pub(super) fn write(licenses: &Licenses) -> Value {
+    licenses.0.iter().map(|choice| match choice {
+        LicenseChoice::Expression(expr) => json!({ "expression": expr }),
+        LicenseChoice::License(entry) => {
+            let mut object = Map::new();
+            if let Some(id) = &entry.id {
+                object.insert("id".into(), json!(id));
+            }
+            if let Some(name) = &entry.name {
+                object.insert("name".into(), json!(name));
+            }
+            if let Some(text) = &entry.text {
+                object.insert("text".into(), json!({
+                    "contentType": "text/plain",
+                    "content": text,
+                }));
+            }
+            if let Some(url) = &entry.url {
+                object.insert("url".into(), json!(url));
+            }
+            json!({ "license": object })
+        },
+    }).collect()
+}
+
+/// Reads licenses from the `licenses` array or a component containing it.
+pub(super) fn read(value: &Value) -> Result<Licenses, Error> {
+    let value = match value.as_object() {
+        Some(component) => component.get("licenses").unwrap_or(&Value::Null),
+        None => value,
+    };
+    let array = value.as_array()
+        .ok_or_else(|| Error::Invalid("expected a `licenses` array".to_owned()))?;
+
+    let mut licenses = Vec::with_capacity(array.len());
+    for (i, choice) in array.iter().enumerate() {
+        let invalid = |message: &str| Error::Invalid(format!("licenses[{}]: {}", i, message));
+
+        if let Some(expr) = choice.get("expression") {
+            let expr = expr.as_str().ok_or_else(|| invalid("expected a string expression"))?;
+            licenses.push(LicenseChoice::Expression(expr.to_owned()));
+            continue;
+        }
+        let license = choice.get("license")
+            .and_then(Value::as_object)
+            .ok_or_else(|| invalid("expected a `license` or an `expression`"))?;
+
+        let text = match license.get("text") {
+            None => None,
+            Some(text) => {
+                let content = string(text, "content").unwrap_or_default();
+                match string(text, "encoding") {
+                    Some("base64") => Some(decode_base64(content)
+                        .ok_or_else(|| invalid("invalid base64 license text"))?),
+                    _ => Some(content.to_owned()),
+                }
+            },
+        };
+        licenses.push(LicenseChoice::License(LicenseEntry {
+            id: license.get("id").and_then(Value::as_str).map(ToOwned::to_owned),
+            name: license.get("name").and_then(Value::as_str).map(ToOwned::to_owned),
+            text,
+            url: license.get("url").and_then(Value::as_str).map(ToOwned::to_owned),
+        }));
+    }
+    Ok(Licenses(licenses))
+}
+
+fn string<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
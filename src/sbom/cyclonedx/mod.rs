@@ -0,0 +1,307 @@
+//! License blocks of [CycloneDX 1.5](https://cyclonedx.org/docs/1.5/json/)
+//! components.
+//!
+//! CycloneDX represents the licenses of a component as either a single SPDX
+//! expression or a list of licenses, each identified by an SPDX `id` or a
+//! free-form `name`.
+//!
+//! ```
+//! use linfo::{sbom::cyclonedx::Licenses, Expr};
+//!
+//! let json = serde_json::json!([
+//!     { "license": { "id": "MIT" } },
+//!     { "license": { "name": "Acme Proprietary", "url": "https://acme.com/license" } },
+//! ]);
+//! let licenses = Licenses::from_json(&json).unwrap();
+//! let expr = licenses.to_expr().unwrap();
+//! assert_eq!(expr.to_string(), "MIT AND LicenseRef-Acme-Proprietary");
+//!
+//! let licenses = Licenses::from_expr(&Expr::parse("MIT OR Apache-2.0").unwrap());
+//! assert_eq!(
+//!     licenses.to_xml(),
+//!     "<licenses><expression>MIT OR Apache-2.0</expression></licenses>",
+//! );
+//! ```
+
+use std::{error, fmt};
+use crate::{spdx::LicenseRef, Expr, License, SpdxLicense};
+
+mod json;
+mod xml;
+
+/// The licenses of a component.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Licenses(pub Vec<LicenseChoice>);
+
+/// An entry of a component's licenses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LicenseChoice {
+    /// A single license.
+    License(LicenseEntry),
+    /// An SPDX license expression.
+    Expression(String),
+}
+
+/// A single license of a component.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LicenseEntry {
+    /// The SPDX identifier of the license.
+    pub id: Option<String>,
+    /// The name of the license, if it has no SPDX identifier.
+    pub name: Option<String>,
+    /// The full text of the license.
+    pub text: Option<String>,
+    /// Where the license can be found.
+    pub url: Option<String>,
+}
+
+impl LicenseEntry {
+    /// Converts this entry into a [`License`](../../enum.License.html).
+    ///
+    /// An entry with neither a known identifier nor name is matched by its
+    /// [URL](../../spdx/enum.SpdxLicense.html#method.from_url). Otherwise,
+    /// identifiers that are unknown to this library and names that are not of
+    /// a known license become user-defined licenses.
+    pub fn to_license(&self) -> Result<License, Error> {
+        if let Some(id) = &self.id {
+            return Ok(License::parse(id.as_str())
+                .unwrap_or_else(|_| License::Ref(LicenseRef::new(id))));
+        }
+        let name = self.name.as_deref().map(str::trim).filter(|name| !name.is_empty());
+        if let Some(name) = name {
+            if let Ok(license) = License::parse(name) {
+                return Ok(license);
+            }
+            if let Some(l) = SpdxLicense::all().find(|l| l.name().eq_ignore_ascii_case(name)) {
+                return Ok(l.into());
+            }
+        }
+
+        match (self.url.as_deref().and_then(SpdxLicense::from_url), name) {
+            (Some(l), _) => Ok(l.into()),
+            (None, Some(name)) => Ok(License::Ref(LicenseRef::new(name))),
+            (None, None) => Err(Error::Invalid(
+                "license has neither an id, a name nor a known url".to_owned(),
+            )),
+        }
+    }
+}
+
+impl From<&License> for LicenseEntry {
+    fn from(license: &License) -> Self {
+        match license {
+            License::Spdx(l) => LicenseEntry {
+                id: Some(l.id().to_owned()),
+                ..Default::default()
+            },
+            License::Ref(l) => LicenseEntry {
+                name: Some(l.id().to_owned()),
+                ..Default::default()
+            },
+            // CycloneDX only supports exceptions within expressions
+            License::With(..) => LicenseEntry {
+                name: Some(license.to_string()),
+                ..Default::default()
+            },
+            License::_NonExhaustive(never) => never.consume(),
+        }
+    }
+}
+
+impl Licenses {
+    /// Creates a license block for `expr`, using a single license entry if
+    /// possible and an expression otherwise.
+    pub fn from_expr(expr: &Expr) -> Self {
+        let choice = match expr {
+            Expr::Single(l @ License::Spdx(_)) | Expr::Single(l @ License::Ref(_)) => {
+                LicenseChoice::License(l.into())
+            },
+            expr => LicenseChoice::Expression(expr.to_string()),
+        };
+        Licenses(vec![choice])
+    }
+
+    /// Converts this block into an [`Expr`](../../expr/enum.Expr.html).
+    ///
+    /// Multiple entries are combined with `AND`, since each is assumed to
+    /// apply to some part of the component.
+    pub fn to_expr(&self) -> Result<Expr, Error> {
+        let mut exprs = Vec::with_capacity(self.0.len());
+        for choice in &self.0 {
+            exprs.push(match choice {
+                LicenseChoice::License(entry) => entry.to_license()?.into(),
+                LicenseChoice::Expression(expr) => {
+                    Expr::parse_strict(expr).map_err(|e| Error::Expression {
+                        expr: expr.clone(),
+                        reason: e.to_string(),
+                    })?
+                },
+            });
+        }
+
+        match exprs.len() {
+            0 => Err(Error::Invalid("no licenses".to_owned())),
+            1 => Ok(exprs.pop().unwrap()),
+            _ => {
+                let mut operands = Vec::new();
+                for expr in exprs {
+                    match expr {
+                        Expr::And(and) => operands.extend(and.into_vec()),
+                        expr => operands.push(expr),
+                    }
+                }
+                Ok(crate::expr::And::new(operands).into())
+            },
+        }
+    }
+
+    /// Reads a license block from JSON, which is either the `licenses` array
+    /// or a component containing it.
+    #[inline]
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, Error> {
+        json::read(value)
+    }
+
+    /// Returns this block as the JSON `licenses` array.
+    #[inline]
+    pub fn to_json(&self) -> serde_json::Value {
+        json::write(self)
+    }
+
+    /// Reads a license block from XML, which is either the `<licenses>`
+    /// element or any document containing it, such as a `<component>`.
+    #[inline]
+    pub fn from_xml(input: &str) -> Result<Self, Error> {
+        xml::read(input)
+    }
+
+    /// Returns this block as the XML `<licenses>` element.
+    #[inline]
+    pub fn to_xml(&self) -> String {
+        xml::write(self)
+    }
+}
+
+/// An error returned when a license block cannot be read or converted.
+#[derive(Debug)]
+pub enum Error {
+    /// The input is not valid XML.
+    Xml {
+        /// The byte offset of the error within the input.
+        offset: usize,
+        /// What is wrong.
+        message: String,
+    },
+    /// The license block is malformed.
+    Invalid(String),
+    /// An expression could not be parsed.
+    Expression {
+        /// The expression as written.
+        expr: String,
+        /// Why the expression could not be parsed.
+        reason: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Xml { offset, message } => {
+                write!(f, "invalid XML at byte {}: {}", offset, message)
+            },
+            Error::Invalid(message) => write!(f, "invalid license block: {}", message),
+            Error::Expression { expr, reason } => {
+                write!(f, "invalid expression '{}': {}", expr, reason)
+            },
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Decodes standard base64 as used by the `encoding` of license texts.
+fn decode_base64(input: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in input.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let exprs = [
+            "MIT",
+            "LicenseRef-Acme",
+            "MIT AND Apache-2.0",
+            "GPL-2.0-only WITH Classpath-exception-2.0",
+        ];
+        for expr in &exprs {
+            let expr = Expr::parse(*expr).unwrap();
+            let licenses = Licenses::from_expr(&expr);
+
+            let json = Licenses::from_json(&licenses.to_json()).unwrap();
+            assert_eq!(json, licenses);
+            let xml = Licenses::from_xml(&licenses.to_xml()).unwrap();
+            assert_eq!(xml, licenses);
+            assert_eq!(licenses.to_expr().unwrap(), expr);
+        }
+    }
+
+    #[test]
+    fn read_component_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <bom xmlns="http://cyclonedx.org/schema/bom/1.5">
+              <components>
+                <component type="library">
+                  <name>acme</name>
+                  <licenses>
+                    <license>
+                      <name>Apache License 2.0</name>
+                    </license>
+                    <license>
+                      <name>Acme &amp; Co. License</name>
+                      <text content-type="text/plain" encoding="base64">RG8gd2hhdGV2ZXIu</text>
+                    </license>
+                    <license>
+                      <url>https://www.eclipse.org/legal/epl-2.0/</url>
+                    </license>
+                  </licenses>
+                </component>
+              </components>
+            </bom>"#;
+        let licenses = Licenses::from_xml(xml).unwrap();
+        match &licenses.0[1] {
+            LicenseChoice::License(entry) => {
+                assert_eq!(entry.name.as_deref(), Some("Acme & Co. License"));
+                assert_eq!(entry.text.as_deref(), Some("Do whatever."));
+            },
+            choice => panic!("unexpected {:?}", choice),
+        }
+        assert_eq!(
+            licenses.to_expr().unwrap().to_string(),
+            "Apache-2.0 AND LicenseRef-Acme---Co.-License AND EPL-2.0",
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Just enough XML to read and write CycloneDX `<licenses>` elements.
+
+use std::fmt::Write;
+use super::*;
+
+/// Returns `licenses` as the XML `<licenses>` element.
+pub(super) fn write(licenses: &Licenses) -> String {
+    let mut out = String::from("<licenses>");
+    for choice in &licenses.0 {
+        match choice {
+            LicenseChoice::Expression(expr) => element(&mut out, "expression", expr),
+            LicenseChoice::License(entry) => {
+                out.push_str("<license>");
+                if let Some(id) = &entry.id {
+                    element(&mut out, "id", id);
+                }
+                if let Some(name) = &entry.name {
+                    element(&mut out, "name", name);
+                }
+                if let Some(text) = &entry.text {
+                    out.push_str(r#"<text content-type="text/plain">"#);
+                    escape(&mut out, text);
+                    out.push_str("</text>");
+                }
+                if let Some(url) = &entry.url {
+                    element(&mut out, "url", url);
+                }
+                out.push_str("</license>");
+            },
+        }
+    }
+    out.push_str("</licenses>");
+    out
+}
+
+fn element(out: &mut String, name: &str, content: &str) {
+    let _ = write!(out, "<{}>", name);
+    escape(out, content);
+    let _ = write!(out, "</{}>", name);
+}
+
+fn escape(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Reads the first `<licenses>` element in `input`.
+pub(super) fn read(input: &str) -> Result<Licenses, Error> {
+    let mut parser = Parser { input, pos: 0 };
+    let licenses = loop {
+        match parser.next()? {
+            Some(Token::Open { name, empty, .. }) if local(name) == "licenses" => {
+                if empty {
+                    return Ok(Licenses::default());
+                }
+                break parser.element_children(name)?;
+            },
+            Some(_) => continue,
+            None => return Err(Error::Invalid("no `licenses` element".to_owned())),
+        }
+    };
+
+    let mut choices = Vec::new();
+    for child in licenses {
+        match local(&child.name) {
+            "expression" => choices.push(LicenseChoice::Expression(child.text)),
+            "license" => {
+                let mut entry = LicenseEntry::default();
+                for field in child.children {
+                    match local(&field.name) {
+                        "id" => entry.id = Some(field.text),
+                        "name" => entry.name = Some(field.text),
+                        "url" => entry.url = Some(field.text),
+                        "text" if field.base64 => {
+                            entry.text = Some(decode_base64(&field.text).ok_or_else(|| {
+                                Error::Invalid("invalid base64 license text".to_owned())
+                            })?);
+                        },
+                        "text" => entry.text = Some(field.text),
+                        _ => {},
+                    }
+                }
+                choices.push(LicenseChoice::License(entry));
+            },
+            _ => {},
+        }
+    }
+    Ok(Licenses(choices))
+}
+
+/// Strips the namespace prefix from `name`.
+fn local(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// An element along with its text and child elements.
+struct Node {
+    name: String,
+    base64: bool,
+    text: String,
+    children: Vec<Node>,
+}
+
+enum Token<'a> {
+    Open { name: &'a str, base64: bool, empty: bool },
+    Close(&'a str),
+    Text(String),
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> Error {
+        Error::Xml { offset: self.pos, message: message.to_owned() }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    /// Skips past the next occurrence of `end`.
+    fn skip_past(&mut self, end: &str) -> Result<&'a str, Error> {
+        let rest = self.rest();
+        let i = rest.find(end).ok_or_else(|| self.error(&format!("missing `{}`", end)))?;
+        self.pos += i + end.len();
+        Ok(&rest[..i])
+    }
+
+    fn next(&mut self) -> Result<Option<Token<'a>>, Error> {
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Ok(None);
+            }
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let text = unescape(&rest[..end]).ok_or_else(|| self.error("invalid entity"))?;
+                self.pos += end;
+                return Ok(Some(Token::Text(text)));
+            }
+            if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                let text = self.skip_past("]]>")?;
+                return Ok(Some(Token::Text(text.to_owned())));
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else if let Some(close) = rest.strip_prefix("</") {
+                self.pos += 2;
+                self.skip_past(">")?;
+                let name = close.split('>').next().unwrap_or_default().trim();
+                return Ok(Some(Token::Close(name)));
+            } else {
+                self.pos += 1;
+                let tag = self.skip_past(">")?;
+                let (tag, empty) = match tag.strip_suffix('/') {
+                    Some(tag) => (tag, true),
+                    None => (tag, false),
+                };
+                let name = tag.split(|c: char| c.is_ascii_whitespace()).next().unwrap_or_default();
+                if name.is_empty() {
+                    return Err(self.error("missing element name"));
+                }
+                let attributes = &tag[name.len()..];
+                let base64 = attributes.contains("encoding=\"base64\"") ||
+                    attributes.contains("encoding='base64'");
+                return Ok(Some(Token::Open { name, base64, empty }));
+            }
+        }
+    }
+
+    /// Reads the children of the element `name` whose start tag was just read.
+    fn element_children(&mut self, name: &str) -> Result<Vec<Node>, Error> {
+        Ok(self.element(name, false)?.children)
+    }
+
+    fn element(&mut self, name: &str, base64: bool) -> Result<Node, Error> {
+        let mut node = Node {
+            name: name.to_owned(),
+            base64,
+            text: String::new(),
+            children: Vec::new(),
+        };
+        loop {
+            match self.next()? {
+                Some(Token::Open { name, base64, empty }) => {
+                    if empty {
+                        node.children.push(Node {
+                            name: name.to_owned(),
+                            base64,
+                            text: String::new(),
+                            children: Vec::new(),
+                        });
+                    } else {
+                        node.children.push(self.element(name, base64)?);
+                    }
+                },
+                Some(Token::Close(close)) if close == name => break,
+                Some(Token::Close(close)) => {
+                    return Err(self.error(&format!("expected `</{}>`, found `</{}>`", name, close)));
+                },
+                Some(Token::Text(text)) => node.text.push_str(&text),
+                None => return Err(self.error(&format!("unclosed `<{}>`", name))),
+            }
+        }
+        node.text = node.text.trim().to_owned();
+        Ok(node)
+    }
+}
+
+/// Replaces entity and character references in `s`.
+fn unescape(s: &str) -> Option<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let end = rest.find(';')?;
+        let c = match &rest[..end] {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            entity => {
+                let code = match entity.strip_prefix("#x") {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => entity.strip_prefix('#')?.parse().ok()?,
+                };
+                core::char::from_u32(code)?
+            },
+        };
+        out.push(c);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Some(out)
+}
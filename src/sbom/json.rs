@@ -0,0 +1,263 @@
+use serde_json::{json, Map, Value};
+use super::*;
+
+/// Returns `document` as a JSON value.
+pub(super) fn write(document: &Document) -> Value {
+    let info = &document.creation_info;
+    let mut creation_info = Map::new();
+    creation_info.insert("created".into(), json!(info.created));
+    creation_info.insert("creators".into(), json!(info.creators));
+    insert_some(&mut creation_info, "licenseListVersion", &info.license_list_version);
+    insert_some(&mut creation_info, "comment", &info.comment);
+
+    let mut root = Map::new();
+    root.insert("spdxVersion".into(), json!(SPDX_VERSION));
+    root.insert("dataLicense".into(), json!(DATA_LICENSE));
+    root.insert("SPDXID".into(), json!(Document::SPDX_ID));
+    root.insert("name".into(), json!(document.name));
+    root.insert("documentNamespace".into(), json!(document.namespace));
+    insert_some(&mut root, "comment", &document.comment);
+    root.insert("creationInfo".into(), Value::Object(creation_info));
+
+    let packages = document.packages.iter().map(|package| {
+        let mut object = Map::new();
+        object.insert("SPDXID".into(), json!(package.id));
+        object.insert("name".into(), json!(package.name));
+        insert_some(&mut object, "versionInfo", &package.version);
+        insert_some(&mut object, "supplier", &package.supplier);
+        object.insert("downloadLocation".into(), or_no_assertion(&package.download_location));
+        object.insert("filesAnalyzed".into(), json!(package.files_analyzed));
+        object.insert("licenseConcluded".into(), json!(package.license_concluded.to_string()));
+        object.insert("licenseDeclared".into(), json!(package.license_declared.to_string()));
+        if !package.license_info_from_files.is_empty() {
+            object.insert("licenseInfoFromFiles".into(), licenses(&package.license_info_from_files));
+        }
+        object.insert("copyrightText".into(), or_no_assertion(&package.copyright_text));
+        Value::Object(object)
+    }).collect();
+    root.insert("packages".into(), Value::Array(packages));
+
+    if !document.files.is_empty() {
+        let files = document.files.iter().map(|file| {
+            let checksums: Vec<Value> = file.checksums.iter().map(|checksum| json!({
+                "algorithm": checksum.algorithm.as_str(),
+                "checksumValue": checksum.value,
+            })).collect();
+
+            let mut object = Map::new();
+            object.insert("SPDXID".into(), json!(file.id));
+            object.insert("fileName".into(), json!(file.name));
+            object.insert("checksums".into(), Value::Array(checksums));
+            object.insert("licenseConcluded".into(), json!(file.license_concluded.to_string()));
+            if !file.license_info_in_file.is_empty() {
+                object.insert("licenseInfoInFiles".into(), licenses(&file.license_info_in_file));
+            }
+            object.insert("copyrightText".into(), or_no_assertion(&file.copyright_text));
+            Value::Object(object)
+        }).collect();
+        root.insert("files".into(), Value::Array(files));
+    }
+
+    let relationships = document.relationships.iter().map(|relationship| json!({
+        "spdxElementId": relationship.element,
+        "relationshipType": relationship.kind.as_str(),
+        "relatedSpdxElement": relationship.related,
+    })).collect();
+    root.insert("relationships".into(), Value::Array(relationships));
+
+    if !document.extracted_licenses.is_empty() {
+        let extracted = document.extracted_licenses.iter().map(|license| {
+            let mut object = Map::new();
+            object.insert("licenseId".into(), json!(license.id.id()));
+            object.insert("extractedText".into(), json!(license.text));
+            insert_some(&mut object, "name", &license.name);
+            if !license.see_also.is_empty() {
+                object.insert("seeAlsos".into(), json!(license.see_also));
+            }
+            insert_some(&mut object, "comment", &license.comment);
+            Value::Object(object)
+        }).collect();
+        root.insert("hasExtractedLicensingInfos".into(), Value::Array(extracted));
+    }
+
+    Value::Object(root)
+}
+
+fn insert_some(object: &mut Map<String, Value>, key: &str, value: &Option<String>) {
+    if let Some(value) = value {
+        object.insert(key.into(), json!(value));
+    }
+}
+
+fn or_no_assertion(value: &Option<String>) -> Value {
+    json!(value.as_deref().unwrap_or("NOASSERTION"))
+}
+
+fn licenses(licenses: &[License]) -> Value {
+    licenses.iter().map(|l| json!(l.to_string())).collect()
+}
+
+/// Reads a document from a JSON value.
+pub(super) fn read(value: &Value) -> Result<Loaded, ReadError> {
+    let invalid = |path: &str, message: &str| ReadError::Invalid {
+        location: Location::Path(path.to_owned()),
+        message: message.to_owned(),
+    };
+    let root = value.as_object()
+        .ok_or_else(|| invalid("document", "expected an object"))?;
+    let version = string(root, "spdxVersion")
+        .ok_or_else(|| invalid("spdxVersion", "missing version"))?;
+    check_version(version, Location::Path("spdxVersion".to_owned()))?;
+
+    let info = root.get("creationInfo").and_then(Value::as_object);
+    let info_string = |key| info.and_then(|info| string(info, key));
+    let mut reader = Reader {
+        errors: Vec::new(),
+        warnings: Vec::new(),
+        list_version: info_string("licenseListVersion").and_then(ListVersion::parse),
+    };
+    let mut document = Document::new(
+        string(root, "name").unwrap_or_default(),
+        string(root, "documentNamespace").unwrap_or_default(),
+        CreationInfo {
+            created: info_string("created").unwrap_or_default().to_owned(),
+            creators: info.map(|info| strings(info, "creators")).unwrap_or_default(),
+            license_list_version: info_string("licenseListVersion").map(ToOwned::to_owned),
+            comment: info_string("comment").map(ToOwned::to_owned),
+        },
+    );
+    document.comment = string(root, "comment").map(ToOwned::to_owned);
+
+    for (i, object) in objects(root, "packages") {
+        let path = |key: &str| Location::Path(format!("packages[{}].{}", i, key));
+        let mut package = Package::new(string(object, "name").unwrap_or_default(), None);
+        package.id = string(object, "SPDXID").unwrap_or_default().to_owned();
+        package.version = string(object, "versionInfo").map(ToOwned::to_owned);
+        package.supplier = string(object, "supplier").and_then(text_value);
+        package.download_location = string(object, "downloadLocation").and_then(text_value);
+        package.files_analyzed = object.get("filesAnalyzed")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+        if let Some(value) = string(object, "licenseConcluded") {
+            package.license_concluded = reader.license_value(value, || path("licenseConcluded"));
+        }
+        if let Some(value) = string(object, "licenseDeclared") {
+            package.license_declared = reader.license_value(value, || path("licenseDeclared"));
+        }
+        for (j, value) in strings(object, "licenseInfoFromFiles").iter().enumerate() {
+            let location = || path(&format!("licenseInfoFromFiles[{}]", j));
+            package.license_info_from_files.extend(reader.license(value, location));
+        }
+        package.copyright_text = string(object, "copyrightText").and_then(text_value);
+
+        for file in strings(object, "hasFiles") {
+            document.relationships.push(Relationship {
+                element: package.id.clone(),
+                kind: RelationshipType::Contains,
+                related: file,
+            });
+        }
+        document.packages.push(package);
+    }
+
+    for (i, object) in objects(root, "files") {
+        let path = |key: &str| Location::Path(format!("files[{}].{}", i, key));
+        let mut file = File::new(string(object, "fileName").unwrap_or_default());
+        file.id = string(object, "SPDXID").unwrap_or_default().to_owned();
+        for (j, checksum) in objects(object, "checksums") {
+            let algorithm = string(checksum, "algorithm").unwrap_or_default();
+            let algorithm = ChecksumAlgorithm::parse(algorithm).ok_or_else(|| {
+                invalid(
+                    &format!("files[{}].checksums[{}].algorithm", i, j),
+                    &format!("unknown checksum algorithm '{}'", algorithm),
+                )
+            })?;
+            file.checksums.push(Checksum {
+                algorithm,
+                value: string(checksum, "checksumValue").unwrap_or_default().to_owned(),
+            });
+        }
+        if let Some(value) = string(object, "licenseConcluded") {
+            file.license_concluded = reader.license_value(value, || path("licenseConcluded"));
+        }
+        for (j, value) in strings(object, "licenseInfoInFiles").iter().enumerate() {
+            let location = || path(&format!("licenseInfoInFiles[{}]", j));
+            file.license_info_in_file.extend(reader.license(value, location));
+        }
+        file.copyright_text = string(object, "copyrightText").and_then(text_value);
+        document.files.push(file);
+    }
+
+    for (i, object) in objects(root, "relationships") {
+        let kind = string(object, "relationshipType").unwrap_or_default();
+        let kind = RelationshipType::parse(kind).ok_or_else(|| {
+            invalid(
+                &format!("relationships[{}].relationshipType", i),
+                &format!("unknown relationship type '{}'", kind),
+            )
+        })?;
+        document.relationships.push(Relationship {
+            element: string(object, "spdxElementId").unwrap_or_default().to_owned(),
+            kind,
+            related: string(object, "relatedSpdxElement").unwrap_or_default().to_owned(),
+        });
+    }
+
+    // Deprecated in SPDX 2.3 in favor of relationships
+    for related in strings(root, "documentDescribes") {
+        let exists = document.relationships.iter().any(|r| {
+            r.element == Document::SPDX_ID &&
+            r.kind == RelationshipType::Describes &&
+            r.related == related
+        });
+        if !exists {
+            document.relationships.push(Relationship {
+                element: Document::SPDX_ID.to_owned(),
+                kind: RelationshipType::Describes,
+                related,
+            });
+        }
+    }
+
+    for (i, object) in objects(root, "hasExtractedLicensingInfos") {
+        let id = string(object, "licenseId").unwrap_or_default();
+        let location = || Location::Path(format!("hasExtractedLicensingInfos[{}].licenseId", i));
+        let id = match reader.license(id, location) {
+            Some(License::Ref(id)) => id,
+            _ => continue,
+        };
+        document.extracted_licenses.push(ExtractedLicense {
+            id,
+            text: string(object, "extractedText").unwrap_or_default().to_owned(),
+            name: string(object, "name").and_then(text_value),
+            see_also: strings(object, "seeAlsos"),
+            comment: string(object, "comment").map(ToOwned::to_owned),
+        });
+    }
+
+    Ok(Loaded { document, errors: reader.errors, warnings: reader.warnings })
+}
+
+fn string<'a>(object: &'a Map<String, Value>, key: &str) -> Option<&'a str> {
+    object.get(key).and_then(Value::as_str)
+}
+
+fn strings(object: &Map<String, Value>, key: &str) -> Vec<String> {
+    let array = object.get(key).and_then(Value::as_array);
+    array.into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+fn objects<'a>(
+    object: &'a Map<String, Value>,
+    key: &str,
+) -> impl Iterator<Item = (usize, &'a Map<String, Value>)> {
+    let array = object.get(key).and_then(Value::as_array);
+    array.into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(i, value)| Some((i, value.as_object()?)))
+}
@@ -0,0 +1,1060 @@
+//! [SPDX 2.3](https://spdx.github.io/spdx-spec/v2.3/) documents.
+//!
+//! A [`Document`](struct.Document.html) can be read and written in both the
+//! tag-value and JSON formats.
+//!
+//! ```
+//! use linfo::{bundle, sbom::{CreationInfo, Document}, Expr};
+//!
+//! let packages = [bundle::Package {
+//!     name: "serde".to_owned(),
+//!     version: "1.0.0".to_owned(),
+//!     expr: Expr::parse("MIT OR Apache-2.0").unwrap(),
+//!     license_files: vec![],
+//!     notice: None,
+//! }];
+//!
+//! let document = Document::from_packages(
+//!     "my-app",
+//!     "https://example.com/spdx/my-app-1.0.0",
+//!     CreationInfo::new("2020-01-01T00:00:00Z"),
+//!     &packages,
+//! );
+//!
+//! let tag_value = document.to_tag_value();
+//! assert!(tag_value.contains("PackageLicenseDeclared: MIT OR Apache-2.0\n"));
+//!
+//! let json = document.to_json();
+//! assert!(json.contains(r#""licenseDeclared": "MIT OR Apache-2.0""#));
+//! ```
+
+use std::{collections::BTreeSet, fmt};
+use crate::{
+    bundle,
+    spdx::{LicenseRef, ListVersion},
+    Expr,
+    License,
+    LicenseField,
+    ParseError,
+    SpdxLicense,
+};
+
+mod json;
+pub mod cyclonedx;
+pub mod spdx3;
+mod tag_value;
+
+/// The version of the SPDX specification that documents are written in.
+pub const SPDX_VERSION: &str = "SPDX-2.3";
+
+/// The license of SPDX documents themselves.
+pub const DATA_LICENSE: &str = "CC0-1.0";
+
+/// An SPDX document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Document {
+    /// The name of this document.
+    pub name: String,
+    /// The unique URI of this document.
+    pub namespace: String,
+    /// Who created this document and when.
+    pub creation_info: CreationInfo,
+    /// A comment about this document.
+    pub comment: Option<String>,
+    /// The packages described by this document.
+    pub packages: Vec<Package>,
+    /// The files described by this document.
+    pub files: Vec<File>,
+    /// The relationships between this document, its packages and its files.
+    pub relationships: Vec<Relationship>,
+    /// The texts of user-defined licenses referenced within this document.
+    pub extracted_licenses: Vec<ExtractedLicense>,
+}
+
+impl Document {
+    /// The identifier of every document.
+    pub const SPDX_ID: &'static str = "SPDXRef-DOCUMENT";
+
+    /// Creates an empty document.
+    pub fn new<N, S>(name: N, namespace: S, creation_info: CreationInfo) -> Self
+        where N: Into<String>, S: Into<String>
+    {
+        Self {
+            name: name.into(),
+            namespace: namespace.into(),
+            creation_info,
+            comment: None,
+            packages: Vec::new(),
+            files: Vec::new(),
+            relationships: Vec::new(),
+            extracted_licenses: Vec::new(),
+        }
+    }
+
+    /// Creates a document that describes `packages`, with each package's
+    /// expression as its declared license.
+    ///
+    /// License files of user-defined licenses become extracted licenses.
+    pub fn from_packages<N, S>(
+        name: N,
+        namespace: S,
+        creation_info: CreationInfo,
+        packages: &[bundle::Package],
+    ) -> Self
+        where N: Into<String>, S: Into<String>
+    {
+        let mut document = Self::new(name, namespace, creation_info);
+
+        for package in packages {
+            let mut spdx_package = Package::new(&package.name, Some(&package.version));
+            spdx_package.license_declared = LicenseField::Expr(package.expr.clone());
+            document.describe(spdx_package);
+
+            for file in &package.license_files {
+                if let Some(License::Ref(id)) = &file.license {
+                    let exists = document.extracted_licenses.iter().any(|e| &e.id == id);
+                    if !exists {
+                        document.extracted_licenses.push(ExtractedLicense {
+                            id: id.clone(),
+                            text: file.text.clone(),
+                            name: None,
+                            see_also: Vec::new(),
+                            comment: None,
+                        });
+                    }
+                }
+            }
+        }
+        document
+    }
+
+    /// Adds `package` along with a relationship that this document describes
+    /// it, and returns its identifier.
+    ///
+    /// The package's identifier is changed if it is already used.
+    pub fn describe(&mut self, mut package: Package) -> &str {
+        package.id = self.unique_id(package.id);
+        self.relationships.push(Relationship {
+            element: Self::SPDX_ID.to_owned(),
+            kind: RelationshipType::Describes,
+            related: package.id.clone(),
+        });
+        self.packages.push(package);
+        &self.packages[self.packages.len() - 1].id
+    }
+
+    /// Returns `id`, suffixed with a number if it is used by any package or
+    /// file within this document.
+    fn unique_id(&self, id: String) -> String {
+        let is_used = |id: &str| {
+            id == Self::SPDX_ID ||
+            self.packages.iter().any(|p| p.id == id) ||
+            self.files.iter().any(|f| f.id == id)
+        };
+        if !is_used(&id) {
+            return id;
+        }
+        (2..).map(|n| format!("{}-{}", id, n))
+            .find(|id| !is_used(id))
+            .unwrap()
+    }
+
+    /// Returns the package or file identified by `id`.
+    pub fn element(&self, id: &str) -> Option<Element<'_>> {
+        self.packages.iter()
+            .find(|p| p.id == id)
+            .map(Element::Package)
+            .or_else(|| self.files.iter().find(|f| f.id == id).map(Element::File))
+    }
+
+    /// Returns every user-defined license referenced by a package or file.
+    pub fn license_refs(&self) -> BTreeSet<&LicenseRef> {
+        let values = self.packages.iter()
+            .flat_map(|p| vec![&p.license_concluded, &p.license_declared])
+            .chain(self.files.iter().map(|f| &f.license_concluded))
+            .filter_map(LicenseField::expr)
+            .flat_map(Expr::licenses);
+        let infos = self.packages.iter()
+            .flat_map(|p| &p.license_info_from_files)
+            .chain(self.files.iter().flat_map(|f| &f.license_info_in_file));
+
+        values.chain(infos)
+            .filter_map(|l| match l.base() {
+                License::Ref(l) if l.document_ref().is_none() => Some(l),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the user-defined licenses that are referenced within this
+    /// document but are missing from
+    /// [`extracted_licenses`](#structfield.extracted_licenses).
+    pub fn missing_extracted_licenses(&self) -> Vec<&LicenseRef> {
+        self.license_refs()
+            .into_iter()
+            .filter(|id| !self.extracted_licenses.iter().any(|e| &e.id == *id))
+            .collect()
+    }
+
+    /// Returns this document in the tag-value format.
+    #[inline]
+    pub fn to_tag_value(&self) -> String {
+        tag_value::write(self)
+    }
+
+    /// Returns this document in the JSON format.
+    #[inline]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json_value())
+            .expect("JSON values are always serializable")
+    }
+
+    /// Returns this document as a JSON value.
+    #[inline]
+    pub fn to_json_value(&self) -> serde_json::Value {
+        json::write(self)
+    }
+
+    /// Reads an SPDX 2.2 or 2.3 document in the tag-value format.
+    ///
+    /// Files listed after a package are contained by that package.
+    ///
+    /// ```
+    /// use linfo::{sbom::Document, LicenseField};
+    ///
+    /// let loaded = Document::from_tag_value("\
+    /// SPDXVersion: SPDX-2.2
+    /// DocumentName: example
+    ///
+    /// PackageName: foo
+    /// SPDXID: SPDXRef-foo
+    /// PackageLicenseConcluded: NOASSERTION
+    /// PackageLicenseDeclared: Not-A-License
+    /// ").unwrap();
+    ///
+    /// let package = &loaded.document.packages[0];
+    /// assert_eq!(package.license_concluded, LicenseField::NoAssertion);
+    ///
+    /// let error = &loaded.errors[0];
+    /// assert_eq!(error.location.to_string(), "line 7");
+    /// ```
+    #[inline]
+    pub fn from_tag_value(input: &str) -> Result<Loaded, ReadError> {
+        tag_value::read(input)
+    }
+
+    /// Reads an SPDX 2.2 or 2.3 document in the JSON format.
+    #[inline]
+    pub fn from_json(input: &str) -> Result<Loaded, ReadError> {
+        let value: serde_json::Value = serde_json::from_str(input).map_err(ReadError::Json)?;
+        json::read(&value)
+    }
+
+    /// Returns the definition of the user-defined license `id`.
+    pub fn extracted_license(&self, id: &LicenseRef) -> Option<&ExtractedLicense> {
+        self.extracted_licenses.iter().find(|e| &e.id == id)
+    }
+}
+
+/// A document that was read, along with the license expressions within it
+/// that could not be parsed.
+#[derive(Clone, Debug)]
+pub struct Loaded {
+    /// The document, where each expression that could not be parsed is
+    /// replaced with [`LicenseField::NoAssertion`](../enum.LicenseField.html#variant.NoAssertion)
+    /// or omitted from lists.
+    pub document: Document,
+    /// The expressions that could not be parsed, including those with SPDX
+    /// licenses that are not valid in the document's license list version.
+    pub errors: Vec<ExprError>,
+    /// The expressions that were parsed, but have SPDX licenses that are
+    /// deprecated in the document's license list version.
+    pub warnings: Vec<Deprecated>,
+}
+
+/// Where a value is within a document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Location {
+    /// A line number of a tag-value document, starting at 1.
+    Line(usize),
+    /// A path within a JSON document, such as `packages[0].licenseDeclared`.
+    Path(String),
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Location::Line(line) => write!(f, "line {}", line),
+            Location::Path(path) => f.write_str(path),
+        }
+    }
+}
+
+/// A license expression within a document that could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExprError {
+    /// Where the expression is.
+    pub location: Location,
+    /// The expression as written.
+    pub expr: String,
+    /// Why the expression could not be parsed.
+    pub reason: String,
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: invalid expression '{}': {}", self.location, self.expr, self.reason)
+    }
+}
+
+/// A license expression within a document that has SPDX licenses deprecated
+/// in the document's license list version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deprecated {
+    /// Where the expression is.
+    pub location: Location,
+    /// The expression as written.
+    pub expr: String,
+    /// The deprecated licenses in the expression.
+    pub licenses: Vec<SpdxLicense>,
+    /// The `LicenseListVersion` of the document.
+    pub version: ListVersion,
+}
+
+impl fmt::Display for Deprecated {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: expression '{}' has licenses deprecated in license list version {}:",
+            self.location,
+            self.expr,
+            self.version,
+        )?;
+        for (i, license) in self.licenses.iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { ", " })?;
+            f.write_str(license.id())?;
+        }
+        Ok(())
+    }
+}
+
+/// An error returned when a document could not be read.
+#[derive(Debug)]
+pub enum ReadError {
+    /// The input is not valid JSON.
+    Json(serde_json::Error),
+    /// The input is not a valid SPDX 2.2 or 2.3 document.
+    Invalid {
+        /// Where the error is.
+        location: Location,
+        /// What is wrong.
+        message: String,
+    },
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Json(error) => write!(f, "invalid JSON: {}", error),
+            ReadError::Invalid { location, message } => {
+                write!(f, "{}: {}", location, message)
+            },
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Json(error) => Some(error),
+            ReadError::Invalid { .. } => None,
+        }
+    }
+}
+
+/// Collects the values of a document as it is read.
+struct Reader {
+    errors: Vec<ExprError>,
+    warnings: Vec<Deprecated>,
+    /// The `LicenseListVersion` of the document, which SPDX licenses must be
+    /// valid and should not be deprecated in.
+    list_version: Option<ListVersion>,
+}
+
+impl Reader {
+    fn license_value<F>(&mut self, value: &str, location: F) -> LicenseField
+        where F: FnOnce() -> Location
+    {
+        let field = match self.list_version {
+            Some(version) => LicenseField::parse_in(value, version),
+            None => LicenseField::parse(value),
+        };
+        match field {
+            Ok(field) => {
+                if let LicenseField::Expr(expr) = &field {
+                    self.check_deprecated(value.trim(), expr.licenses(), location);
+                }
+                field
+            },
+            Err(error) => {
+                self.error(value.trim(), self.reason(error), location());
+                LicenseField::NoAssertion
+            },
+        }
+    }
+
+    /// Parses a single license in a list, where `NONE` and `NOASSERTION` are
+    /// treated as empty.
+    fn license<F>(&mut self, value: &str, location: F) -> Option<License>
+        where F: FnOnce() -> Location
+    {
+        let license = match (value.trim(), self.list_version) {
+            ("NONE", _) | ("NOASSERTION", _) => return None,
+            (value, Some(version)) => License::parse_in(value, version),
+            (value, None) => License::parse(value),
+        };
+        match license {
+            Ok(license) => {
+                self.check_deprecated(value.trim(), Some(&license), location);
+                Some(license)
+            },
+            Err(error) => {
+                self.error(value.trim(), self.reason(error), location());
+                None
+            },
+        }
+    }
+
+    /// Warns if any of `licenses` is deprecated in the document's license list
+    /// version.
+    fn check_deprecated<'l, I, F>(&mut self, expr: &str, licenses: I, location: F)
+        where I: IntoIterator<Item = &'l License>, F: FnOnce() -> Location
+    {
+        let version = match self.list_version {
+            Some(version) => version,
+            None => return,
+        };
+        let deprecated: Vec<SpdxLicense> = licenses.into_iter()
+            .filter_map(|license| match license.base() {
+                License::Spdx(l) if l.is_deprecated_in(version) => Some(*l),
+                _ => None,
+            })
+            .collect();
+        if !deprecated.is_empty() {
+            self.warnings.push(Deprecated {
+                location: location(),
+                expr: expr.to_owned(),
+                licenses: deprecated,
+                version,
+            });
+        }
+    }
+
+    /// Describes `error`, including the license list version that an id is
+    /// unknown in.
+    fn reason(&self, error: ParseError) -> String {
+        match (error, self.list_version) {
+            (ParseError::UnknownLicenseId(id), Some(version)) => {
+                format!("'{}' is not a known license ID in license list version {}", id, version)
+            },
+            (error, _) => error.to_string(),
+        }
+    }
+
+    fn error(&mut self, expr: &str, reason: String, location: Location) {
+        self.errors.push(ExprError { location, expr: expr.to_owned(), reason });
+    }
+}
+
+/// Returns the value of a text field, where `NOASSERTION` is `None`.
+fn text_value(value: &str) -> Option<String> {
+    match value {
+        "NOASSERTION" => None,
+        value => Some(value.to_owned()),
+    }
+}
+
+/// Returns an error if `version` is not a supported SPDX version.
+fn check_version(version: &str, location: Location) -> Result<(), ReadError> {
+    match version {
+        "SPDX-2.2" | "SPDX-2.3" => Ok(()),
+        version => Err(ReadError::Invalid {
+            location,
+            message: format!("unsupported version '{}'", version),
+        }),
+    }
+}
+
+/// Who created a document and when.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreationInfo {
+    /// When the document was created, in the form `YYYY-MM-DDThh:mm:ssZ`.
+    pub created: String,
+    /// The people, organizations and tools that created the document, such
+    /// as `Tool: linfo-0.1.3`.
+    pub creators: Vec<String>,
+    /// The version of the SPDX license list used, such as `3.7`. See
+    /// [`ListVersion`](../spdx/struct.ListVersion.html).
+    pub license_list_version: Option<String>,
+    /// A comment about the creation of the document.
+    pub comment: Option<String>,
+}
+
+impl CreationInfo {
+    /// Creates an instance with this library as the only creator.
+    pub fn new<S: Into<String>>(created: S) -> Self {
+        Self {
+            created: created.into(),
+            creators: vec![concat!("Tool: linfo-", env!("CARGO_PKG_VERSION")).to_owned()],
+            license_list_version: None,
+            comment: None,
+        }
+    }
+}
+
+/// A package or file within a document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Element<'a> {
+    /// A package.
+    Package(&'a Package),
+    /// A file.
+    File(&'a File),
+}
+
+/// A package within a document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Package {
+    /// The identifier of this package within the document.
+    pub id: String,
+    /// The name of this package.
+    pub name: String,
+    /// The version of this package.
+    pub version: Option<String>,
+    /// The distributor of this package, such as `Organization: ExampleCo`.
+    pub supplier: Option<String>,
+    /// Where this package can be downloaded from, or `None` if unknown.
+    pub download_location: Option<String>,
+    /// Whether the files of this package were analyzed.
+    pub files_analyzed: bool,
+    /// The license of this package, as determined by the document's creator.
+    pub license_concluded: LicenseField,
+    /// The license of this package, as stated by its authors.
+    pub license_declared: LicenseField,
+    /// The licenses found within the files of this package.
+    pub license_info_from_files: Vec<License>,
+    /// The copyright notices of this package, or `None` if unknown.
+    pub copyright_text: Option<String>,
+}
+
+impl Package {
+    /// Creates a package with unknown license information.
+    pub fn new(name: &str, version: Option<&str>) -> Self {
+        let id = match version {
+            Some(version) => spdx_id(&format!("Package-{}-{}", name, version)),
+            None => spdx_id(&format!("Package-{}", name)),
+        };
+        Self {
+            id,
+            name: name.to_owned(),
+            version: version.map(ToOwned::to_owned),
+            supplier: None,
+            download_location: None,
+            files_analyzed: false,
+            license_concluded: LicenseField::NoAssertion,
+            license_declared: LicenseField::NoAssertion,
+            license_info_from_files: Vec::new(),
+            copyright_text: None,
+        }
+    }
+}
+
+/// A file within a document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct File {
+    /// The identifier of this file within the document.
+    pub id: String,
+    /// The path of this file, relative to its package, such as `./src/lib.rs`.
+    pub name: String,
+    /// Checksums of the contents of this file.
+    pub checksums: Vec<Checksum>,
+    /// The license of this file, as determined by the document's creator.
+    pub license_concluded: LicenseField,
+    /// The licenses found within this file.
+    pub license_info_in_file: Vec<License>,
+    /// The copyright notices of this file, or `None` if unknown.
+    pub copyright_text: Option<String>,
+}
+
+impl File {
+    /// Creates a file with unknown license information.
+    pub fn new(name: &str) -> Self {
+        Self {
+            id: spdx_id(&format!("File-{}", name.trim_start_matches("./"))),
+            name: name.to_owned(),
+            checksums: Vec::new(),
+            license_concluded: LicenseField::NoAssertion,
+            license_info_in_file: Vec::new(),
+            copyright_text: None,
+        }
+    }
+}
+
+/// A checksum of a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checksum {
+    /// The algorithm used.
+    pub algorithm: ChecksumAlgorithm,
+    /// The checksum in lowercase hexadecimal.
+    pub value: String,
+}
+
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                #[doc = $doc:expr]
+                $variant:ident = $str:expr,
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum $name {
+            $(
+                #[doc = $doc]
+                $variant,
+            )+
+        }
+
+        impl fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl $name {
+            /// Returns the string that represents this value in a document.
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $($name::$variant => $str,)+
+                }
+            }
+
+            /// Returns the value represented by `s` in a document.
+            pub fn parse(s: &str) -> Option<Self> {
+                match s {
+                    $($str => Some($name::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+string_enum! {
+    /// An algorithm used for a [`Checksum`](struct.Checksum.html).
+    pub enum ChecksumAlgorithm {
+        /// SHA-1.
+        Sha1 = "SHA1",
+        /// SHA-224.
+        Sha224 = "SHA224",
+        /// SHA-256.
+        Sha256 = "SHA256",
+        /// SHA-384.
+        Sha384 = "SHA384",
+        /// SHA-512.
+        Sha512 = "SHA512",
+        /// SHA3-256.
+        Sha3_256 = "SHA3-256",
+        /// SHA3-384.
+        Sha3_384 = "SHA3-384",
+        /// SHA3-512.
+        Sha3_512 = "SHA3-512",
+        /// BLAKE2b-256.
+        Blake2b256 = "BLAKE2b-256",
+        /// BLAKE2b-384.
+        Blake2b384 = "BLAKE2b-384",
+        /// BLAKE2b-512.
+        Blake2b512 = "BLAKE2b-512",
+        /// BLAKE3.
+        Blake3 = "BLAKE3",
+        /// MD2.
+        Md2 = "MD2",
+        /// MD4.
+        Md4 = "MD4",
+        /// MD5.
+        Md5 = "MD5",
+        /// MD6.
+        Md6 = "MD6",
+        /// Adler-32.
+        Adler32 = "ADLER32",
+    }
+}
+
+string_enum! {
+    /// The kind of a [`Relationship`](struct.Relationship.html).
+    pub enum RelationshipType {
+        /// The element describes the related element.
+        Describes = "DESCRIBES",
+        /// The element is described by the related element.
+        DescribedBy = "DESCRIBED_BY",
+        /// The element contains the related element.
+        Contains = "CONTAINS",
+        /// The element is contained by the related element.
+        ContainedBy = "CONTAINED_BY",
+        /// The element depends on the related element.
+        DependsOn = "DEPENDS_ON",
+        /// The element is a dependency of the related element.
+        DependencyOf = "DEPENDENCY_OF",
+        /// The element is a manifest file that lists dependencies of the related element.
+        DependencyManifestOf = "DEPENDENCY_MANIFEST_OF",
+        /// The element is a build dependency of the related element.
+        BuildDependencyOf = "BUILD_DEPENDENCY_OF",
+        /// The element is a development dependency of the related element.
+        DevDependencyOf = "DEV_DEPENDENCY_OF",
+        /// The element is an optional dependency of the related element.
+        OptionalDependencyOf = "OPTIONAL_DEPENDENCY_OF",
+        /// The element is a dependency provided by the environment of the related element.
+        ProvidedDependencyOf = "PROVIDED_DEPENDENCY_OF",
+        /// The element is a test dependency of the related element.
+        TestDependencyOf = "TEST_DEPENDENCY_OF",
+        /// The element is a runtime dependency of the related element.
+        RuntimeDependencyOf = "RUNTIME_DEPENDENCY_OF",
+        /// The element is an example of the related element.
+        ExampleOf = "EXAMPLE_OF",
+        /// The element generates the related element.
+        Generates = "GENERATES",
+        /// The element is generated from the related element.
+        GeneratedFrom = "GENERATED_FROM",
+        /// The element is an ancestor of the related element.
+        AncestorOf = "ANCESTOR_OF",
+        /// The element is a descendant of the related element.
+        DescendantOf = "DESCENDANT_OF",
+        /// The element is a variant of the related element.
+        VariantOf = "VARIANT_OF",
+        /// Distributing the element also requires distributing the related element.
+        DistributionArtifact = "DISTRIBUTION_ARTIFACT",
+        /// The element is a patch for the related element.
+        PatchFor = "PATCH_FOR",
+        /// The element is a patch that was applied to the related element.
+        PatchApplied = "PATCH_APPLIED",
+        /// The element is an exact copy of the related element.
+        CopyOf = "COPY_OF",
+        /// The element is a file added to the related element.
+        FileAdded = "FILE_ADDED",
+        /// The element is a file deleted from the related element.
+        FileDeleted = "FILE_DELETED",
+        /// The element is a file modified from the related element.
+        FileModified = "FILE_MODIFIED",
+        /// The element is expanded from the archive the related element.
+        ExpandedFromArchive = "EXPANDED_FROM_ARCHIVE",
+        /// The element dynamically links to the related element.
+        DynamicLink = "DYNAMIC_LINK",
+        /// The element statically links to the related element.
+        StaticLink = "STATIC_LINK",
+        /// The element is a data file used by the related element.
+        DataFileOf = "DATA_FILE_OF",
+        /// The element is a test case used in testing the related element.
+        TestCaseOf = "TEST_CASE_OF",
+        /// The element is used to build the related element.
+        BuildToolOf = "BUILD_TOOL_OF",
+        /// The element is used as a development tool for the related element.
+        DevToolOf = "DEV_TOOL_OF",
+        /// The element is used for testing the related element.
+        TestOf = "TEST_OF",
+        /// The element is used as a test tool for the related element.
+        TestToolOf = "TEST_TOOL_OF",
+        /// The element provides documentation of the related element.
+        DocumentationOf = "DOCUMENTATION_OF",
+        /// The element is an optional component of the related element.
+        OptionalComponentOf = "OPTIONAL_COMPONENT_OF",
+        /// The element is a metafile of the related element.
+        MetafileOf = "METAFILE_OF",
+        /// The element is used as a package as part of the related element.
+        PackageOf = "PACKAGE_OF",
+        /// The element is an amendment to the related element.
+        Amends = "AMENDS",
+        /// The element is a prerequisite for the related element.
+        PrerequisiteFor = "PREREQUISITE_FOR",
+        /// The element has as a prerequisite the related element.
+        HasPrerequisite = "HAS_PREREQUISITE",
+        /// The element describes requirements for the related element.
+        RequirementDescriptionFor = "REQUIREMENT_DESCRIPTION_FOR",
+        /// The element is a specification for the related element.
+        SpecificationFor = "SPECIFICATION_FOR",
+        /// Any other relationship.
+        Other = "OTHER",
+    }
+}
+
+/// A relationship between two elements of a document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Relationship {
+    /// The identifier of the element.
+    pub element: String,
+    /// How `element` relates to `related`.
+    pub kind: RelationshipType,
+    /// The identifier of the related element.
+    pub related: String,
+}
+
+/// The text of a user-defined license.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtractedLicense {
+    /// The identifier of the license.
+    pub id: LicenseRef,
+    /// The full text of the license.
+    pub text: String,
+    /// The name of the license.
+    pub name: Option<String>,
+    /// URLs where the license can be found.
+    pub see_also: Vec<String>,
+    /// A comment about the license.
+    pub comment: Option<String>,
+}
+
+/// Returns `SPDXRef-<s>` with invalid characters in `s` replaced by `-`.
+fn spdx_id(s: &str) -> String {
+    let s: String = s.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '-' })
+        .collect();
+    format!("SPDXRef-{}", s)
+}
+
+#[cfg(feature = "cargo")]
+impl Document {
+    /// Adds every package reachable in `graph`, with a relationship that this
+    /// document describes each root and a relationship for each dependency.
+    ///
+    /// The license of each node is its declared license. Graphs created from
+    /// [clarified](../cargo/struct.Metadata.html#method.clarify) metadata
+    /// declare the clarified licenses instead.
+    pub fn add_graph(&mut self, graph: &crate::cargo::Graph) {
+        let nodes = graph.nodes();
+        let index = |node: &crate::cargo::Node| {
+            nodes.iter().position(|n| std::ptr::eq(n, node)).unwrap()
+        };
+        let mut ids = vec![None; nodes.len()];
+
+        for node in graph.reachable() {
+            let mut package = Package::new(&node.name, Some(&node.version));
+            package.id = self.unique_id(package.id);
+            package.license_declared = node.license.clone();
+            ids[index(node)] = Some(package.id.clone());
+            self.packages.push(package);
+        }
+
+        for root in graph.roots() {
+            if let Some(id) = &ids[index(root)] {
+                self.relationships.push(Relationship {
+                    element: Self::SPDX_ID.to_owned(),
+                    kind: RelationshipType::Describes,
+                    related: id.clone(),
+                });
+            }
+        }
+        for (i, node) in nodes.iter().enumerate() {
+            let id = match &ids[i] {
+                Some(id) => id,
+                None => continue,
+            };
+            for &dep in &node.dependencies {
+                if let Some(related) = &ids[dep] {
+                    self.relationships.push(Relationship {
+                        element: id.clone(),
+                        kind: RelationshipType::DependsOn,
+                        related: related.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> Document {
+        let mut document = Document::new(
+            "test",
+            "https://example.com/test",
+            CreationInfo::new("2020-01-01T00:00:00Z"),
+        );
+        let mut package = Package::new("foo", Some("1.0.0"));
+        package.license_declared = Expr::parse("MIT OR LicenseRef-Foo").unwrap().into();
+        package.license_info_from_files = vec![License::parse("MIT").unwrap()];
+        package.copyright_text = Some("Copyright (c) Foo\nCopyright (c) Bar".to_owned());
+        document.describe(package);
+        document.describe(Package::new("foo", Some("1.0.0")));
+
+        let mut file = File::new("./LICENSE");
+        file.checksums.push(Checksum {
+            algorithm: ChecksumAlgorithm::Sha1,
+            value: "da39a3ee5e6b4b0d3255bfef95601890afd80709".to_owned(),
+        });
+        document.files.push(file);
+        document.extracted_licenses.push(ExtractedLicense {
+            id: LicenseRef::parse("LicenseRef-Foo").unwrap(),
+            text: "Do whatever.".to_owned(),
+            name: Some("Foo License".to_owned()),
+            see_also: vec!["https://example.com/foo".to_owned()],
+            comment: None,
+        });
+        document
+    }
+
+    #[test]
+    fn tag_value() {
+        let document = document();
+        assert!(document.missing_extracted_licenses().is_empty());
+
+        let tag_value = document.to_tag_value();
+        for line in &[
+            "SPDXVersion: SPDX-2.3",
+            "SPDXID: SPDXRef-DOCUMENT",
+            "Creator: Tool: linfo-",
+            "SPDXID: SPDXRef-Package-foo-1.0.0",
+            "SPDXID: SPDXRef-Package-foo-1.0.0-2",
+            "PackageDownloadLocation: NOASSERTION",
+            "PackageLicenseConcluded: NOASSERTION",
+            "PackageLicenseDeclared: MIT OR LicenseRef-Foo",
+            "PackageLicenseInfoFromFiles: MIT",
+            "PackageCopyrightText: <text>Copyright (c) Foo\nCopyright (c) Bar</text>",
+            "FileChecksum: SHA1: da39a3ee5e6b4b0d3255bfef95601890afd80709",
+            "Relationship: SPDXRef-DOCUMENT DESCRIBES SPDXRef-Package-foo-1.0.0",
+            "LicenseID: LicenseRef-Foo",
+            "ExtractedText: <text>Do whatever.</text>",
+        ] {
+            assert!(tag_value.contains(line), "missing {:?} in:\n{}", line, tag_value);
+        }
+    }
+
+    #[test]
+    fn json() {
+        let json = document().to_json_value();
+        assert_eq!(json["spdxVersion"], "SPDX-2.3");
+        assert_eq!(json["packages"][0]["licenseDeclared"], "MIT OR LicenseRef-Foo");
+        assert_eq!(json["packages"][0]["licenseInfoFromFiles"][0], "MIT");
+        assert_eq!(json["files"][0]["checksums"][0]["algorithm"], "SHA1");
+        assert_eq!(json["relationships"][1]["relatedSpdxElement"], "SPDXRef-Package-foo-1.0.0-2");
+        assert_eq!(json["hasExtractedLicensingInfos"][0]["licenseId"], "LicenseRef-Foo");
+    }
+
+    #[test]
+    fn round_trip() {
+        let document = document();
+
+        let loaded = Document::from_tag_value(&document.to_tag_value()).unwrap();
+        assert!(loaded.errors.is_empty());
+        assert_eq!(loaded.document, document);
+
+        let loaded = Document::from_json(&document.to_json()).unwrap();
+        assert!(loaded.errors.is_empty());
+        assert_eq!(loaded.document, document);
+    }
+
+    #[test]
+    fn round_trip_with() {
+        let mut document = document();
+        let license = License::parse("GPL-2.0-only WITH Classpath-exception-2.0").unwrap();
+        document.packages[0].license_info_from_files.push(license.clone());
+        document.files[0].license_info_in_file.push(license);
+
+        let tag_value = document.to_tag_value();
+        for line in &[
+            "PackageLicenseInfoFromFiles: GPL-2.0-only WITH Classpath-exception-2.0",
+            "LicenseInfoInFile: GPL-2.0-only WITH Classpath-exception-2.0",
+        ] {
+            assert!(tag_value.contains(line), "missing {:?} in:\n{}", line, tag_value);
+        }
+        let loaded = Document::from_tag_value(&tag_value).unwrap();
+        assert!(loaded.errors.is_empty());
+        assert_eq!(loaded.document, document);
+
+        let json = document.to_json_value();
+        assert_eq!(
+            json["packages"][0]["licenseInfoFromFiles"][1],
+            "GPL-2.0-only WITH Classpath-exception-2.0",
+        );
+        let loaded = Document::from_json(&document.to_json()).unwrap();
+        assert!(loaded.errors.is_empty());
+        assert_eq!(loaded.document, document);
+    }
+
+    #[test]
+    fn read_errors() {
+        let loaded = Document::from_json(r#"{
+            "spdxVersion": "SPDX-2.3",
+            "packages": [
+                {
+                    "SPDXID": "SPDXRef-foo",
+                    "name": "foo",
+                    "licenseConcluded": "NONE",
+                    "licenseDeclared": "MIT WITH Classpath-exception-2.0",
+                    "licenseInfoFromFiles": ["MIT", "Not-A-License"]
+                },
+                {
+                    "SPDXID": "SPDXRef-bar",
+                    "name": "bar",
+                    "licenseDeclared": "GPL-2.0-only WITH Not-An-Exception"
+                }
+            ],
+            "documentDescribes": ["SPDXRef-foo"]
+        }"#).unwrap();
+
+        let package = &loaded.document.packages[0];
+        assert_eq!(package.license_concluded, LicenseField::None);
+        assert_eq!(package.license_declared.to_string(), "MIT WITH Classpath-exception-2.0");
+        assert_eq!(package.license_info_from_files.len(), 1);
+        assert_eq!(loaded.document.relationships[0].related, "SPDXRef-foo");
+        assert_eq!(loaded.document.packages[1].license_declared, LicenseField::NoAssertion);
+
+        let locations: Vec<String> = loaded.errors.iter()
+            .map(|e| e.location.to_string())
+            .collect();
+        assert_eq!(locations, [
+            "packages[0].licenseInfoFromFiles[1]",
+            "packages[1].licenseDeclared",
+        ]);
+
+        let loaded = Document::from_tag_value("\
+            SPDXVersion: SPDX-2.3
+            LicenseListVersion: 3.5
+            PackageName: foo
+            SPDXID: SPDXRef-foo
+            PackageLicenseDeclared: MIT OR BlueOak-1.0.0
+            PackageLicenseInfoFromFiles: Apache-2.0
+        ").unwrap();
+        assert_eq!(loaded.document.packages[0].license_declared, LicenseField::NoAssertion);
+        assert_eq!(loaded.document.packages[0].license_info_from_files.len(), 1);
+        assert_eq!(
+            loaded.errors[0].to_string(),
+            "line 5: invalid expression 'MIT OR BlueOak-1.0.0': 'BlueOak-1.0.0' is not a known \
+             license ID in license list version 3.5",
+        );
+
+        let loaded = Document::from_tag_value("\
+            SPDXVersion: SPDX-2.3
+            LicenseListVersion: 3.10
+            PackageName: foo
+            SPDXID: SPDXRef-foo
+            PackageLicenseDeclared: BSD-2-Clause-FreeBSD
+        ").unwrap();
+        assert_eq!(
+            loaded.document.packages[0].license_declared,
+            LicenseField::from(SpdxLicense::Bsd2ClauseFreeBsd),
+        );
+        assert!(loaded.errors.is_empty());
+        assert_eq!(
+            loaded.warnings[0].to_string(),
+            "line 5: expression 'BSD-2-Clause-FreeBSD' has licenses deprecated in license list \
+             version 3.10: BSD-2-Clause-FreeBSD",
+        );
+
+        let error = Document::from_tag_value("SPDXVersion: SPDX-1.2\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: unsupported version 'SPDX-1.2'");
+    }
+}
@@ -0,0 +1,670 @@
+//! The licensing profiles of [SPDX 3.0](https://spdx.github.io/spdx-spec/v3.0.1/).
+//!
+//! SPDX 3.0 represents a license expression either as a string in the
+//! simple-licensing profile or as a graph of elements in the
+//! expanded-licensing profile, both of which are modeled by
+//! [`AnyLicenseInfo`](enum.AnyLicenseInfo.html) and serialized as JSON-LD.
+//!
+//! ```
+//! use linfo::{sbom::spdx3::{AnyLicenseInfo, JsonLdWriter}, Expr};
+//!
+//! let expr = Expr::parse("MIT OR GPL-2.0-only WITH Classpath-exception-2.0").unwrap();
+//! let info = AnyLicenseInfo::from(&expr);
+//!
+//! let mut writer = JsonLdWriter::new("https://example.com/doc", "_:creationinfo");
+//! let id = writer.add(&info);
+//! let json_ld = writer.into_json_ld();
+//!
+//! let read = AnyLicenseInfo::from_json_ld(&json_ld, &id).unwrap();
+//! assert_eq!(read.to_expr().unwrap(), expr);
+//! ```
+
+use std::{collections::BTreeSet, error, fmt};
+use serde_json::{json, Map, Value};
+use crate::{
+    spdx::{LicenseRef, SpdxException},
+    Expr,
+    License,
+    SpdxLicense,
+};
+use super::{Location, ReadError};
+
+/// The version of the SPDX specification of the JSON-LD context.
+pub const SPEC_VERSION: &str = "3.0.1";
+
+/// The JSON-LD context of SPDX 3.0 documents.
+pub const CONTEXT: &str = "https://spdx.org/rdf/3.0.1/spdx-context.jsonld";
+
+const LISTED_LICENSE_PREFIX: &str = "https://spdx.org/licenses/";
+const NO_ASSERTION_LICENSE: &str = "https://spdx.org/rdf/3.0.1/terms/Expanded/NoAssertionLicense";
+const NONE_LICENSE: &str = "https://spdx.org/rdf/3.0.1/terms/Expanded/NoneLicense";
+
+// Elements referencing each other more deeply than this are rejected, which
+// also guards against cycles.
+const MAX_DEPTH: usize = 64;
+
+/// A license expression in SPDX 3.0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnyLicenseInfo {
+    /// There is no license (`NoneLicense`).
+    None,
+    /// The license was not determined (`NoAssertionLicense`).
+    NoAssertion,
+    /// A license expression string (`LicenseExpression`) in the
+    /// simple-licensing profile.
+    Expression(String),
+    /// A license on the SPDX License List (`ListedLicense`), by identifier.
+    Listed(String),
+    /// A license that is not on the SPDX License List (`CustomLicense`).
+    Custom(CustomLicense),
+    /// The license or any later version of it (`OrLaterOperator`).
+    OrLater(Box<AnyLicenseInfo>),
+    /// A license with an addition, such as an exception
+    /// (`WithAdditionOperator`).
+    WithAddition(Box<AnyLicenseInfo>, LicenseAddition),
+    /// The conditions of all licenses apply (`ConjunctiveLicenseSet`).
+    Conjunctive(Vec<AnyLicenseInfo>),
+    /// The conditions of any of the licenses apply (`DisjunctiveLicenseSet`).
+    Disjunctive(Vec<AnyLicenseInfo>),
+}
+
+/// A license that is not on the SPDX License List.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomLicense {
+    /// The identifier of the license.
+    pub id: LicenseRef,
+    /// The name of the license.
+    pub name: Option<String>,
+    /// The full text of the license.
+    pub text: String,
+}
+
+/// Text that modifies a license, such as an exception.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LicenseAddition {
+    /// An exception on the SPDX License List (`ListedLicenseException`), by
+    /// identifier.
+    Listed(String),
+    /// An addition that is not on the SPDX License List
+    /// (`CustomLicenseAddition`).
+    Custom(CustomLicenseAddition),
+}
+
+/// An addition that is not on the SPDX License List.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomLicenseAddition {
+    /// The identifier of the addition, such as `AdditionRef-Custom`.
+    pub id: String,
+    /// The name of the addition.
+    pub name: Option<String>,
+    /// The full text of the addition.
+    pub text: String,
+}
+
+impl From<SpdxLicense> for AnyLicenseInfo {
+    #[inline]
+    fn from(l: SpdxLicense) -> Self {
+        AnyLicenseInfo::Listed(l.id().to_owned())
+    }
+}
+
+impl From<&License> for AnyLicenseInfo {
+    fn from(l: &License) -> Self {
+        match l {
+            License::Spdx(l) => (*l).into(),
+            License::Ref(l) => AnyLicenseInfo::Custom(CustomLicense {
+                id: l.clone(),
+                name: None,
+                text: String::new(),
+            }),
+            License::With(l, e) => AnyLicenseInfo::WithAddition(
+                Box::new((&**l).into()),
+                LicenseAddition::Listed(e.id().to_owned()),
+            ),
+            License::_NonExhaustive(never) => never.consume(),
+        }
+    }
+}
+
+impl From<&Expr> for AnyLicenseInfo {
+    /// Converts `expr` into the expanded-licensing profile.
+    fn from(expr: &Expr) -> Self {
+        let members = |exprs: &[Expr]| exprs.iter().map(AnyLicenseInfo::from).collect();
+        match expr {
+            Expr::Single(l) => l.into(),
+            Expr::Or(or) => AnyLicenseInfo::Disjunctive(members(or.as_slice())),
+            Expr::And(and) => AnyLicenseInfo::Conjunctive(members(and.as_slice())),
+        }
+    }
+}
+
+impl AnyLicenseInfo {
+    /// Converts `expr` into the simple-licensing profile.
+    #[inline]
+    pub fn expression(expr: &Expr) -> Self {
+        AnyLicenseInfo::Expression(expr.to_string())
+    }
+
+    /// Converts this into an [`Expr`](../../expr/enum.Expr.html).
+    ///
+    /// Custom additions, `NONE` and `NOASSERTION` cannot be converted.
+    pub fn to_expr(&self) -> Result<Expr, ConvertError> {
+        let flatten = |members: &[AnyLicenseInfo], conjunctive: bool| {
+            let mut exprs = Vec::new();
+            self::flatten(members, conjunctive, &mut exprs)?;
+            Ok(exprs)
+        };
+        match self {
+            AnyLicenseInfo::Expression(expr) => Expr::parse_strict(expr)
+                .map_err(|e| ConvertError::Expression(expr.clone(), e.to_string())),
+            AnyLicenseInfo::Conjunctive(members) => match flatten(members, true)? {
+                mut exprs if exprs.len() == 1 => Ok(exprs.remove(0)),
+                exprs => Ok(crate::expr::And::new(exprs).into()),
+            },
+            AnyLicenseInfo::Disjunctive(members) => match flatten(members, false)? {
+                mut exprs if exprs.len() == 1 => Ok(exprs.remove(0)),
+                exprs => Ok(crate::expr::Or::new(exprs).into()),
+            },
+            info => info.to_license().map(Expr::Single),
+        }
+    }
+
+    /// Converts this into a single [`License`](../../enum.License.html).
+    pub fn to_license(&self) -> Result<License, ConvertError> {
+        match self {
+            AnyLicenseInfo::None | AnyLicenseInfo::NoAssertion => {
+                Err(ConvertError::NoLicense)
+            },
+            AnyLicenseInfo::Expression(expr) => match self.to_expr()? {
+                Expr::Single(l) => Ok(l),
+                _ => Err(ConvertError::Nested(expr.clone())),
+            },
+            AnyLicenseInfo::Listed(id) => SpdxLicense::parse(id.as_str())
+                .map(License::Spdx)
+                .map_err(|_| ConvertError::UnknownLicense(id.clone())),
+            AnyLicenseInfo::Custom(l) => Ok(License::Ref(l.id.clone())),
+            AnyLicenseInfo::OrLater(l) => match &**l {
+                AnyLicenseInfo::Listed(id) => or_later(id)
+                    .map(License::Spdx)
+                    .ok_or_else(|| ConvertError::UnknownLicense(format!("{}+", id))),
+                _ => Err(ConvertError::Nested(self.to_string())),
+            },
+            AnyLicenseInfo::WithAddition(l, addition) => {
+                let exception = match addition {
+                    LicenseAddition::Listed(id) => SpdxException::parse(id.as_str())
+                        .map_err(|_| ConvertError::UnknownException(id.clone()))?,
+                    LicenseAddition::Custom(a) => {
+                        return Err(ConvertError::UnknownException(a.id.clone()));
+                    },
+                };
+                match l.to_license()? {
+                    License::With(..) => Err(ConvertError::Nested(self.to_string())),
+                    l => Ok(License::With(Box::new(l), exception)),
+                }
+            },
+            AnyLicenseInfo::Conjunctive(_) | AnyLicenseInfo::Disjunctive(_) => {
+                Err(ConvertError::Nested(self.to_string()))
+            },
+        }
+    }
+
+    /// Reads the license element identified by `id` from a JSON-LD document
+    /// or its `@graph` array.
+    ///
+    /// Listed licenses, listed exceptions and the `NoneLicense` and
+    /// `NoAssertionLicense` individuals may be referenced without being
+    /// defined in the graph.
+    pub fn from_json_ld(json_ld: &Value, id: &str) -> Result<Self, ReadError> {
+        let graph = json_ld.get("@graph").unwrap_or(json_ld);
+        let graph = graph.as_array().ok_or_else(|| ReadError::Invalid {
+            location: Location::Path("@graph".to_owned()),
+            message: "expected an array".to_owned(),
+        })?;
+        JsonLdReader { graph }.read(&Value::String(id.to_owned()), 0)
+    }
+}
+
+impl fmt::Display for AnyLicenseInfo {
+    /// Writes this as an SPDX license expression, with parentheses around
+    /// nested license sets.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let set = |f: &mut fmt::Formatter, members: &[AnyLicenseInfo], sep| {
+            for (i, member) in members.iter().enumerate() {
+                if i != 0 {
+                    f.write_str(sep)?;
+                }
+                match member {
+                    AnyLicenseInfo::Conjunctive(_) |
+                    AnyLicenseInfo::Disjunctive(_) => write!(f, "({})", member)?,
+                    _ => write!(f, "{}", member)?,
+                }
+            }
+            Ok(())
+        };
+        match self {
+            AnyLicenseInfo::None => f.write_str("NONE"),
+            AnyLicenseInfo::NoAssertion => f.write_str("NOASSERTION"),
+            AnyLicenseInfo::Expression(expr) => f.write_str(expr),
+            AnyLicenseInfo::Listed(id) => f.write_str(id),
+            AnyLicenseInfo::Custom(l) => l.id.fmt(f),
+            AnyLicenseInfo::OrLater(l) => write!(f, "{}+", l),
+            AnyLicenseInfo::WithAddition(l, addition) => match addition {
+                LicenseAddition::Listed(id) => write!(f, "{} WITH {}", l, id),
+                LicenseAddition::Custom(a) => write!(f, "{} WITH {}", l, a.id),
+            },
+            AnyLicenseInfo::Conjunctive(members) => set(f, members, " AND "),
+            AnyLicenseInfo::Disjunctive(members) => set(f, members, " OR "),
+        }
+    }
+}
+
+/// Appends the licenses of `members` to `licenses`, flattening nested sets of
+/// the same kind.
+fn flatten(
+    members: &[AnyLicenseInfo],
+    conjunctive: bool,
+    exprs: &mut Vec<Expr>,
+) -> Result<(), ConvertError> {
+    for member in members {
+        match member.to_expr()? {
+            Expr::And(and) if conjunctive => exprs.extend(and.into_vec()),
+            Expr::Or(or) if !conjunctive => exprs.extend(or.into_vec()),
+            expr => exprs.push(expr),
+        }
+    }
+    Ok(())
+}
+
+/// Returns the "or later" version of the license `id`, such as
+/// `GPL-2.0-or-later` for `GPL-2.0-only`.
+fn or_later(id: &str) -> Option<SpdxLicense> {
+    let base = id.strip_suffix("-only").unwrap_or(id);
+    SpdxLicense::parse(format!("{}-or-later", base).as_str()).ok()
+}
+
+/// An error returned when an [`AnyLicenseInfo`](enum.AnyLicenseInfo.html)
+/// cannot be converted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConvertError {
+    /// The value is `NONE` or `NOASSERTION`.
+    NoLicense,
+    /// The license is not known to this library.
+    UnknownLicense(String),
+    /// The exception is not known to this library.
+    UnknownException(String),
+    /// The expression string could not be parsed.
+    Expression(String, String),
+    /// The expression nests sets of different kinds, which cannot be
+    /// represented by an [`Expr`](../../expr/enum.Expr.html).
+    Nested(String),
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConvertError::NoLicense => f.write_str("no license expression"),
+            ConvertError::UnknownLicense(id) => write!(f, "unknown license '{}'", id),
+            ConvertError::UnknownException(id) => write!(f, "unknown exception '{}'", id),
+            ConvertError::Expression(expr, reason) => {
+                write!(f, "invalid expression '{}': {}", expr, reason)
+            },
+            ConvertError::Nested(expr) => write!(f, "unsupported nesting in '{}'", expr),
+        }
+    }
+}
+
+impl error::Error for ConvertError {}
+
+/// Writes license elements into a JSON-LD graph.
+#[derive(Clone, Debug)]
+pub struct JsonLdWriter {
+    namespace: String,
+    creation_info: String,
+    elements: Vec<Value>,
+    defined: BTreeSet<String>,
+    next: usize,
+}
+
+impl JsonLdWriter {
+    /// Creates a writer where new elements are identified within `namespace`
+    /// and refer to the `CreationInfo` identified by `creation_info`.
+    pub fn new<N, C>(namespace: N, creation_info: C) -> Self
+        where N: Into<String>, C: Into<String>
+    {
+        Self {
+            namespace: namespace.into(),
+            creation_info: creation_info.into(),
+            elements: Vec::new(),
+            defined: BTreeSet::new(),
+            next: 0,
+        }
+    }
+
+    /// Adds the elements of `info` to the graph and returns the identifier to
+    /// refer to it by, such as from a `hasDeclaredLicense` relationship.
+    pub fn add(&mut self, info: &AnyLicenseInfo) -> String {
+        match info {
+            AnyLicenseInfo::None => NONE_LICENSE.to_owned(),
+            AnyLicenseInfo::NoAssertion => NO_ASSERTION_LICENSE.to_owned(),
+            AnyLicenseInfo::Listed(id) => format!("{}{}", LISTED_LICENSE_PREFIX, id),
+            AnyLicenseInfo::Expression(expr) => self.element(
+                "simplelicensing_LicenseExpression",
+                json!({ "simplelicensing_licenseExpression": expr }),
+            ),
+            AnyLicenseInfo::Custom(l) => {
+                let id = format!("{}#{}", self.namespace, l.id);
+                if self.defined.insert(id.clone()) {
+                    let mut element = json!({
+                        "type": "expandedlicensing_CustomLicense",
+                        "spdxId": id,
+                        "creationInfo": self.creation_info,
+                        "simplelicensing_licenseText": l.text,
+                    });
+                    if let Some(name) = &l.name {
+                        element["name"] = json!(name);
+                    }
+                    self.elements.push(element);
+                }
+                id
+            },
+            AnyLicenseInfo::OrLater(l) => {
+                let subject = self.add(l);
+                self.element(
+                    "expandedlicensing_OrLaterOperator",
+                    json!({ "expandedlicensing_subjectLicense": subject }),
+                )
+            },
+            AnyLicenseInfo::WithAddition(l, addition) => {
+                let subject = self.add(l);
+                let addition = self.addition(addition);
+                self.element(
+                    "expandedlicensing_WithAdditionOperator",
+                    json!({
+                        "expandedlicensing_subjectExtendableLicense": subject,
+                        "expandedlicensing_subjectAddition": addition,
+                    }),
+                )
+            },
+            AnyLicenseInfo::Conjunctive(members) => {
+                let members: Vec<String> = members.iter().map(|m| self.add(m)).collect();
+                self.element(
+                    "expandedlicensing_ConjunctiveLicenseSet",
+                    json!({ "expandedlicensing_member": members }),
+                )
+            },
+            AnyLicenseInfo::Disjunctive(members) => {
+                let members: Vec<String> = members.iter().map(|m| self.add(m)).collect();
+                self.element(
+                    "expandedlicensing_DisjunctiveLicenseSet",
+                    json!({ "expandedlicensing_member": members }),
+                )
+            },
+        }
+    }
+
+    fn addition(&mut self, addition: &LicenseAddition) -> String {
+        match addition {
+            LicenseAddition::Listed(id) => format!("{}{}", LISTED_LICENSE_PREFIX, id),
+            LicenseAddition::Custom(a) => {
+                let id = format!("{}#{}", self.namespace, a.id);
+                if self.defined.insert(id.clone()) {
+                    let mut element = json!({
+                        "type": "expandedlicensing_CustomLicenseAddition",
+                        "spdxId": id,
+                        "creationInfo": self.creation_info,
+                        "expandedlicensing_additionText": a.text,
+                    });
+                    if let Some(name) = &a.name {
+                        element["name"] = json!(name);
+                    }
+                    self.elements.push(element);
+                }
+                id
+            },
+        }
+    }
+
+    /// Adds an element of type `kind` with `properties` and a new identifier.
+    fn element(&mut self, kind: &str, properties: Value) -> String {
+        self.next += 1;
+        let id = format!("{}#license-{}", self.namespace, self.next);
+
+        let mut element = Map::new();
+        element.insert("type".into(), json!(kind));
+        element.insert("spdxId".into(), json!(id));
+        element.insert("creationInfo".into(), json!(self.creation_info));
+        if let Value::Object(properties) = properties {
+            element.extend(properties);
+        }
+        self.elements.push(Value::Object(element));
+        id
+    }
+
+    /// Returns the elements added so far, to be included in a document's
+    /// `@graph`.
+    #[inline]
+    pub fn into_elements(self) -> Vec<Value> {
+        self.elements
+    }
+
+    /// Returns a JSON-LD document containing the elements added so far.
+    ///
+    /// The `CreationInfo` referenced by each element must be added to the
+    /// `@graph` for the document to be valid.
+    pub fn into_json_ld(self) -> Value {
+        json!({
+            "@context": CONTEXT,
+            "@graph": self.elements,
+        })
+    }
+}
+
+struct JsonLdReader<'a> {
+    graph: &'a [Value],
+}
+
+impl JsonLdReader<'_> {
+    /// Reads the element `value`, which is either an identifier or an inline
+    /// object.
+    fn read(&self, value: &Value, depth: usize) -> Result<AnyLicenseInfo, ReadError> {
+        let (id, element) = match value {
+            Value::String(id) => (id.as_str(), self.find(id)),
+            Value::Object(object) => {
+                let id = object.get("spdxId").and_then(Value::as_str).unwrap_or("");
+                (id, Some(object))
+            },
+            _ => return Err(invalid("", "expected a license element")),
+        };
+        if depth > MAX_DEPTH {
+            return Err(invalid(id, "license elements are nested too deeply"));
+        }
+
+        let element = match element {
+            Some(element) => element,
+            None => return referenced(id).ok_or_else(|| invalid(id, "undefined license element")),
+        };
+        let kind = element.get("type")
+            .or_else(|| element.get("@type"))
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        let property = |name: &str| {
+            element.get(&format!("expandedlicensing_{}", name))
+                .or_else(|| element.get(name))
+        };
+        let string = |name: &str| {
+            element.get(name).and_then(Value::as_str).map(ToOwned::to_owned)
+        };
+        let required = |name: &str| {
+            property(name).ok_or_else(|| invalid(id, &format!("missing '{}'", name)))
+        };
+
+        match kind.rsplit('_').next().unwrap_or(kind) {
+            "LicenseExpression" => {
+                let expr = element.get("simplelicensing_licenseExpression")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| invalid(id, "missing 'licenseExpression'"))?;
+                Ok(AnyLicenseInfo::Expression(expr.to_owned()))
+            },
+            "ListedLicense" => Ok(AnyLicenseInfo::Listed(last_segment(id).to_owned())),
+            "CustomLicense" => Ok(AnyLicenseInfo::Custom(CustomLicense {
+                id: license_ref(id, string("name").as_deref()),
+                name: string("name"),
+                text: string("simplelicensing_licenseText").unwrap_or_default(),
+            })),
+            "OrLaterOperator" => {
+                let subject = self.read(required("subjectLicense")?, depth + 1)?;
+                Ok(AnyLicenseInfo::OrLater(Box::new(subject)))
+            },
+            "WithAdditionOperator" => {
+                let subject = self.read(required("subjectExtendableLicense")?, depth + 1)?;
+                let addition = self.addition(required("subjectAddition")?)?;
+                Ok(AnyLicenseInfo::WithAddition(Box::new(subject), addition))
+            },
+            "ConjunctiveLicenseSet" | "DisjunctiveLicenseSet" => {
+                let members = required("member")?
+                    .as_array()
+                    .ok_or_else(|| invalid(id, "expected an array of members"))?
+                    .iter()
+                    .map(|member| self.read(member, depth + 1))
+                    .collect::<Result<_, _>>()?;
+                if kind.ends_with("ConjunctiveLicenseSet") {
+                    Ok(AnyLicenseInfo::Conjunctive(members))
+                } else {
+                    Ok(AnyLicenseInfo::Disjunctive(members))
+                }
+            },
+            _ => referenced(id).ok_or_else(|| {
+                invalid(id, &format!("unsupported license element type '{}'", kind))
+            }),
+        }
+    }
+
+    fn addition(&self, value: &Value) -> Result<LicenseAddition, ReadError> {
+        let (id, element) = match value {
+            Value::String(id) => (id.as_str(), self.find(id)),
+            Value::Object(object) => {
+                let id = object.get("spdxId").and_then(Value::as_str).unwrap_or("");
+                (id, Some(object))
+            },
+            _ => return Err(invalid("", "expected a license addition")),
+        };
+        let kind = element
+            .and_then(|e| e.get("type").or_else(|| e.get("@type")))
+            .and_then(Value::as_str)
+            .unwrap_or("");
+
+        if kind.ends_with("CustomLicenseAddition") {
+            let element = element.unwrap();
+            let string = |name: &str| {
+                element.get(name).and_then(Value::as_str).map(ToOwned::to_owned)
+            };
+            let addition_id = match id.rfind("AdditionRef-") {
+                Some(start) => id[start..].to_owned(),
+                None => last_segment(id).to_owned(),
+            };
+            Ok(LicenseAddition::Custom(CustomLicenseAddition {
+                id: addition_id,
+                name: string("name"),
+                text: string("expandedlicensing_additionText").unwrap_or_default(),
+            }))
+        } else if id.starts_with(LISTED_LICENSE_PREFIX) || kind.ends_with("ListedLicenseException") {
+            Ok(LicenseAddition::Listed(last_segment(id).to_owned()))
+        } else {
+            Err(invalid(id, "undefined license addition"))
+        }
+    }
+
+    fn find(&self, id: &str) -> Option<&Map<String, Value>> {
+        self.graph.iter()
+            .filter_map(Value::as_object)
+            .find(|element| {
+                let element_id = element.get("spdxId").or_else(|| element.get("@id"));
+                element_id.and_then(Value::as_str) == Some(id)
+            })
+    }
+}
+
+/// Returns the license referred to by `id` without being defined in a graph.
+fn referenced(id: &str) -> Option<AnyLicenseInfo> {
+    if id.ends_with("NoAssertionLicense") {
+        Some(AnyLicenseInfo::NoAssertion)
+    } else if id.ends_with("NoneLicense") {
+        Some(AnyLicenseInfo::None)
+    } else if id.starts_with(LISTED_LICENSE_PREFIX) {
+        Some(AnyLicenseInfo::Listed(last_segment(id).to_owned()))
+    } else {
+        None
+    }
+}
+
+fn last_segment(iri: &str) -> &str {
+    iri.rsplit(['/', '#']).next().unwrap_or(iri)
+}
+
+/// Returns the `LicenseRef-` at the end of `id`, or one derived from `name` or
+/// `id` otherwise.
+fn license_ref(id: &str, name: Option<&str>) -> LicenseRef {
+    id.rfind("LicenseRef-")
+        .and_then(|start| LicenseRef::parse(&id[start..]).ok())
+        .unwrap_or_else(|| LicenseRef::new(name.unwrap_or_else(|| last_segment(id))))
+}
+
+fn invalid(id: &str, message: &str) -> ReadError {
+    ReadError::Invalid {
+        location: Location::Path(id.to_owned()),
+        message: message.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expanded_round_trip() {
+        let info = AnyLicenseInfo::Conjunctive(vec![
+            AnyLicenseInfo::Disjunctive(vec![
+                AnyLicenseInfo::Listed("MIT".to_owned()),
+                AnyLicenseInfo::OrLater(Box::new(AnyLicenseInfo::Listed("GPL-2.0-only".to_owned()))),
+            ]),
+            AnyLicenseInfo::Custom(CustomLicense {
+                id: LicenseRef::new("Foo"),
+                name: Some("Foo License".to_owned()),
+                text: "Do whatever.".to_owned(),
+            }),
+        ]);
+        assert_eq!(info.to_string(), "(MIT OR GPL-2.0-only+) AND LicenseRef-Foo");
+        assert_eq!(
+            info.to_expr().unwrap().to_string(),
+            "(MIT OR GPL-2.0-or-later) AND LicenseRef-Foo",
+        );
+
+        let mut writer = JsonLdWriter::new("https://example.com/doc", "_:creationinfo");
+        let id = writer.add(&info);
+        let json_ld = writer.into_json_ld();
+        assert_eq!(AnyLicenseInfo::from_json_ld(&json_ld, &id).unwrap(), info);
+
+        let or_later = AnyLicenseInfo::OrLater(Box::new(AnyLicenseInfo::Listed("GPL-2.0-only".to_owned())));
+        assert_eq!(or_later.to_expr().unwrap(), Expr::from(SpdxLicense::Gpl2OrLater));
+    }
+
+    #[test]
+    fn simple_expression() {
+        let json_ld = json!({
+            "@context": CONTEXT,
+            "@graph": [
+                {
+                    "type": "simplelicensing_LicenseExpression",
+                    "spdxId": "https://example.com/doc#expr",
+                    "simplelicensing_licenseExpression": "MIT OR Apache-2.0",
+                },
+            ],
+        });
+        let info = AnyLicenseInfo::from_json_ld(&json_ld, "https://example.com/doc#expr").unwrap();
+        assert_eq!(info.to_expr().unwrap(), Expr::parse("MIT OR Apache-2.0").unwrap());
+
+        let none = AnyLicenseInfo::from_json_ld(&json_ld, NONE_LICENSE).unwrap();
+        assert_eq!(none, AnyLicenseInfo::None);
+        assert!(AnyLicenseInfo::from_json_ld(&json_ld, "https://example.com/doc#x").is_err());
+    }
+}
@@ -0,0 +1,336 @@
+use std::fmt::Write;
+use super::*;
+
+/// Writes `document` in the tag-value format.
+pub(super) fn write(document: &Document) -> String {
+    let mut out = String::new();
+
+    tag(&mut out, "SPDXVersion", SPDX_VERSION);
+    tag(&mut out, "DataLicense", DATA_LICENSE);
+    tag(&mut out, "SPDXID", Document::SPDX_ID);
+    tag(&mut out, "DocumentName", &document.name);
+    tag(&mut out, "DocumentNamespace", &document.namespace);
+    if let Some(comment) = &document.comment {
+        tag(&mut out, "DocumentComment", comment);
+    }
+
+    let info = &document.creation_info;
+    for creator in &info.creators {
+        tag(&mut out, "Creator", creator);
+    }
+    tag(&mut out, "Created", &info.created);
+    if let Some(version) = &info.license_list_version {
+        tag(&mut out, "LicenseListVersion", version);
+    }
+    if let Some(comment) = &info.comment {
+        tag(&mut out, "CreatorComment", comment);
+    }
+
+    // Files that follow a package are implicitly contained by it, so those
+    // outside of any package come first.
+    let contains = |package: &Package, file: &File| {
+        document.relationships.iter().any(|r| {
+            r.kind == RelationshipType::Contains &&
+            r.element == package.id &&
+            r.related == file.id
+        })
+    };
+    for file in &document.files {
+        if !document.packages.iter().any(|p| contains(p, file)) {
+            write_file(&mut out, file);
+        }
+    }
+
+    for package in &document.packages {
+        out.push('\n');
+        tag(&mut out, "PackageName", &package.name);
+        tag(&mut out, "SPDXID", &package.id);
+        if let Some(version) = &package.version {
+            tag(&mut out, "PackageVersion", version);
+        }
+        if let Some(supplier) = &package.supplier {
+            tag(&mut out, "PackageSupplier", supplier);
+        }
+        tag(&mut out, "PackageDownloadLocation", or_no_assertion(&package.download_location));
+        tag(&mut out, "FilesAnalyzed", if package.files_analyzed { "true" } else { "false" });
+        tag(&mut out, "PackageLicenseConcluded", &package.license_concluded.to_string());
+        for license in &package.license_info_from_files {
+            tag(&mut out, "PackageLicenseInfoFromFiles", &license.to_string());
+        }
+        tag(&mut out, "PackageLicenseDeclared", &package.license_declared.to_string());
+        tag(&mut out, "PackageCopyrightText", or_no_assertion(&package.copyright_text));
+
+        for file in &document.files {
+            if contains(package, file) {
+                write_file(&mut out, file);
+            }
+        }
+    }
+
+    if !document.relationships.is_empty() {
+        out.push('\n');
+    }
+    for relationship in &document.relationships {
+        let value = format!(
+            "{} {} {}",
+            relationship.element,
+            relationship.kind,
+            relationship.related,
+        );
+        tag(&mut out, "Relationship", &value);
+    }
+
+    for license in &document.extracted_licenses {
+        out.push('\n');
+        tag(&mut out, "LicenseID", license.id.id());
+        // Always delimited, since license texts commonly span lines
+        writeln!(out, "ExtractedText: <text>{}</text>", license.text).unwrap();
+        if let Some(name) = &license.name {
+            tag(&mut out, "LicenseName", name);
+        }
+        for url in &license.see_also {
+            tag(&mut out, "LicenseCrossReference", url);
+        }
+        if let Some(comment) = &license.comment {
+            tag(&mut out, "LicenseComment", comment);
+        }
+    }
+
+    out
+}
+
+fn write_file(out: &mut String, file: &File) {
+    out.push('\n');
+    tag(out, "FileName", &file.name);
+    tag(out, "SPDXID", &file.id);
+    for checksum in &file.checksums {
+        let value = format!("{}: {}", checksum.algorithm, checksum.value);
+        tag(out, "FileChecksum", &value);
+    }
+    tag(out, "LicenseConcluded", &file.license_concluded.to_string());
+    for license in &file.license_info_in_file {
+        tag(out, "LicenseInfoInFile", &license.to_string());
+    }
+    tag(out, "FileCopyrightText", or_no_assertion(&file.copyright_text));
+}
+
+fn tag(out: &mut String, tag: &str, value: &str) {
+    if value.contains('\n') {
+        writeln!(out, "{}: <text>{}</text>", tag, value)
+    } else {
+        writeln!(out, "{}: {}", tag, value)
+    }.unwrap();
+}
+
+fn or_no_assertion(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("NOASSERTION")
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    Document,
+    Package(usize),
+    File(usize),
+    License(usize),
+    // Snippets, annotations, and invalid license definitions
+    Other,
+}
+
+/// Reads a document in the tag-value format.
+pub(super) fn read(input: &str) -> Result<Loaded, ReadError> {
+    let mut reader = Reader {
+        errors: Vec::new(),
+        warnings: Vec::new(),
+        list_version: None,
+    };
+    let mut document = Document::new(String::new(), String::new(), CreationInfo {
+        created: String::new(),
+        creators: Vec::new(),
+        license_list_version: None,
+        comment: None,
+    });
+    let mut section = Section::Document;
+    let mut package = None;
+    let mut contained = Vec::new();
+
+    let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
+    while let Some((number, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |message: String| ReadError::Invalid {
+            location: Location::Line(number),
+            message,
+        };
+
+        let colon = line.find(':')
+            .ok_or_else(|| invalid(format!("expected 'Tag: value', found '{}'", line)))?;
+        let tag = line[..colon].trim();
+        let mut value = line[(colon + 1)..].trim().to_owned();
+
+        if let Some(text) = value.strip_prefix("<text>") {
+            let mut text = text.to_owned();
+            while !text.contains("</text>") {
+                let (_, next) = lines.next()
+                    .ok_or_else(|| invalid("unterminated <text>".to_owned()))?;
+                text.push('\n');
+                text.push_str(next);
+            }
+            text.truncate(text.find("</text>").unwrap());
+            value = text;
+        }
+        let location = || Location::Line(number);
+
+        match tag {
+            "SPDXVersion" => check_version(&value, location())?,
+            "DocumentName" => document.name = value,
+            "DocumentNamespace" => document.namespace = value,
+            "DocumentComment" => document.comment = Some(value),
+            "Creator" => document.creation_info.creators.push(value),
+            "Created" => document.creation_info.created = value,
+            "LicenseListVersion" => {
+                reader.list_version = ListVersion::parse(&value);
+                document.creation_info.license_list_version = Some(value);
+            },
+            "CreatorComment" => document.creation_info.comment = Some(value),
+
+            "SPDXID" => match section {
+                Section::Package(i) => document.packages[i].id = value,
+                Section::File(i) => document.files[i].id = value,
+                _ => {},
+            },
+
+            "PackageName" => {
+                let mut new = Package::new(&value, None);
+                new.id = String::new();
+                new.files_analyzed = true;
+                document.packages.push(new);
+                section = Section::Package(document.packages.len() - 1);
+                package = Some(document.packages.len() - 1);
+            },
+            "FileName" => {
+                let mut new = File::new(&value);
+                new.id = String::new();
+                document.files.push(new);
+                section = Section::File(document.files.len() - 1);
+                if let Some(package) = package {
+                    contained.push((package, document.files.len() - 1));
+                }
+            },
+            "LicenseID" => {
+                section = match reader.license(&value, location) {
+                    Some(License::Ref(id)) => {
+                        document.extracted_licenses.push(ExtractedLicense {
+                            id,
+                            text: String::new(),
+                            name: None,
+                            see_also: Vec::new(),
+                            comment: None,
+                        });
+                        Section::License(document.extracted_licenses.len() - 1)
+                    },
+                    _ => Section::Other,
+                };
+            },
+            "SnippetSPDXID" => section = Section::Other,
+
+            "Relationship" => {
+                let parts: Vec<&str> = value.split_whitespace().collect();
+                let kind = match parts.get(1) {
+                    Some(kind) if parts.len() == 3 => RelationshipType::parse(kind)
+                        .ok_or_else(|| invalid(format!("unknown relationship type '{}'", kind)))?,
+                    _ => return Err(invalid(format!("invalid relationship '{}'", value))),
+                };
+                document.relationships.push(Relationship {
+                    element: parts[0].to_owned(),
+                    kind,
+                    related: parts[2].to_owned(),
+                });
+            },
+
+            _ => match section {
+                Section::Package(i) => {
+                    let package = &mut document.packages[i];
+                    match tag {
+                        "PackageVersion" => package.version = Some(value),
+                        "PackageSupplier" => package.supplier = text_value(&value),
+                        "PackageDownloadLocation" => {
+                            package.download_location = text_value(&value);
+                        },
+                        "FilesAnalyzed" => package.files_analyzed = value != "false",
+                        "PackageLicenseConcluded" => {
+                            package.license_concluded = reader.license_value(&value, location);
+                        },
+                        "PackageLicenseDeclared" => {
+                            package.license_declared = reader.license_value(&value, location);
+                        },
+                        "PackageLicenseInfoFromFiles" => {
+                            if let Some(license) = reader.license(&value, location) {
+                                package.license_info_from_files.push(license);
+                            }
+                        },
+                        "PackageCopyrightText" => package.copyright_text = text_value(&value),
+                        _ => {},
+                    }
+                },
+                Section::File(i) => {
+                    let file = &mut document.files[i];
+                    match tag {
+                        "FileChecksum" => {
+                            let checksum = value.find(':').and_then(|colon| {
+                                let algorithm = ChecksumAlgorithm::parse(value[..colon].trim())?;
+                                let value = value[(colon + 1)..].trim().to_owned();
+                                Some(Checksum { algorithm, value })
+                            });
+                            let checksum = checksum
+                                .ok_or_else(|| invalid(format!("invalid checksum '{}'", value)))?;
+                            file.checksums.push(checksum);
+                        },
+                        "LicenseConcluded" => {
+                            file.license_concluded = reader.license_value(&value, location);
+                        },
+                        "LicenseInfoInFile" => {
+                            if let Some(license) = reader.license(&value, location) {
+                                file.license_info_in_file.push(license);
+                            }
+                        },
+                        "FileCopyrightText" => file.copyright_text = text_value(&value),
+                        _ => {},
+                    }
+                },
+                Section::License(i) => {
+                    let license = &mut document.extracted_licenses[i];
+                    match tag {
+                        "ExtractedText" => license.text = value,
+                        "LicenseName" => license.name = text_value(&value),
+                        "LicenseCrossReference" => license.see_also.push(value),
+                        "LicenseComment" => license.comment = Some(value),
+                        _ => {},
+                    }
+                },
+                Section::Document | Section::Other => {},
+            },
+        }
+    }
+
+    // Files that follow a package are contained by it, unless stated
+    // otherwise.
+    for (package, file) in contained {
+        let package = &document.packages[package].id;
+        let file = &document.files[file].id;
+        let is_related = document.relationships.iter().any(|r| {
+            (&r.element == package && &r.related == file) ||
+            (&r.element == file && &r.related == package)
+        });
+        if !is_related {
+            document.relationships.push(Relationship {
+                element: package.clone(),
+                kind: RelationshipType::Contains,
+                related: file.clone(),
+            });
+        }
+    }
+
+    Ok(Loaded { document, errors: reader.errors, warnings: reader.warnings })
+}
@@ -0,0 +1,478 @@
+//! Choosing one license of each `OR` expression across many packages.
+//!
+//! Choices interact with each other: choosing `Apache-2.0` for one package and
+//! `GPL-2.0-only` for another produces a work that can't be distributed. The
+//! [`solve`](fn.solve.html) function finds choices such that every chosen
+//! license is allowed by a [`Policy`](../policy/struct.Policy.html) and all are
+//! [compatible](../compat/index.html) with each other, while minimizing the
+//! number of distinct licenses (and therefore attribution obligations).
+
+use std::{collections::HashMap, fmt};
+use crate::{
+    compat,
+    policy::Policy,
+    Expr,
+    License,
+};
+
+/// Chooses one branch of every `OR` expression in `packages` such that the
+/// chosen licenses are all allowed by `policy` and mutually compatible.
+///
+/// Among valid choices, the one with the fewest distinct licenses is returned.
+/// Ties are broken by `preference`, where earlier licenses are preferred and
+/// unlisted licenses are least preferred.
+///
+/// If there is no valid choice, the returned error contains a minimal set of
+/// packages that can't be satisfied together.
+///
+/// The search is exhaustive, but packages with the same expression share the
+/// same choice, which keeps typical dependency graphs small.
+///
+/// ```
+/// use linfo::{policy::Policy, solve, spdx::LicenseKind, Expr, SpdxLicense};
+///
+/// let a = Expr::parse("MIT OR Apache-2.0").unwrap();
+/// let b = Expr::parse("Apache-2.0 OR GPL-2.0-only").unwrap();
+/// let c = Expr::parse("MIT").unwrap();
+///
+/// let policy = Policy {
+///     allow_kinds: vec![LicenseKind::Permissive, LicenseKind::StrongCopyleft],
+///     ..Policy::default()
+/// };
+///
+/// let solution = solve::solve(vec![("a", &a), ("b", &b), ("c", &c)], &policy, &[]).unwrap();
+/// assert_eq!(solution.licenses().len(), 2);
+/// assert_eq!(solution.choices()[1].licenses, [&SpdxLicense::Apache2.into()]);
+/// ```
+pub fn solve<'a, I>(
+    packages: I,
+    policy: &Policy,
+    preference: &[License],
+) -> Result<Solution<'a>, Unsatisfiable<'a>>
+    where I: IntoIterator<Item = (&'a str, &'a Expr)>
+{
+    let packages: Vec<(&'a str, &'a Expr)> = packages.into_iter().collect();
+
+    // Packages with the same expression share the same choice, since choosing
+    // differently can only add licenses.
+    let mut exprs: Vec<&'a Expr> = Vec::new();
+    for &(_, expr) in &packages {
+        if !exprs.contains(&expr) {
+            exprs.push(expr);
+        }
+    }
+
+    let rank = |license: &License| {
+        preference.iter().position(|p| p == license).unwrap_or(preference.len())
+    };
+    let options: Vec<Vec<Vec<&'a License>>> = exprs.iter()
+        .map(|expr| {
+            let mut options: Vec<_> = expr.choices().into_iter()
+                .filter(|branch| branch.iter().all(|l| policy.allows(l)))
+                .filter(|branch| is_compatible(branch))
+                .collect();
+            options.sort_by_key(|branch| branch.iter().map(|l| rank(l)).sum::<usize>());
+            options
+        })
+        .collect();
+
+    let all: Vec<usize> = (0..exprs.len()).collect();
+    let chosen = match search(&options, &all, &rank) {
+        Some(chosen) => chosen,
+        None => {
+            let core = unsatisfiable_core(&options, &rank);
+            let core_exprs: Vec<&'a Expr> = core.iter().map(|&i| exprs[i]).collect();
+            return Err(Unsatisfiable::new(&packages, &core_exprs, policy));
+        },
+    };
+
+    let choices = packages.iter()
+        .map(|&(package, expr)| {
+            let i = exprs.iter().position(|&e| e == expr).unwrap_or(0);
+            Choice { package, expr, licenses: options[i][chosen[i]].clone() }
+        })
+        .collect();
+
+    Ok(Solution { choices })
+}
+
+/// Returns whether the licenses of `branch` can be combined with each other.
+fn is_compatible(branch: &[&License]) -> bool {
+    branch.iter().enumerate().all(|(i, a)| {
+        branch[(i + 1)..].iter().all(|b| compat::are_compatible(a, b))
+    })
+}
+
+/// The best choice found so far and the state of the current one.
+struct Search<'o, 'a, R> {
+    options: &'o [Vec<Vec<&'a License>>],
+    order: Vec<usize>,
+    rank: &'o R,
+    counts: HashMap<&'a License, usize>,
+    current: Vec<usize>,
+    best: Option<(usize, usize, Vec<usize>)>,
+}
+
+/// Returns the index of the chosen option for each expression, or `None` if
+/// the expressions in `subset` can't be satisfied together.
+fn search<R>(
+    options: &[Vec<Vec<&License>>],
+    subset: &[usize],
+    rank: &R,
+) -> Option<Vec<usize>>
+    where R: Fn(&License) -> usize
+{
+    // Most constrained first to fail early
+    let mut order = subset.to_vec();
+    order.sort_by_key(|&i| options[i].len());
+
+    let mut search = Search {
+        options,
+        order,
+        rank,
+        counts: HashMap::new(),
+        current: vec![0; options.len()],
+        best: None,
+    };
+    search.step(0);
+    search.best.map(|(_, _, chosen)| chosen)
+}
+
+impl<'a, R> Search<'_, 'a, R>
+    where R: Fn(&License) -> usize
+{
+    fn cost(&self) -> (usize, usize) {
+        let rank: usize = self.counts.keys().map(|l| (self.rank)(l)).sum();
+        (self.counts.len(), rank)
+    }
+
+    fn step(&mut self, depth: usize) {
+        if let Some((count, _, _)) = &self.best {
+            if self.counts.len() > *count {
+                return;
+            }
+        }
+
+        let i = match self.order.get(depth) {
+            Some(&i) => i,
+            None => {
+                let (count, rank) = self.cost();
+                let better = match &self.best {
+                    Some((c, r, _)) => (count, rank) < (*c, *r),
+                    None => true,
+                };
+                if better {
+                    self.best = Some((count, rank, self.current.clone()));
+                }
+                return;
+            },
+        };
+
+        for option in 0..self.options[i].len() {
+            let branch = &self.options[i][option];
+            let compatible = branch.iter().all(|l| {
+                self.counts.keys().all(|other| compat::are_compatible(l, other))
+            });
+            if !compatible {
+                continue;
+            }
+
+            for &l in branch {
+                *self.counts.entry(l).or_insert(0) += 1;
+            }
+            self.current[i] = option;
+
+            self.step(depth + 1);
+
+            for &l in branch {
+                if let Some(count) = self.counts.get_mut(l) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.counts.remove(l);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns a minimal set of expressions that can't be satisfied together.
+///
+/// Each expression is removed in turn and kept out if the rest remain
+/// unsatisfiable.
+fn unsatisfiable_core<R>(options: &[Vec<Vec<&License>>], rank: &R) -> Vec<usize>
+    where R: Fn(&License) -> usize
+{
+    let mut core: Vec<usize> = (0..options.len()).collect();
+    let mut i = 0;
+    while i < core.len() {
+        let removed = core.remove(i);
+        if search(options, &core, rank).is_some() {
+            core.insert(i, removed);
+            i += 1;
+        }
+    }
+    core
+}
+
+/// The licenses chosen for every package.
+///
+/// See [`solve`](fn.solve.html).
+#[derive(Clone, Debug)]
+pub struct Solution<'a> {
+    choices: Vec<Choice<'a>>,
+}
+
+impl<'a> Solution<'a> {
+    /// Returns the choice made for each package, in the order given.
+    #[inline]
+    pub fn choices(&self) -> &[Choice<'a>] {
+        &self.choices
+    }
+
+    /// Returns every distinct chosen license, in order of first appearance.
+    pub fn licenses(&self) -> Vec<&'a License> {
+        let mut licenses = Vec::new();
+        for &l in self.choices.iter().flat_map(|c| c.licenses.iter()) {
+            if !licenses.contains(&l) {
+                licenses.push(l);
+            }
+        }
+        licenses
+    }
+}
+
+impl fmt::Display for Solution<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for choice in &self.choices {
+            writeln!(f, "{}", choice)?;
+        }
+        Ok(())
+    }
+}
+
+/// The licenses chosen for a single package.
+#[derive(Clone, Debug)]
+pub struct Choice<'a> {
+    /// The package.
+    pub package: &'a str,
+    /// The license expression of the package.
+    pub expr: &'a Expr,
+    /// The licenses whose conditions must be followed: the chosen branch of an
+    /// `OR` expression, or every license otherwise.
+    pub licenses: Vec<&'a License>,
+}
+
+impl fmt::Display for Choice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.package)?;
+        for (i, l) in self.licenses.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" AND ")?;
+            }
+            l.fmt(f)?;
+        }
+        if self.expr.choices().len() > 1 {
+            write!(f, " (chosen from {})", self.expr)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error returned when no choice of licenses is valid.
+///
+/// This contains a minimal set of packages that can't be satisfied together,
+/// and the reasons why.
+#[derive(Clone, Debug)]
+pub struct Unsatisfiable<'a> {
+    /// A minimal set of packages whose expressions can't be satisfied together.
+    pub core: Vec<(&'a str, &'a Expr)>,
+    /// Why the packages in `core` can't be satisfied together.
+    pub reasons: Vec<Reason<'a>>,
+}
+
+/// Why a set of packages can't be satisfied.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reason<'a> {
+    /// A license is not allowed by the policy.
+    Denied(&'a License),
+    /// Two licenses can't be combined.
+    Incompatible(&'a License, &'a License),
+}
+
+impl fmt::Display for Reason<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reason::Denied(l) => {
+                write!(f, "{} is not allowed by the policy", l)
+            },
+            Reason::Incompatible(a, b) => {
+                write!(f, "{} is incompatible with {}", a, b)
+            },
+        }
+    }
+}
+
+impl<'a> Unsatisfiable<'a> {
+    fn new(
+        packages: &[(&'a str, &'a Expr)],
+        core_exprs: &[&'a Expr],
+        policy: &Policy,
+    ) -> Self {
+        let core = packages.iter()
+            .filter(|(_, expr)| core_exprs.contains(expr))
+            .cloned()
+            .collect();
+
+        let mut reasons = Vec::new();
+        let mut push = |reason| {
+            if !reasons.contains(&reason) {
+                reasons.push(reason);
+            }
+        };
+        for (i, a) in core_exprs.iter().enumerate() {
+            for l in a.licenses() {
+                if !policy.allows(l) {
+                    push(Reason::Denied(l));
+                }
+            }
+            for choice in a.choices() {
+                let allowed: Vec<&License> = choice.into_iter()
+                    .filter(|l| policy.allows(l))
+                    .collect();
+                for (j, l1) in allowed.iter().enumerate() {
+                    for l2 in &allowed[(j + 1)..] {
+                        if !compat::are_compatible(l1, l2) {
+                            push(Reason::Incompatible(l1, l2));
+                        }
+                    }
+                }
+            }
+            for b in &core_exprs[(i + 1)..] {
+                for l1 in a.licenses().filter(|l| policy.allows(l)) {
+                    for l2 in b.licenses().filter(|l| policy.allows(l)) {
+                        if !compat::are_compatible(l1, l2) {
+                            push(Reason::Incompatible(l1, l2));
+                        }
+                    }
+                }
+            }
+        }
+
+        Self { core, reasons }
+    }
+}
+
+impl fmt::Display for Unsatisfiable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "no valid choice of licenses for:")?;
+        for (package, expr) in &self.core {
+            writeln!(f, "  {}: {}", package, expr)?;
+        }
+        writeln!(f, "because:")?;
+        for reason in &self.reasons {
+            writeln!(f, "  {}", reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Unsatisfiable<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{spdx::{LicenseKind, LicenseRef}, SpdxLicense};
+
+    fn policy() -> Policy {
+        Policy {
+            allow_kinds: vec![
+                LicenseKind::Permissive,
+                LicenseKind::WeakCopyleft,
+                LicenseKind::StrongCopyleft,
+            ],
+            ..Policy::default()
+        }
+    }
+
+    #[test]
+    fn minimizes_licenses() {
+        let a = Expr::parse("MIT OR Apache-2.0").unwrap();
+        let b = Expr::parse("Apache-2.0 OR ISC").unwrap();
+        let c = Expr::parse("ISC OR MIT").unwrap();
+        let solution = solve(vec![("a", &a), ("b", &b), ("c", &c)], &policy(), &[]).unwrap();
+        assert_eq!(solution.licenses().len(), 2);
+
+        let a = Expr::parse("MIT OR ISC").unwrap();
+        let b = Expr::parse("ISC OR MIT").unwrap();
+        let packages = vec![("a", &a), ("b", &b)];
+
+        let solution = solve(packages.clone(), &policy(), &[]).unwrap();
+        assert_eq!(solution.licenses().len(), 1);
+
+        let preference = [SpdxLicense::Isc.into()];
+        let solution = solve(packages, &policy(), &preference).unwrap();
+        assert_eq!(solution.licenses(), [&License::from(SpdxLicense::Isc)]);
+    }
+
+    #[test]
+    fn avoids_incompatible() {
+        let a = Expr::parse("MIT OR Apache-2.0").unwrap();
+        let b = Expr::parse("Apache-2.0").unwrap();
+        let c = Expr::parse("GPL-2.0-only OR GPL-3.0-only").unwrap();
+
+        let solution = solve(vec![("a", &a), ("b", &b), ("c", &c)], &policy(), &[]).unwrap();
+        let licenses = solution.licenses();
+        assert!(licenses.contains(&&SpdxLicense::Gpl3Only.into()));
+        assert!(!licenses.contains(&&SpdxLicense::Gpl2Only.into()));
+    }
+
+    #[test]
+    fn unsatisfiable_core() {
+        let a = Expr::parse("MIT").unwrap();
+        let b = Expr::parse("Apache-2.0").unwrap();
+        let c = Expr::parse("GPL-2.0-only").unwrap();
+        let d = Expr::parse("AGPL-3.0-only OR ISC").unwrap();
+
+        let packages = vec![("a", &a), ("b", &b), ("c", &c), ("d", &d)];
+        let error = solve(packages, &policy(), &[]).unwrap_err();
+
+        let core: Vec<&str> = error.core.iter().map(|(p, _)| *p).collect();
+        assert_eq!(core, ["b", "c"]);
+        assert_eq!(error.reasons, [Reason::Incompatible(
+            &SpdxLicense::Apache2.into(),
+            &SpdxLicense::Gpl2Only.into(),
+        )]);
+    }
+
+    #[test]
+    fn allowed_refs() {
+        let a = Expr::parse("LicenseRef-Proprietary OR GPL-3.0-only").unwrap();
+        let b = Expr::parse("LicenseRef-Custom").unwrap();
+
+        let error = solve(vec![("b", &b)], &policy(), &[]).unwrap_err();
+        assert_eq!(error.reasons, [Reason::Denied(b.licenses().next().unwrap())]);
+
+        let policy = Policy {
+            allow_refs: vec![
+                LicenseRef::parse("LicenseRef-Proprietary").unwrap(),
+                LicenseRef::parse("LicenseRef-Custom").unwrap(),
+            ],
+            allow_kinds: vec![],
+            ..policy()
+        };
+        let solution = solve(vec![("a", &a), ("b", &b)], &policy, &[]).unwrap();
+        assert_eq!(solution.licenses(), [a.licenses().next().unwrap(), b.licenses().next().unwrap()]);
+    }
+
+    #[test]
+    fn incompatible_within_package() {
+        let a = Expr::parse("Apache-2.0 AND GPL-2.0-only").unwrap();
+        let error = solve(vec![("a", &a)], &policy(), &[]).unwrap_err();
+        assert_eq!(error.core.len(), 1);
+        assert_eq!(error.reasons, [Reason::Incompatible(
+            &SpdxLicense::Apache2.into(),
+            &SpdxLicense::Gpl2Only.into(),
+        )]);
+    }
+}
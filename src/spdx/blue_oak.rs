@@ -0,0 +1,160 @@
+use core::fmt;
+use super::{sparse_map, Map, SpdxLicense};
+
+/// The rating of a permissive license by the [Blue Oak
+/// Council](https://blueoakcouncil.org/list), in order of increasing quality.
+///
+/// This means that `rating >= BlueOakRating::Silver` checks for a rating of
+/// Silver or better.
+///
+/// See [`SpdxLicense::blue_oak_rating`](enum.SpdxLicense.html#method.blue_oak_rating).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum BlueOakRating {
+    /// Permissive, but with serious flaws, such as lacking a warranty
+    /// disclaimer or being unclear.
+    Lead,
+    /// Permissive, but with flaws, such as an advertising clause.
+    Bronze,
+    /// A good permissive license.
+    Silver,
+    /// A permissive license that also grants patent rights clearly.
+    Gold,
+    /// The license written by the Blue Oak Council as a model.
+    Model,
+}
+
+impl fmt::Display for BlueOakRating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            BlueOakRating::Lead => "Lead",
+            BlueOakRating::Bronze => "Bronze",
+            BlueOakRating::Silver => "Silver",
+            BlueOakRating::Gold => "Gold",
+            BlueOakRating::Model => "Model",
+        })
+    }
+}
+
+impl SpdxLicense {
+    // Based on the Blue Oak Council's list as of January 2024. Licenses not
+    // listed here are not rated.
+    pub(crate) const BLUE_OAK: Map<Option<BlueOakRating>> = {
+        use BlueOakRating::*;
+        use SpdxLicense as L;
+
+        sparse_map(None, &[
+            (L::BlueOak1, Some(Model)),
+
+            (L::Bsd2ClausePatent, Some(Gold)),
+
+            (L::Bsd0, Some(Silver)),
+            (L::Afl3, Some(Silver)),
+            (L::Apache2, Some(Silver)),
+            (L::Bsd2Clause, Some(Silver)),
+            (L::Bsd2ClauseFreeBsd, Some(Silver)),
+            (L::Bsd2ClauseNetBsd, Some(Silver)),
+            (L::Bsd3Clause, Some(Silver)),
+            (L::Bsl1, Some(Silver)),
+            (L::Ecl2, Some(Silver)),
+            (L::Efl2, Some(Silver)),
+            (L::Isc, Some(Silver)),
+            (L::Mit, Some(Silver)),
+            (L::Mit0, Some(Silver)),
+            (L::MsPl, Some(Silver)),
+            (L::Ncsa, Some(Silver)),
+            (L::PostgreSql, Some(Silver)),
+            (L::UnicodeDfs2015, Some(Silver)),
+            (L::UnicodeDfs2016, Some(Silver)),
+            (L::Upl1, Some(Silver)),
+            (L::W3C20150513, Some(Silver)),
+            (L::Xnet, Some(Silver)),
+            (L::Zlib, Some(Silver)),
+
+            (L::Afl1_1, Some(Bronze)),
+            (L::Afl1_2, Some(Bronze)),
+            (L::Afl2, Some(Bronze)),
+            (L::Afl2_1, Some(Bronze)),
+            (L::Apache1_1, Some(Bronze)),
+            (L::Bsd1Clause, Some(Bronze)),
+            (L::Bsd3ClauseAttribution, Some(Bronze)),
+            (L::Bsd3ClauseClear, Some(Bronze)),
+            (L::Bsd3ClauseLbnl, Some(Bronze)),
+            (L::Bsd3ClauseOpenMpi, Some(Bronze)),
+            (L::Bsd4Clause, Some(Bronze)),
+            (L::Bsd4ClauseUc, Some(Bronze)),
+            (L::BsdSourceCode, Some(Bronze)),
+            (L::CcBy4, Some(Bronze)),
+            (L::CecillB, Some(Bronze)),
+            (L::Curl, Some(Bronze)),
+            (L::Hpnd, Some(Bronze)),
+            (L::HpndSellVariant, Some(Bronze)),
+            (L::Icu, Some(Bronze)),
+            (L::Ijg, Some(Bronze)),
+            (L::Libpng, Some(Bronze)),
+            (L::Libpng2, Some(Bronze)),
+            (L::Libtiff, Some(Bronze)),
+            (L::MitCmu, Some(Bronze)),
+            (L::MitEnna, Some(Bronze)),
+            (L::MitFeh, Some(Bronze)),
+            (L::Mitnfa, Some(Bronze)),
+            (L::MulanPsl1, Some(Bronze)),
+            (L::Ntp, Some(Bronze)),
+            (L::OpenSsl, Some(Bronze)),
+            (L::Php3, Some(Bronze)),
+            (L::Php3_01, Some(Bronze)),
+            (L::Python2, Some(Bronze)),
+            (L::W3C, Some(Bronze)),
+            (L::W3C19980720, Some(Bronze)),
+            (L::X11, Some(Bronze)),
+            (L::ZlibAcknowledgement, Some(Bronze)),
+            (L::Zpl2, Some(Bronze)),
+            (L::Zpl2_1, Some(Bronze)),
+
+            (L::AntlrPd, Some(Lead)),
+            (L::Apache1, Some(Lead)),
+            (L::Beerware, Some(Lead)),
+            (L::Blessing, Some(Lead)),
+            (L::CC01, Some(Lead)),
+            (L::CcBy1, Some(Lead)),
+            (L::CcBy2, Some(Lead)),
+            (L::CcBy2_5, Some(Lead)),
+            (L::CcBy3, Some(Lead)),
+            (L::CcPddc, Some(Lead)),
+            (L::Fsfap, Some(Lead)),
+            (L::Fsful, Some(Lead)),
+            (L::Fsfullr, Some(Lead)),
+            (L::Json, Some(Lead)),
+            (L::MitAdvertising, Some(Lead)),
+            (L::SaxPd, Some(Lead)),
+            (L::Unlicense, Some(Lead)),
+            (L::Wtfpl, Some(Lead)),
+        ])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spdx::LicenseKind;
+
+    #[test]
+    fn rates_permissive() {
+        // Data and documentation licenses are not rated
+        let unrated = [SpdxLicense::CdlaPermissive1, SpdxLicense::OdcBy1, SpdxLicense::Pddl1];
+
+        for license in SpdxLicense::all() {
+            let permissive = matches!(
+                license.kind(),
+                LicenseKind::PublicDomain | LicenseKind::Permissive,
+            );
+            if permissive && !unrated.contains(&license) {
+                assert!(license.blue_oak_rating().is_some(), "{}", license);
+            }
+            if license.blue_oak_rating().is_some() && license != SpdxLicense::Json {
+                assert!(permissive, "{}", license);
+            }
+        }
+    }
+}
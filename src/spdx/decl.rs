@@ -28,11 +28,13 @@ macro_rules! spdx_license {
             // `COUNT` which is declared with the other public items.
             pub(crate) const _COUNT: usize = count!($($l)+);
 
-            // TODO: URLs for where each license corpus can be found
             pub(crate) const ID:    Map<&'static str> = [$($id,)+];
             pub(crate) const NAME:  Map<&'static str> = [$($name,)+];
             pub(crate) const LIBRE: Map<bool> = [$($libre,)+];
             pub(crate) const OSI:   Map<bool> = [$($osi,)+];
+            pub(crate) const URL:   Map<&'static str> = [
+                $(concat!("https://spdx.org/licenses/", $id, ".html"),)+
+            ];
 
             // Creates static a hash map if `phf` is enabled, else resorts to a
             // good ol' `match` statement :D
@@ -59,7 +61,7 @@ macro_rules! spdx_license {
 }
 
 spdx_license! {
-    Bsd0 = "BSD 0", "BSD Zero Clause License", false, true;
+    Bsd0 = "0BSD", "BSD Zero Clause License", false, true;
     Aal = "AAL", "Attribution Assurance License", false, true;
     Abstyles = "Abstyles", "Abstyles License", false, false;
     Adobe2006 = "Adobe-2006", "Adobe Systems Incorporated Source Code License Agreement", false, false;
@@ -362,8 +364,8 @@ spdx_license! {
     Osl3 = "OSL-3.0", "Open Software License 3.0", true, true;
     Parity6 = "Parity-6.0.0", "The Parity Public License 6.0.0", false, false;
     Pddl1 = "PDDL-1.0", "ODC Public Domain Dedication & License 1.0", false, false;
-    Php3 = "-PHP 3.0", "PHP License v3.0", false, true;
-    Php3_01 = "-PHP 3.01", "PHP License v3.01", true, false;
+    Php3 = "PHP-3.0", "PHP License v3.0", false, true;
+    Php3_01 = "PHP-3.01", "PHP License v3.01", true, false;
     Plexus = "Plexus", "Plexus Classworlds License", false, false;
     PostgreSql = "PostgreSQL", "PostgreSQL License", false, true;
     Psfrag = "psfrag", "psfrag License", false, false;
@@ -447,3 +449,33 @@ spdx_license! {
     Zpl2 = "ZPL-2.0", "Zope Public License 2.0", true, true;
     Zpl2_1 = "ZPL-2.1", "Zope Public License 2.1", true, false;
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use super::*;
+
+    #[test]
+    fn ids_are_valid() {
+        let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '+';
+        for license in SpdxLicense::all() {
+            let id = license.id();
+            assert!(id.chars().all(valid) && !id.starts_with('-'), "{:?}", id);
+            assert_eq!(SpdxLicense::_from_id(id), Some(license));
+        }
+    }
+
+    #[test]
+    fn parse_corrected_ids() {
+        for &(id, license) in &[
+            ("0BSD", SpdxLicense::Bsd0),
+            ("PHP-3.0", SpdxLicense::Php3),
+            ("PHP-3.01", SpdxLicense::Php3_01),
+        ] {
+            assert_eq!(SpdxLicense::parse(id), Ok(license));
+            assert_eq!(license.to_string(), id);
+        }
+        assert!(SpdxLicense::parse("BSD 0").is_err());
+        assert!(SpdxLicense::parse("-PHP 3.01").is_err());
+    }
+}
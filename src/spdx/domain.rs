@@ -0,0 +1,123 @@
+use core::fmt;
+use super::{sparse_map, Map, SpdxLicense};
+
+/// The subject matter that a license is intended for.
+///
+/// See [`SpdxLicense::domain`](enum.SpdxLicense.html#method.domain).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Domain {
+    /// Computer programs.
+    Software,
+    /// Manuals and other documentation, such as with the GFDL.
+    Documentation,
+    /// Databases and datasets, such as with the ODbL.
+    Data,
+    /// Typefaces, such as with the OFL.
+    Font,
+    /// Hardware designs, such as with the CERN OHL.
+    Hardware,
+    /// Creative works, such as text, images and music.
+    Content,
+}
+
+impl fmt::Display for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Domain::Software => "software",
+            Domain::Documentation => "documentation",
+            Domain::Data => "data",
+            Domain::Font => "font",
+            Domain::Hardware => "hardware",
+            Domain::Content => "content",
+        })
+    }
+}
+
+impl SpdxLicense {
+    // Licenses not listed here are for `Software`. This includes CC0, which is
+    // intended for any kind of work.
+    pub(crate) const DOMAIN: Map<Domain> = {
+        use Domain::*;
+        use SpdxLicense as L;
+
+        sparse_map(Software, &[
+            (L::Gfdl1_1Only, Documentation),
+            (L::Gfdl1_1OrLater, Documentation),
+            (L::Gfdl1_2Only, Documentation),
+            (L::Gfdl1_2OrLater, Documentation),
+            (L::Gfdl1_3Only, Documentation),
+            (L::Gfdl1_3OrLater, Documentation),
+            (L::Opl1, Documentation),
+
+            (L::CdlaPermissive1, Data),
+            (L::CdlaSharing1, Data),
+            (L::Etalab2, Data),
+            (L::Nlod1, Data),
+            (L::ODbL1, Data),
+            (L::OdcBy1, Data),
+            (L::OglCanada2, Data),
+            (L::OglUk1, Data),
+            (L::OglUk2, Data),
+            (L::OglUk3, Data),
+            (L::Pddl1, Data),
+            (L::UnicodeTou, Data),
+
+            (L::Ofl1, Font),
+            (L::Ofl1_1, Font),
+
+            (L::CernOhl1_1, Hardware),
+            (L::CernOhl1_2, Hardware),
+            (L::Shl0_5, Hardware),
+            (L::Shl0_51, Hardware),
+            (L::TaprOhl1, Hardware),
+
+            (L::CcBy1, Content),
+            (L::CcBy2, Content),
+            (L::CcBy2_5, Content),
+            (L::CcBy3, Content),
+            (L::CcBy4, Content),
+            (L::CcByNc1, Content),
+            (L::CcByNc2, Content),
+            (L::CcByNc2_5, Content),
+            (L::CcByNc3, Content),
+            (L::CcByNc4, Content),
+            (L::CcByNcNd1, Content),
+            (L::CcByNcNd2, Content),
+            (L::CcByNcNd2_5, Content),
+            (L::CcByNcNd3, Content),
+            (L::CcByNcNd4, Content),
+            (L::CcByNcSa1, Content),
+            (L::CcByNcSa2, Content),
+            (L::CcByNcSa2_5, Content),
+            (L::CcByNcSa3, Content),
+            (L::CcByNcSa4, Content),
+            (L::CcByNd1, Content),
+            (L::CcByNd2, Content),
+            (L::CcByNd2_5, Content),
+            (L::CcByNd3, Content),
+            (L::CcByNd4, Content),
+            (L::CcBySa1, Content),
+            (L::CcBySa2, Content),
+            (L::CcBySa2_5, Content),
+            (L::CcBySa3, Content),
+            (L::CcBySa4, Content),
+            (L::CcPddc, Content),
+        ])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creative_commons_is_content() {
+        for license in SpdxLicense::all() {
+            if license.is_creative_commons() && license != SpdxLicense::CC01 {
+                assert_eq!(license.domain(), Domain::Content, "{}", license);
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use core::{
+    convert::{TryFrom, TryInto},
+    fmt,
+};
+use crate::{ParseError, SpdxLicense};
+
+macro_rules! spdx_exception {
+    ($($e:ident = $id:literal, $name:literal;)+) => {
+        /// A license exception listed [here](https://spdx.org/licenses/exceptions-index.html),
+        /// which grants additional permissions when added to a license via
+        /// `WITH`.
+        ///
+        /// This list is based on version 3.7 (2019-10-22).
+        ///
+        /// **SemVer Compatibility:** this exception is intended to have the
+        /// semantics of `#[non_exhaustive]`. This library reserves the right to
+        /// add, reorganize, or otherwise adjust variants. These changes are
+        /// allowed between otherwise API-compatible versions.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        // TODO: Add `#[non_exhaustive]` when stable
+        pub enum SpdxException {
+            $(
+                #[doc = $name]
+                #[doc = "— `"]
+                #[doc = $id]
+                #[doc = "`."]
+                $e,
+            )+
+        }
+
+        impl SpdxException {
+            /// The current number of SPDX exceptions.
+            ///
+            /// **SemVer Compatibility:** This number is allowed to change
+            /// between otherwise API-compatible versions.
+            pub const COUNT: usize = count!($($e)+);
+
+            const ID:   [&'static str; Self::COUNT] = [$($id,)+];
+            const NAME: [&'static str; Self::COUNT] = [$($name,)+];
+
+            fn from_id(id: &str) -> Option<Self> {
+                match id {
+                    $($id => Some(Self::$e),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+spdx_exception! {
+    Exception389 = "389-exception", "389 Directory Server Exception";
+    Autoconf2 = "Autoconf-exception-2.0", "Autoconf exception 2.0";
+    Autoconf3 = "Autoconf-exception-3.0", "Autoconf exception 3.0";
+    Bison2_2 = "Bison-exception-2.2", "Bison exception 2.2";
+    Bootloader = "Bootloader-exception", "Bootloader Distribution Exception";
+    Classpath2 = "Classpath-exception-2.0", "Classpath exception 2.0";
+    Clisp2 = "CLISP-exception-2.0", "CLISP exception 2.0";
+    DigiRuleFoss = "DigiRule-FOSS-exception", "DigiRule FOSS License Exception";
+    ECos2 = "eCos-exception-2.0", "eCos exception 2.0";
+    FawkesRuntime = "Fawkes-Runtime-exception", "Fawkes Runtime Exception";
+    Fltk = "FLTK-exception", "FLTK exception";
+    Font2 = "Font-exception-2.0", "Font exception 2.0";
+    FreeRtos2 = "freertos-exception-2.0", "FreeRTOS Exception 2.0";
+    Gcc2 = "GCC-exception-2.0", "GCC Runtime Library exception 2.0";
+    Gcc3_1 = "GCC-exception-3.1", "GCC Runtime Library exception 3.1";
+    GnuJavaMail = "gnu-javamail-exception", "GNU JavaMail exception";
+    GplCc1 = "GPL-CC-1.0", "GPL Cooperation Commitment 1.0";
+    I2pGplJava = "i2p-gpl-java-exception", "i2p GPL+Java Exception";
+    Libtool = "Libtool-exception", "Libtool Exception";
+    LinuxSyscallNote = "Linux-syscall-note", "Linux Syscall Note";
+    Llvm = "LLVM-exception", "LLVM Exception";
+    Lzma = "LZMA-exception", "LZMA exception";
+    Mif = "mif-exception", "Macros and Inline Functions Exception";
+    NokiaQt1_1 = "Nokia-Qt-exception-1.1", "Nokia Qt LGPL exception 1.1";
+    OcamlLgplLinking = "OCaml-LGPL-linking-exception", "OCaml LGPL Linking Exception";
+    Occt1 = "OCCT-exception-1.0", "Open CASCADE Exception 1.0";
+    OpenJdkAssembly1 = "OpenJDK-assembly-exception-1.0", "OpenJDK Assembly exception 1.0";
+    OpenVpnOpenSsl = "openvpn-openssl-exception", "OpenVPN OpenSSL Exception";
+    PsOrPdfFont20170817 = "PS-or-PDF-font-exception-20170817", "PS/PDF font exception (2017-08-17)";
+    QtGpl1 = "Qt-GPL-exception-1.0", "Qt GPL exception 1.0";
+    QtLgpl1_1 = "Qt-LGPL-exception-1.1", "Qt LGPL exception 1.1";
+    Qwt1 = "Qwt-exception-1.0", "Qwt exception 1.0";
+    Swift = "Swift-exception", "Swift Exception";
+    UBoot2 = "u-boot-exception-2.0", "U-Boot exception 2.0";
+    UniversalFoss1 = "Universal-FOSS-exception-1.0", "Universal FOSS Exception, Version 1.0";
+    WxWindows3_1 = "WxWindows-exception-3.1", "WxWindows Library Exception 3.1";
+}
+
+impl<'a> TryFrom<&'a str> for SpdxException {
+    type Error = ParseError<'a>;
+
+    #[inline]
+    fn try_from(id: &'a str) -> Result<Self, Self::Error> {
+        if id.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        Self::from_id(id).ok_or(ParseError::UnknownExceptionId(id))
+    }
+}
+
+impl fmt::Display for SpdxException {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.id().fmt(f)
+    }
+}
+
+impl SpdxException {
+    /// Returns an iterator over all exceptions.
+    ///
+    /// ```
+    /// use linfo::spdx::SpdxException;
+    ///
+    /// assert_eq!(SpdxException::all().len(), SpdxException::COUNT);
+    /// ```
+    #[inline]
+    pub fn all() -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator {
+        (0..(Self::COUNT as u8)).map(|e| unsafe {
+            // SAFETY: Transmuting a `u8` up to `COUNT` is safe because the
+            // range contains all instantiable values.
+            core::mem::transmute(e)
+        })
+    }
+
+    /// Attempts to parse `input` and returns a [`ParseError`] on error.
+    ///
+    /// [`ParseError`]: ../enum.ParseError.html
+    #[inline]
+    pub fn parse<'a, I>(input: I) -> Result<Self, ParseError<'a>>
+        where I: TryInto<Self, Error = ParseError<'a>>
+    {
+        input.try_into()
+    }
+
+    /// Returns the string identifier of this exception.
+    #[inline]
+    pub const fn id(self) -> &'static str {
+        Self::ID[self as usize]
+    }
+
+    /// Returns the full name of this exception.
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        Self::NAME[self as usize]
+    }
+
+    /// Returns the licenses that this exception is designed to modify.
+    ///
+    /// ```
+    /// use linfo::spdx::{SpdxException, SpdxLicense};
+    ///
+    /// let classpath = SpdxException::Classpath2;
+    /// assert!(classpath.applies_to().contains(&SpdxLicense::Gpl2Only));
+    /// assert!(!classpath.applies_to().contains(&SpdxLicense::Mit));
+    /// ```
+    pub const fn applies_to(self) -> &'static [SpdxLicense] {
+        use SpdxException::*;
+        use SpdxLicense as L;
+
+        const GPL: &[SpdxLicense] = &[
+            L::Gpl1Only, L::Gpl1OrLater, L::Gpl2Only, L::Gpl2OrLater,
+            L::Gpl3Only, L::Gpl3OrLater,
+        ];
+        const LGPL: &[SpdxLicense] = &[
+            L::Lgpl2Only, L::Lgpl2OrLater, L::Lgpl2_1Only, L::Lgpl2_1OrLater,
+            L::Lgpl3Only, L::Lgpl3OrLater,
+        ];
+        const GPL_LGPL: &[SpdxLicense] = &[
+            L::Gpl1Only, L::Gpl1OrLater, L::Gpl2Only, L::Gpl2OrLater,
+            L::Gpl3Only, L::Gpl3OrLater,
+            L::Lgpl2Only, L::Lgpl2OrLater, L::Lgpl2_1Only, L::Lgpl2_1OrLater,
+            L::Lgpl3Only, L::Lgpl3OrLater,
+        ];
+        const GPL_AGPL: &[SpdxLicense] = &[
+            L::Gpl1Only, L::Gpl1OrLater, L::Gpl2Only, L::Gpl2OrLater,
+            L::Gpl3Only, L::Gpl3OrLater,
+            L::Agpl3Only, L::Agpl3OrLater,
+        ];
+
+        match self {
+            Exception389 | Autoconf2 | Autoconf3 | Bison2_2 | Bootloader |
+            Classpath2 | Clisp2 | DigiRuleFoss | ECos2 | FawkesRuntime | Font2 |
+            FreeRtos2 | Gcc2 | Gcc3_1 | GnuJavaMail | I2pGplJava | Libtool |
+            LinuxSyscallNote | Mif | OpenJdkAssembly1 | OpenVpnOpenSsl |
+            QtGpl1 | UBoot2 | UniversalFoss1 => GPL,
+
+            Fltk | NokiaQt1_1 | OcamlLgplLinking | Occt1 | QtLgpl1_1 | Qwt1 |
+            WxWindows3_1 => LGPL,
+
+            GplCc1 | Lzma => GPL_LGPL,
+            PsOrPdfFont20170817 => GPL_AGPL,
+            Llvm | Swift => &[L::Apache2],
+        }
+    }
+
+    /// Returns whether this exception permits linking the licensed work with
+    /// works under other terms, such as a runtime library or generated code
+    /// being used by any program.
+    ///
+    /// ```
+    /// use linfo::spdx::SpdxException;
+    ///
+    /// assert!(SpdxException::Classpath2.grants_linking());
+    /// assert!(!SpdxException::Font2.grants_linking());
+    /// ```
+    pub const fn grants_linking(self) -> bool {
+        use SpdxException::*;
+
+        matches!(
+            self,
+            Autoconf2 | Autoconf3 | Bison2_2 | Classpath2 | Fltk | Gcc2 |
+            Gcc3_1 | Libtool | Llvm | OcamlLgplLinking | WxWindows3_1
+        )
+    }
+
+    /// Returns whether this exception's identifier is deprecated by SPDX.
+    #[inline]
+    pub const fn is_deprecated(self) -> bool {
+        matches!(self, SpdxException::NokiaQt1_1)
+    }
+}
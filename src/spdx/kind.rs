@@ -0,0 +1,245 @@
+use core::fmt;
+use super::{sparse_map, Map, SpdxLicense};
+
+/// The broad category of a license, in order of increasing restrictions on
+/// redistribution.
+///
+/// See [`SpdxLicense::kind`](enum.SpdxLicense.html#method.kind).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum LicenseKind {
+    /// Dedicates the work to the public domain or waives all rights.
+    PublicDomain,
+    /// Places minimal requirements on redistribution, such as keeping the
+    /// copyright notice.
+    Permissive,
+    /// Requires modifications to the licensed files themselves to be shared
+    /// under the same terms.
+    WeakCopyleft,
+    /// Requires derivative works as a whole to be shared under the same terms.
+    StrongCopyleft,
+    /// Like [`StrongCopyleft`](#variant.StrongCopyleft), but also applies when
+    /// the work is only used over a network.
+    NetworkCopyleft,
+    /// Restricts use or modification, such as for non-commercial purposes only.
+    Restricted,
+    /// Not yet categorized. This is ordered last since it must be assumed to be
+    /// as restrictive as any other kind.
+    Unknown,
+}
+
+impl fmt::Display for LicenseKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            LicenseKind::PublicDomain => "public domain",
+            LicenseKind::Permissive => "permissive",
+            LicenseKind::WeakCopyleft => "weak copyleft",
+            LicenseKind::StrongCopyleft => "strong copyleft",
+            LicenseKind::NetworkCopyleft => "network copyleft",
+            LicenseKind::Restricted => "restricted",
+            LicenseKind::Unknown => "unknown",
+        })
+    }
+}
+
+impl LicenseKind {
+    /// Returns whether this is any kind of copyleft.
+    #[inline]
+    pub const fn is_copyleft(self) -> bool {
+        matches!(
+            self,
+            LicenseKind::WeakCopyleft |
+            LicenseKind::StrongCopyleft |
+            LicenseKind::NetworkCopyleft
+        )
+    }
+}
+
+impl SpdxLicense {
+    // Licenses not listed here are `Unknown`.
+    pub(crate) const KIND: Map<LicenseKind> = {
+        use LicenseKind::*;
+        use SpdxLicense as L;
+
+        sparse_map(Unknown, &[
+            (L::AntlrPd, PublicDomain),
+            (L::CC01, PublicDomain),
+            (L::CcPddc, PublicDomain),
+            (L::Pddl1, PublicDomain),
+            (L::SaxPd, PublicDomain),
+            (L::Unlicense, PublicDomain),
+
+            (L::Bsd0, Permissive),
+            (L::Afl1_1, Permissive),
+            (L::Afl1_2, Permissive),
+            (L::Afl2, Permissive),
+            (L::Afl2_1, Permissive),
+            (L::Afl3, Permissive),
+            (L::Apache1, Permissive),
+            (L::Apache1_1, Permissive),
+            (L::Apache2, Permissive),
+            (L::Beerware, Permissive),
+            (L::Blessing, Permissive),
+            (L::BlueOak1, Permissive),
+            (L::Bsd1Clause, Permissive),
+            (L::Bsd2Clause, Permissive),
+            (L::Bsd2ClauseFreeBsd, Permissive),
+            (L::Bsd2ClauseNetBsd, Permissive),
+            (L::Bsd2ClausePatent, Permissive),
+            (L::Bsd3Clause, Permissive),
+            (L::Bsd3ClauseAttribution, Permissive),
+            (L::Bsd3ClauseClear, Permissive),
+            (L::Bsd3ClauseLbnl, Permissive),
+            (L::Bsd3ClauseOpenMpi, Permissive),
+            (L::Bsd4Clause, Permissive),
+            (L::Bsd4ClauseUc, Permissive),
+            (L::BsdSourceCode, Permissive),
+            (L::Bsl1, Permissive),
+            (L::CcBy1, Permissive),
+            (L::CcBy2, Permissive),
+            (L::CcBy2_5, Permissive),
+            (L::CcBy3, Permissive),
+            (L::CcBy4, Permissive),
+            (L::CdlaPermissive1, Permissive),
+            (L::CecillB, Permissive),
+            (L::Curl, Permissive),
+            (L::Ecl2, Permissive),
+            (L::Efl2, Permissive),
+            (L::Fsfap, Permissive),
+            (L::Fsful, Permissive),
+            (L::Fsfullr, Permissive),
+            (L::Hpnd, Permissive),
+            (L::HpndSellVariant, Permissive),
+            (L::Icu, Permissive),
+            (L::Ijg, Permissive),
+            (L::Isc, Permissive),
+            (L::Libpng, Permissive),
+            (L::Libpng2, Permissive),
+            (L::Libtiff, Permissive),
+            (L::Mit, Permissive),
+            (L::Mit0, Permissive),
+            (L::MitAdvertising, Permissive),
+            (L::MitCmu, Permissive),
+            (L::MitEnna, Permissive),
+            (L::MitFeh, Permissive),
+            (L::Mitnfa, Permissive),
+            (L::MsPl, Permissive),
+            (L::MulanPsl1, Permissive),
+            (L::Ncsa, Permissive),
+            (L::Ntp, Permissive),
+            (L::OdcBy1, Permissive),
+            (L::OpenSsl, Permissive),
+            (L::Php3, Permissive),
+            (L::Php3_01, Permissive),
+            (L::PostgreSql, Permissive),
+            (L::Python2, Permissive),
+            (L::UnicodeDfs2015, Permissive),
+            (L::UnicodeDfs2016, Permissive),
+            (L::Upl1, Permissive),
+            (L::W3C, Permissive),
+            (L::W3C19980720, Permissive),
+            (L::W3C20150513, Permissive),
+            (L::Wtfpl, Permissive),
+            (L::X11, Permissive),
+            (L::Xnet, Permissive),
+            (L::Zlib, Permissive),
+            (L::ZlibAcknowledgement, Permissive),
+            (L::Zpl2, Permissive),
+            (L::Zpl2_1, Permissive),
+
+            (L::Apsl2, WeakCopyleft),
+            (L::Cddl1, WeakCopyleft),
+            (L::Cddl1_1, WeakCopyleft),
+            (L::CecillC, WeakCopyleft),
+            (L::Cpal1, WeakCopyleft),
+            (L::Cpl1, WeakCopyleft),
+            (L::Epl1, WeakCopyleft),
+            (L::Epl2, WeakCopyleft),
+            (L::ErlPl1_1, WeakCopyleft),
+            (L::Ipl1, WeakCopyleft),
+            (L::Lgpl2Only, WeakCopyleft),
+            (L::Lgpl2OrLater, WeakCopyleft),
+            (L::Lgpl2_1Only, WeakCopyleft),
+            (L::Lgpl2_1OrLater, WeakCopyleft),
+            (L::Lgpl3Only, WeakCopyleft),
+            (L::Lgpl3OrLater, WeakCopyleft),
+            (L::Lgpllr, WeakCopyleft),
+            (L::LiLiQR1_1, WeakCopyleft),
+            (L::Mpl1, WeakCopyleft),
+            (L::Mpl1_1, WeakCopyleft),
+            (L::Mpl2, WeakCopyleft),
+            (L::Mpl2NoCopyleftException, WeakCopyleft),
+            (L::MsRl, WeakCopyleft),
+            (L::Npl1, WeakCopyleft),
+            (L::Npl1_1, WeakCopyleft),
+            (L::Ofl1, WeakCopyleft),
+            (L::Ofl1_1, WeakCopyleft),
+            (L::Spl1, WeakCopyleft),
+
+            (L::CcBySa1, StrongCopyleft),
+            (L::CcBySa2, StrongCopyleft),
+            (L::CcBySa2_5, StrongCopyleft),
+            (L::CcBySa3, StrongCopyleft),
+            (L::CcBySa4, StrongCopyleft),
+            (L::Cecill1, StrongCopyleft),
+            (L::Cecill1_1, StrongCopyleft),
+            (L::Cecill2, StrongCopyleft),
+            (L::Cecill2_1, StrongCopyleft),
+            (L::CopyleftNext0_3, StrongCopyleft),
+            (L::CopyleftNext0_3_1, StrongCopyleft),
+            (L::Eupl1, StrongCopyleft),
+            (L::Eupl1_1, StrongCopyleft),
+            (L::Eupl1_2, StrongCopyleft),
+            (L::Gfdl1_1Only, StrongCopyleft),
+            (L::Gfdl1_1OrLater, StrongCopyleft),
+            (L::Gfdl1_2Only, StrongCopyleft),
+            (L::Gfdl1_2OrLater, StrongCopyleft),
+            (L::Gfdl1_3Only, StrongCopyleft),
+            (L::Gfdl1_3OrLater, StrongCopyleft),
+            (L::Gpl1Only, StrongCopyleft),
+            (L::Gpl1OrLater, StrongCopyleft),
+            (L::Gpl2Only, StrongCopyleft),
+            (L::Gpl2OrLater, StrongCopyleft),
+            (L::Gpl3Only, StrongCopyleft),
+            (L::Gpl3OrLater, StrongCopyleft),
+            (L::LiLiQRplus1_1, StrongCopyleft),
+            (L::ODbL1, StrongCopyleft),
+            (L::Osl1, StrongCopyleft),
+            (L::Osl1_1, StrongCopyleft),
+            (L::Sleepycat, StrongCopyleft),
+
+            (L::Agpl1Only, NetworkCopyleft),
+            (L::Agpl1OrLater, NetworkCopyleft),
+            (L::Agpl3Only, NetworkCopyleft),
+            (L::Agpl3OrLater, NetworkCopyleft),
+            (L::Osl2, NetworkCopyleft),
+            (L::Osl2_1, NetworkCopyleft),
+            (L::Osl3, NetworkCopyleft),
+            (L::Rpl1_1, NetworkCopyleft),
+            (L::Rpl1_5, NetworkCopyleft),
+            (L::Sspl1, NetworkCopyleft),
+
+            (L::CcByNc1, Restricted),
+            (L::CcByNc2, Restricted),
+            (L::CcByNc2_5, Restricted),
+            (L::CcByNc3, Restricted),
+            (L::CcByNc4, Restricted),
+            (L::CcByNcNd1, Restricted),
+            (L::CcByNcNd2, Restricted),
+            (L::CcByNcNd2_5, Restricted),
+            (L::CcByNcNd3, Restricted),
+            (L::CcByNcNd4, Restricted),
+            (L::CcByNcSa1, Restricted),
+            (L::CcByNcSa2, Restricted),
+            (L::CcByNcSa2_5, Restricted),
+            (L::CcByNcSa3, Restricted),
+            (L::CcByNcSa4, Restricted),
+            (L::CcByNd1, Restricted),
+            (L::CcByNd2, Restricted),
+            (L::CcByNd2_5, Restricted),
+            (L::CcByNd3, Restricted),
+            (L::CcByNd4, Restricted),
+        ])
+    };
+}
@@ -0,0 +1,152 @@
+use core::fmt;
+use alloc::{borrow::ToOwned, format, string::String};
+use crate::ParseError;
+
+/// A user-defined license, identified by `LicenseRef-` and optionally prefixed
+/// by `DocumentRef-...:` when defined in another SPDX document.
+///
+/// ```
+/// use linfo::spdx::LicenseRef;
+///
+/// let l = LicenseRef::parse("DocumentRef-spdx-tool-1.2:LicenseRef-MIT-Style-2").unwrap();
+/// assert_eq!(l.document_ref(), Some("spdx-tool-1.2"));
+/// assert_eq!(l.license_ref(), "MIT-Style-2");
+///
+/// assert!(LicenseRef::parse("MIT").is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LicenseRef {
+    id: String,
+    // Byte index of `LicenseRef-` within `id`.
+    start: usize,
+}
+
+impl fmt::Display for LicenseRef {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.id)
+    }
+}
+
+impl LicenseRef {
+    const DOCUMENT: &'static str = "DocumentRef-";
+    const LICENSE: &'static str = "LicenseRef-";
+
+    /// Attempts to parse `input` as `[DocumentRef-<id>:]LicenseRef-<id>`.
+    pub fn parse(input: &str) -> Result<Self, ParseError<'_>> {
+        let s = input.trim();
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let unknown = || ParseError::UnknownLicenseId(s);
+
+        let (document, license) = match s.find(':') {
+            Some(colon) => (Some(&s[..colon]), &s[(colon + 1)..]),
+            None => (None, s),
+        };
+        if let Some(document) = document {
+            match document.strip_prefix(Self::DOCUMENT) {
+                Some(id) if is_idstring(id) => {},
+                _ => return Err(unknown()),
+            }
+        }
+        match license.strip_prefix(Self::LICENSE) {
+            Some(id) if is_idstring(id) => {},
+            _ => return Err(unknown()),
+        }
+
+        Ok(Self { id: s.to_owned(), start: s.len() - license.len() })
+    }
+
+    /// Creates a reference to a license defined in the same document, with
+    /// invalid characters in `id` replaced by `-`.
+    ///
+    /// ```
+    /// use linfo::spdx::LicenseRef;
+    ///
+    /// assert_eq!(LicenseRef::new("My License 1.0").to_string(), "LicenseRef-My-License-1.0");
+    /// ```
+    pub fn new(id: &str) -> Self {
+        let id: String = id.chars()
+            .map(|c| if is_idchar(c) { c } else { '-' })
+            .collect();
+        let id = if id.is_empty() { "-".to_owned() } else { id };
+        Self { id: format!("{}{}", Self::LICENSE, id), start: 0 }
+    }
+
+    /// Returns the full identifier, such as `LicenseRef-Custom`.
+    #[inline]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the identifier of the document that defines this license, after
+    /// `DocumentRef-`.
+    #[inline]
+    pub fn document_ref(&self) -> Option<&str> {
+        if self.start == 0 {
+            None
+        } else {
+            Some(&self.id[Self::DOCUMENT.len()..(self.start - 1)])
+        }
+    }
+
+    /// Returns the identifier of this license, after `LicenseRef-`.
+    #[inline]
+    pub fn license_ref(&self) -> &str {
+        &self.id[(self.start + Self::LICENSE.len())..]
+    }
+}
+
+fn is_idchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '.'
+}
+
+fn is_idstring(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(is_idchar)
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use core::fmt;
+    use serde::{
+        ser::{Serialize, Serializer},
+        de::{self, Deserialize, Deserializer, Visitor},
+    };
+    use super::LicenseRef;
+
+    struct LicenseRefVisitor;
+
+    impl<'de> Visitor<'de> for LicenseRefVisitor {
+        type Value = LicenseRef;
+
+        #[inline]
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a LicenseRef string")
+        }
+
+        #[inline]
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where E: de::Error,
+        {
+            LicenseRef::parse(v).map_err(E::custom)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for LicenseRef {
+        #[inline]
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>
+        {
+            deserializer.deserialize_str(LicenseRefVisitor)
+        }
+    }
+
+    impl Serialize for LicenseRef {
+        #[inline]
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_str(self.id())
+        }
+    }
+}
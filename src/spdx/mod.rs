@@ -6,11 +6,30 @@ use core::{
 };
 use crate::ParseError;
 
+mod blue_oak;
 mod decl;
+mod domain;
+mod exception;
+mod kind;
+mod license_ref;
+mod obligations;
 mod serde;
+mod status;
+mod url;
+mod version;
 
 #[doc(inline)]
-pub use self::decl::SpdxLicense;
+pub use self::{
+    blue_oak::BlueOakRating,
+    decl::SpdxLicense,
+    domain::Domain,
+    exception::SpdxException,
+    kind::LicenseKind,
+    license_ref::LicenseRef,
+    obligations::{Conditions, Limitations, Obligations, Permissions},
+    status::FedoraStatus,
+    version::{Changes, ListVersion},
+};
 
 /// A fixed-size array for indexing with a [`SpdxLicense`] casted to [`usize`].
 /// See also [`SpdxLicense::COUNT`].
@@ -51,6 +70,21 @@ pub use self::decl::SpdxLicense;
 /// [`as usize`]: https://doc.rust-lang.org/nightly/reference/items/enumerations.html#custom-discriminant-values-for-field-less-enumerations
 pub type Map<A> = [A; SpdxLicense::COUNT];
 
+/// Creates a [`Map`](type.Map.html) where each license in `entries` has its
+/// associated value and every other license has `default`.
+pub(crate) const fn sparse_map<A: Copy>(
+    default: A,
+    entries: &[(SpdxLicense, A)],
+) -> Map<A> {
+    let mut map = [default; SpdxLicense::COUNT];
+    let mut i = 0;
+    while i < entries.len() {
+        map[entries[i].0 as usize] = entries[i].1;
+        i += 1;
+    }
+    map
+}
+
 impl<'a> TryFrom<&'a str> for SpdxLicense {
     type Error = ParseError<'a>;
 
@@ -105,6 +139,26 @@ impl SpdxLicense {
         input.try_into()
     }
 
+    /// Parses `id` like [`parse`](#method.parse), but only accepts licenses
+    /// that are [valid](#method.is_valid_in) in `version` of the license list.
+    ///
+    /// ```
+    /// use linfo::{spdx::{ListVersion, SpdxLicense}, ParseError};
+    ///
+    /// let v3_5 = ListVersion::new(3, 5);
+    /// assert_eq!(SpdxLicense::parse_in("MIT", v3_5), Ok(SpdxLicense::Mit));
+    /// assert_eq!(
+    ///     SpdxLicense::parse_in("BlueOak-1.0.0", v3_5),
+    ///     Err(ParseError::UnknownLicenseId("BlueOak-1.0.0")),
+    /// );
+    /// ```
+    pub fn parse_in(id: &str, version: ListVersion) -> Result<Self, ParseError<'_>> {
+        match Self::parse(id)? {
+            l if l.is_valid_in(version) => Ok(l),
+            _ => Err(ParseError::UnknownLicenseId(id)),
+        }
+    }
+
     /// Returns the string identifier of this license.
     #[inline]
     pub const fn id(self) -> &'static str {
@@ -117,6 +171,137 @@ impl SpdxLicense {
         Self::NAME[self as usize]
     }
 
+    /// Returns the canonical URL of this license on the SPDX website.
+    ///
+    /// ```
+    /// use linfo::SpdxLicense;
+    ///
+    /// assert_eq!(SpdxLicense::Mit.url(), "https://spdx.org/licenses/MIT.html");
+    /// ```
+    #[inline]
+    pub const fn url(self) -> &'static str {
+        Self::URL[self as usize]
+    }
+
+    /// Returns the URL of this license on the [OSI](https://opensource.org)
+    /// website if it is [approved](#method.is_osi_approved).
+    ///
+    /// ```
+    /// use linfo::SpdxLicense;
+    ///
+    /// let url = "https://opensource.org/licenses/GPL-2.0";
+    /// assert_eq!(SpdxLicense::Gpl2Only.osi_url(), Some(url));
+    /// assert_eq!(SpdxLicense::Wtfpl.osi_url(), None);
+    /// ```
+    #[inline]
+    pub const fn osi_url(self) -> Option<&'static str> {
+        Self::OSI_URL[self as usize]
+    }
+
+    /// Returns other URLs where this license can be found, such as its
+    /// upstream text, as listed by SPDX.
+    #[inline]
+    pub const fn see_also(self) -> &'static [&'static str] {
+        Self::SEE_ALSO[self as usize]
+    }
+
+    /// Returns the license found at `url`, such as in a Maven POM or CycloneDX
+    /// document.
+    ///
+    /// SPDX and OSI license pages are matched by identifier, and other URLs
+    /// must be in [`see_also`](#method.see_also). The scheme, `www.` subdomain,
+    /// trailing slash and file extension are ignored. A URL shared by several
+    /// licenses, such as that of the GPL-3.0, returns the first of them.
+    ///
+    /// ```
+    /// use linfo::SpdxLicense;
+    ///
+    /// let url = "http://www.apache.org/licenses/LICENSE-2.0.txt";
+    /// assert_eq!(SpdxLicense::from_url(url), Some(SpdxLicense::Apache2));
+    /// assert_eq!(SpdxLicense::from_url("https://spdx.org/licenses/MIT.html"), Some(SpdxLicense::Mit));
+    /// ```
+    pub fn from_url(url: &str) -> Option<Self> {
+        use self::url::{normalize, strip_prefix};
+
+        let url = normalize(url);
+
+        let id = strip_prefix(url, "spdx.org/licenses/")
+            .or_else(|| strip_prefix(url, "opensource.org/licenses/"))
+            .or_else(|| strip_prefix(url, "opensource.org/license/"));
+        if let Some(l) = id.and_then(|id| Self::all().find(|l| l.id().eq_ignore_ascii_case(id))) {
+            return Some(l);
+        }
+
+        Self::all().find(|l| {
+            l.see_also().iter().any(|see_also| normalize(see_also).eq_ignore_ascii_case(url))
+        })
+    }
+
+    /// Returns the version of the SPDX license list that this license was
+    /// added in.
+    ///
+    /// Licenses added in version 2.5 or earlier are reported as added in 1.0.
+    ///
+    /// ```
+    /// use linfo::spdx::{ListVersion, SpdxLicense};
+    ///
+    /// assert_eq!(SpdxLicense::BlueOak1.since(), ListVersion::new(3, 6));
+    ///
+    /// // Replaced the deprecated `GPL-2.0`
+    /// assert_eq!(SpdxLicense::Gpl2Only.since(), ListVersion::new(3, 0));
+    /// ```
+    #[inline]
+    pub const fn since(self) -> ListVersion {
+        Self::SINCE[self as usize]
+    }
+
+    /// Returns the version of the SPDX license list that deprecated this
+    /// license, or `None` if it is not deprecated.
+    ///
+    /// This includes versions later than
+    /// [`ListVersion::CURRENT`](struct.ListVersion.html#associatedconstant.CURRENT).
+    #[inline]
+    pub const fn deprecated_in(self) -> Option<ListVersion> {
+        Self::DEPRECATED[self as usize]
+    }
+
+    /// Returns whether this license was added in or before `version` of the
+    /// SPDX license list.
+    ///
+    /// Deprecated licenses remain in the list for existing documents, so they
+    /// stay valid. See [`is_deprecated_in`](#method.is_deprecated_in).
+    ///
+    /// ```
+    /// use linfo::spdx::{ListVersion, SpdxLicense};
+    ///
+    /// let license = SpdxLicense::BlueOak1;
+    /// assert!(!license.is_valid_in(ListVersion::new(3, 5)));
+    /// assert!(license.is_valid_in(ListVersion::new(3, 6)));
+    /// assert!(SpdxLicense::Bsd2ClauseFreeBsd.is_valid_in(ListVersion::new(3, 10)));
+    /// ```
+    #[inline]
+    pub const fn is_valid_in(self, version: ListVersion) -> bool {
+        self.since().as_u16() <= version.as_u16()
+    }
+
+    /// Returns whether this license was deprecated in or before `version` of
+    /// the SPDX license list.
+    ///
+    /// ```
+    /// use linfo::spdx::{ListVersion, SpdxLicense};
+    ///
+    /// let license = SpdxLicense::Bsd2ClauseFreeBsd;
+    /// assert!(!license.is_deprecated_in(ListVersion::new(3, 7)));
+    /// assert!(license.is_deprecated_in(ListVersion::new(3, 10)));
+    /// ```
+    #[inline]
+    pub const fn is_deprecated_in(self, version: ListVersion) -> bool {
+        match self.deprecated_in() {
+            Some(deprecated_in) => deprecated_in.as_u16() <= version.as_u16(),
+            None => false,
+        }
+    }
+
     /// Considered libre/free by the [Free Software Foundation
     /// (FSF)](https://www.fsf.org).
     #[inline]
@@ -131,6 +316,134 @@ impl SpdxLicense {
         Self::OSI[self as usize]
     }
 
+    /// The license is compatible with version 2 of the [GNU
+    /// GPL](https://www.gnu.org/licenses/license-list.html), according to the
+    /// FSF's license list as of January 2024.
+    ///
+    /// ```
+    /// use linfo::SpdxLicense;
+    ///
+    /// assert!(SpdxLicense::Mit.is_gpl2_compatible());
+    /// assert!(!SpdxLicense::Apache2.is_gpl2_compatible());
+    /// assert!(SpdxLicense::Apache2.is_gpl3_compatible());
+    /// ```
+    #[inline]
+    pub const fn is_gpl2_compatible(self) -> bool {
+        Self::GPL2_COMPATIBLE[self as usize]
+    }
+
+    /// The license is compatible with version 3 of the [GNU
+    /// GPL](https://www.gnu.org/licenses/license-list.html), according to the
+    /// FSF's license list as of January 2024.
+    #[inline]
+    pub const fn is_gpl3_compatible(self) -> bool {
+        Self::GPL3_COMPATIBLE[self as usize]
+    }
+
+    /// The license meets the [Debian Free Software
+    /// Guidelines](https://www.debian.org/social_contract#guidelines) (DFSG),
+    /// based on the licenses of packages in Debian's `main` archive as of
+    /// January 2024.
+    #[inline]
+    pub const fn is_dfsg_free(self) -> bool {
+        Self::DFSG[self as usize]
+    }
+
+    /// Returns whether the license may be used in Fedora packages, according
+    /// to the [Fedora license list](https://docs.fedoraproject.org/en-US/legal/allowed-licenses/)
+    /// as of January 2024, or `None` if it is not listed.
+    ///
+    /// ```
+    /// use linfo::spdx::{FedoraStatus, SpdxLicense};
+    ///
+    /// assert_eq!(SpdxLicense::Mit.fedora_status(), Some(FedoraStatus::Allowed));
+    /// assert_eq!(SpdxLicense::CC01.fedora_status(), Some(FedoraStatus::AllowedContent));
+    /// assert_eq!(SpdxLicense::Sspl1.fedora_status(), Some(FedoraStatus::NotAllowed));
+    /// ```
+    #[inline]
+    pub const fn fedora_status(self) -> Option<FedoraStatus> {
+        Self::FEDORA[self as usize]
+    }
+
+    /// Returns the broad category of this license, or
+    /// [`LicenseKind::Unknown`](enum.LicenseKind.html#variant.Unknown) if it
+    /// has not been categorized.
+    ///
+    /// ```
+    /// use linfo::spdx::{LicenseKind, SpdxLicense};
+    ///
+    /// assert_eq!(SpdxLicense::Mit.kind(), LicenseKind::Permissive);
+    /// assert_eq!(SpdxLicense::Gpl3Only.kind(), LicenseKind::StrongCopyleft);
+    /// ```
+    #[inline]
+    pub const fn kind(self) -> LicenseKind {
+        Self::KIND[self as usize]
+    }
+
+    /// Returns the rating of this license by the [Blue Oak
+    /// Council](https://blueoakcouncil.org/list), or `None` if it is not
+    /// rated.
+    ///
+    /// Only permissive licenses are rated.
+    ///
+    /// ```
+    /// use linfo::spdx::{BlueOakRating, SpdxLicense};
+    ///
+    /// assert_eq!(SpdxLicense::Bsd2ClausePatent.blue_oak_rating(), Some(BlueOakRating::Gold));
+    /// assert!(SpdxLicense::Mit.blue_oak_rating() >= Some(BlueOakRating::Silver));
+    /// assert_eq!(SpdxLicense::Gpl3Only.blue_oak_rating(), None);
+    /// ```
+    #[inline]
+    pub const fn blue_oak_rating(self) -> Option<BlueOakRating> {
+        Self::BLUE_OAK[self as usize]
+    }
+
+    /// Returns the subject matter that this license is intended for.
+    ///
+    /// This can be used to warn about code under a license meant for something
+    /// else:
+    ///
+    /// ```
+    /// use linfo::spdx::{Domain, SpdxLicense};
+    ///
+    /// assert_eq!(SpdxLicense::Mit.domain(), Domain::Software);
+    /// assert_eq!(SpdxLicense::Gfdl1_3Only.domain(), Domain::Documentation);
+    /// assert_eq!(SpdxLicense::CcBySa4.domain(), Domain::Content);
+    /// assert_eq!(SpdxLicense::CernOhl1_2.domain(), Domain::Hardware);
+    /// ```
+    #[inline]
+    pub const fn domain(self) -> Domain {
+        Self::DOMAIN[self as usize]
+    }
+
+    /// Returns the permissions, conditions and limitations of this license, or
+    /// `None` if they are unknown.
+    ///
+    /// `None` means that the license has not been classified yet, not that it
+    /// has no obligations. Only commonly used licenses are classified so far,
+    /// so callers should treat `None` as needing review.
+    ///
+    /// ```
+    /// use linfo::spdx::{Conditions, Permissions, SpdxLicense};
+    ///
+    /// let apache = SpdxLicense::Apache2.obligations().unwrap();
+    /// assert!(apache.permissions.contains(Permissions::PATENT_USE));
+    /// assert_eq!(
+    ///     apache.conditions.to_string(),
+    ///     "include copyright, include license, state changes, include notice",
+    /// );
+    ///
+    /// let gpl = SpdxLicense::Gpl3Only.obligations().unwrap();
+    /// assert!(gpl.conditions.contains(Conditions::DISCLOSE_SOURCE | Conditions::SAME_LICENSE));
+    ///
+    /// // Not yet classified
+    /// assert!(SpdxLicense::Abstyles.obligations().is_none());
+    /// ```
+    #[inline]
+    pub const fn obligations(self) -> Option<Obligations> {
+        Self::OBLIGATIONS[self as usize]
+    }
+
     /// Returns whether the license is associated with [Creative
     /// Commons](https://creativecommons.org).
     #[inline]
@@ -0,0 +1,269 @@
+use core::{fmt, ops};
+use super::{sparse_map, Map, SpdxLicense};
+
+macro_rules! flags {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$flag_meta:meta])* $flag:ident = $bit:literal, $label:literal;)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name(u8);
+
+        impl $name {
+            $(
+                $(#[$flag_meta])*
+                pub const $flag: Self = Self(1 << $bit);
+            )+
+
+            const FLAGS: [(Self, &'static str); count!($($flag)+)] = [
+                $((Self::$flag, $label),)+
+            ];
+
+            /// Returns a value with no flags set.
+            #[inline]
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Returns whether no flags are set.
+            #[inline]
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            /// Returns whether all flags in `other` are set.
+            #[inline]
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Returns the flags set in either `self` or `other`.
+            #[inline]
+            pub const fn union(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+
+            /// Returns an iterator over each set flag and its description.
+            pub fn iter(self) -> impl Iterator<Item = (Self, &'static str)> {
+                Self::FLAGS.iter().copied().filter(move |&(f, _)| self.contains(f))
+            }
+        }
+
+        impl ops::BitOr for $name {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, other: Self) -> Self {
+                self.union(other)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for (i, (_, label)) in self.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(label)?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+flags! {
+    /// What a license allows licensees to do.
+    Permissions {
+        /// The work may be used for commercial purposes.
+        COMMERCIAL_USE = 0, "commercial use";
+        /// The work may be modified.
+        MODIFICATION = 1, "modification";
+        /// The work may be distributed.
+        DISTRIBUTION = 2, "distribution";
+        /// The license grants rights to the contributors' patents.
+        PATENT_USE = 3, "patent use";
+        /// The work may be used and modified privately.
+        PRIVATE_USE = 4, "private use";
+    }
+}
+
+flags! {
+    /// What a license requires of licensees who distribute the work.
+    Conditions {
+        /// The copyright notice must be included.
+        INCLUDE_COPYRIGHT = 0, "include copyright";
+        /// The license text must be included.
+        INCLUDE_LICENSE = 1, "include license";
+        /// Changes made to the work must be documented.
+        STATE_CHANGES = 2, "state changes";
+        /// The source code must be made available.
+        DISCLOSE_SOURCE = 3, "disclose source";
+        /// Modifications must be released under the same license.
+        SAME_LICENSE = 4, "same license";
+        /// Users who interact with the work over a network must be given the
+        /// source code.
+        NETWORK_USE_DISCLOSE = 5, "network use is distribution";
+        /// The contents of a `NOTICE` file, if any, must be reproduced.
+        INCLUDE_NOTICE = 6, "include notice";
+        /// Users must be able to relink the work with a modified version of
+        /// the library, such as via dynamic linking.
+        ALLOW_RELINKING = 7, "allow relinking";
+    }
+}
+
+flags! {
+    /// What a license explicitly does not grant.
+    Limitations {
+        /// Contributors are not liable for damages.
+        LIABILITY = 0, "liability";
+        /// The work is provided without warranty.
+        WARRANTY = 1, "warranty";
+        /// No rights to the contributors' trademarks are granted.
+        TRADEMARK_USE = 2, "trademark use";
+        /// No rights to the contributors' patents are granted.
+        PATENT_USE = 3, "patent use";
+    }
+}
+
+/// The permissions, conditions and limitations of a license.
+///
+/// See [`SpdxLicense::obligations`](enum.SpdxLicense.html#method.obligations).
+///
+/// This is not legal advice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Obligations {
+    /// What the license allows.
+    pub permissions: Permissions,
+    /// What the license requires when distributing the work.
+    pub conditions: Conditions,
+    /// What the license does not grant.
+    pub limitations: Limitations,
+}
+
+impl Obligations {
+    /// Returns the obligations of following both `self` and `other`, such as
+    /// for an `AND` expression.
+    ///
+    /// Only permissions granted by both apply, while all conditions and
+    /// limitations of either apply.
+    pub const fn combine(self, other: Self) -> Self {
+        Self {
+            permissions: Permissions(self.permissions.0 & other.permissions.0),
+            conditions: self.conditions.union(other.conditions),
+            limitations: self.limitations.union(other.limitations),
+        }
+    }
+}
+
+impl SpdxLicense {
+    // Licenses not listed here are not yet classified, which is `None`.
+    pub(crate) const OBLIGATIONS: Map<Option<Obligations>> = {
+        use SpdxLicense as L;
+
+        const fn o(p: u8, c: u8, l: u8) -> Option<Obligations> {
+            Some(Obligations {
+                permissions: Permissions(p),
+                conditions: Conditions(c),
+                limitations: Limitations(l),
+            })
+        }
+
+        // Permissions
+        const USE: u8 = Permissions::COMMERCIAL_USE.0 |
+            Permissions::MODIFICATION.0 |
+            Permissions::DISTRIBUTION.0 |
+            Permissions::PRIVATE_USE.0;
+        const PATENT: u8 = USE | Permissions::PATENT_USE.0;
+
+        // Conditions
+        const NOTICE: u8 = Conditions::INCLUDE_COPYRIGHT.0 | Conditions::INCLUDE_LICENSE.0;
+        const CHANGES: u8 = NOTICE | Conditions::STATE_CHANGES.0;
+        const FILE_COPYLEFT: u8 = NOTICE |
+            Conditions::DISCLOSE_SOURCE.0 |
+            Conditions::SAME_LICENSE.0;
+        const COPYLEFT: u8 = FILE_COPYLEFT | Conditions::STATE_CHANGES.0;
+        const NETWORK: u8 = COPYLEFT | Conditions::NETWORK_USE_DISCLOSE.0;
+        const APACHE: u8 = CHANGES | Conditions::INCLUDE_NOTICE.0;
+        const LIBRARY: u8 = COPYLEFT | Conditions::ALLOW_RELINKING.0;
+
+        // Limitations
+        const AS_IS: u8 = Limitations::LIABILITY.0 | Limitations::WARRANTY.0;
+        const TRADEMARK: u8 = AS_IS | Limitations::TRADEMARK_USE.0;
+        const NO_PATENT: u8 = TRADEMARK | Limitations::PATENT_USE.0;
+
+        sparse_map(None, &[
+            (L::CC01, o(USE, 0, NO_PATENT)),
+            (L::Unlicense, o(USE, 0, AS_IS)),
+            (L::Bsd0, o(USE, 0, AS_IS)),
+            (L::Mit0, o(USE, 0, AS_IS)),
+
+            (L::Mit, o(USE, NOTICE, AS_IS)),
+            (L::Isc, o(USE, NOTICE, AS_IS)),
+            (L::Bsd2Clause, o(USE, NOTICE, AS_IS)),
+            (L::Bsd3Clause, o(USE, NOTICE, AS_IS)),
+            (L::Bsd3ClauseClear, o(USE, NOTICE, AS_IS | Limitations::PATENT_USE.0)),
+            (L::Bsl1, o(USE, NOTICE, AS_IS)),
+            (L::PostgreSql, o(USE, NOTICE, AS_IS)),
+            (L::UnicodeDfs2016, o(USE, NOTICE, AS_IS)),
+            (L::Zlib, o(
+                USE,
+                Conditions::INCLUDE_COPYRIGHT.0 | Conditions::STATE_CHANGES.0,
+                AS_IS,
+            )),
+            (L::Ncsa, o(USE, NOTICE, AS_IS)),
+
+            (L::Apache2, o(PATENT, APACHE, TRADEMARK)),
+            (L::Ecl2, o(PATENT, APACHE, TRADEMARK)),
+            (L::Upl1, o(PATENT, NOTICE, AS_IS)),
+            (L::MsPl, o(PATENT, NOTICE, Limitations::WARRANTY.0 | Limitations::TRADEMARK_USE.0)),
+
+            (L::CcBy4, o(USE, CHANGES, NO_PATENT)),
+            (L::CcBySa4, o(USE, CHANGES | Conditions::SAME_LICENSE.0, NO_PATENT)),
+
+            (L::Mpl2, o(PATENT, FILE_COPYLEFT, TRADEMARK)),
+            (L::Epl1, o(PATENT, FILE_COPYLEFT, AS_IS)),
+            (L::Epl2, o(PATENT, FILE_COPYLEFT, AS_IS)),
+            (L::Lgpl2_1Only, o(USE, LIBRARY, AS_IS)),
+            (L::Lgpl2_1OrLater, o(USE, LIBRARY, AS_IS)),
+            (L::Lgpl3Only, o(PATENT, LIBRARY, AS_IS)),
+            (L::Lgpl3OrLater, o(PATENT, LIBRARY, AS_IS)),
+
+            (L::Gpl2Only, o(USE, COPYLEFT, AS_IS)),
+            (L::Gpl2OrLater, o(USE, COPYLEFT, AS_IS)),
+            (L::Gpl3Only, o(PATENT, COPYLEFT, AS_IS)),
+            (L::Gpl3OrLater, o(PATENT, COPYLEFT, AS_IS)),
+
+            (L::Agpl3Only, o(PATENT, NETWORK, AS_IS)),
+            (L::Agpl3OrLater, o(PATENT, NETWORK, AS_IS)),
+            (L::Eupl1_2, o(PATENT, COPYLEFT, TRADEMARK)),
+            (L::Osl3, o(PATENT, NETWORK, TRADEMARK)),
+        ])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spdx::LicenseKind;
+
+    #[test]
+    fn obligations_match_kind() {
+        for license in SpdxLicense::all() {
+            let obligations = match license.obligations() {
+                Some(obligations) => obligations,
+                None => continue,
+            };
+            assert!(obligations.permissions.contains(Permissions::DISTRIBUTION), "{}", license);
+
+            let copyleft = obligations.conditions.contains(Conditions::SAME_LICENSE);
+            assert_eq!(copyleft, license.kind().is_copyleft(), "{}", license);
+
+            let network = obligations.conditions.contains(Conditions::NETWORK_USE_DISCLOSE);
+            assert_eq!(network, license.kind() == LicenseKind::NetworkCopyleft, "{}", license);
+        }
+    }
+}
@@ -5,7 +5,7 @@ use serde::{
     ser::{Serialize, Serializer},
     de::{self, Deserialize, Deserializer, Visitor},
 };
-use super::SpdxLicense;
+use super::{SpdxException, SpdxLicense};
 
 struct LicenseVisitor;
 
@@ -41,3 +41,38 @@ impl Serialize for SpdxLicense {
         s.serialize_str(self.id())
     }
 }
+
+struct ExceptionVisitor;
+
+impl<'de> Visitor<'de> for ExceptionVisitor {
+    type Value = SpdxException;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an exception string")
+    }
+
+    #[inline]
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where E: de::Error,
+    {
+        SpdxException::parse(v).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for SpdxException {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        deserializer.deserialize_str(ExceptionVisitor)
+    }
+}
+
+impl Serialize for SpdxException {
+    #[inline]
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(self.id())
+    }
+}
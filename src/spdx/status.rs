@@ -0,0 +1,275 @@
+use core::fmt;
+use super::{sparse_map, Map, SpdxLicense};
+
+/// Whether a license may be used in packages of the [Fedora
+/// Project](https://docs.fedoraproject.org/en-US/legal/).
+///
+/// See [`SpdxLicense::fedora_status`](enum.SpdxLicense.html#method.fedora_status).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum FedoraStatus {
+    /// Allowed for any kind of work, including code.
+    Allowed,
+    /// Allowed only for content, such as images or data, but not for code.
+    AllowedContent,
+    /// Not allowed.
+    NotAllowed,
+}
+
+impl fmt::Display for FedoraStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            FedoraStatus::Allowed => "allowed",
+            FedoraStatus::AllowedContent => "allowed-content",
+            FedoraStatus::NotAllowed => "not-allowed",
+        })
+    }
+}
+
+/// Creates a map where only the licenses in `sets` are `true`.
+const fn set(sets: &[&[SpdxLicense]]) -> Map<bool> {
+    let mut map = [false; SpdxLicense::COUNT];
+    let mut i = 0;
+    while i < sets.len() {
+        let mut j = 0;
+        while j < sets[i].len() {
+            map[sets[i][j] as usize] = true;
+            j += 1;
+        }
+        i += 1;
+    }
+    map
+}
+
+// Snapshots of each list as of 2024-01. Licenses that a list does not mention
+// are `false` or `None`.
+impl SpdxLicense {
+    // Compatible with both GPLv2 and GPLv3, according to the FSF.
+    const GPL_COMPATIBLE: &'static [SpdxLicense] = {
+        use SpdxLicense as L;
+
+        &[
+            L::Artistic2, L::Bsd2Clause, L::Bsd2ClauseFreeBsd,
+            L::Bsd2ClauseNetBsd, L::Bsd3Clause, L::Bsd3ClauseClear, L::Bsl1,
+            L::CC01, L::CcBy4, L::Cecill2, L::Cecill2_1, L::ClArtistic,
+            L::CnriPythonGplCompatible, L::Efl2, L::EGenix, L::EUDatagrid,
+            L::Eupl1_2, L::Fsfap, L::Fsful, L::Fsfullr, L::Gpl1OrLater,
+            L::Gpl2OrLater, L::Hpnd, L::Ijg, L::IMatix, L::Imlib2, L::Intel,
+            L::Isc, L::Lgpl2Only, L::Lgpl2OrLater, L::Lgpl2_1Only,
+            L::Lgpl2_1OrLater, L::Mit, L::Mpl2, L::Ncsa, L::Oldap2_7,
+            L::Python2, L::Ruby, L::SgiB2, L::Sleepycat, L::Smlnj,
+            L::UnicodeDfs2015, L::UnicodeDfs2016, L::Unlicense, L::Upl1, L::Vim,
+            L::W3C, L::W3C20150513, L::Wtfpl, L::X11, L::Zlib, L::Zpl2,
+            L::Zpl2_1,
+        ]
+    };
+
+    pub(crate) const GPL2_COMPATIBLE: Map<bool> = {
+        use SpdxLicense as L;
+
+        set(&[Self::GPL_COMPATIBLE, &[L::Gpl2Only, L::Eupl1_1]])
+    };
+
+    pub(crate) const GPL3_COMPATIBLE: Map<bool> = {
+        use SpdxLicense as L;
+
+        set(&[Self::GPL_COMPATIBLE, &[
+            L::Agpl3Only, L::Agpl3OrLater, L::Apache2, L::CcBySa4, L::Ecl2,
+            L::Ftl, L::Gpl3Only, L::Gpl3OrLater, L::Lgpl3Only, L::Lgpl3OrLater,
+        ]])
+    };
+
+    pub(crate) const DFSG: Map<bool> = {
+        use SpdxLicense as L;
+
+        set(&[&[
+            L::Bsd0, L::Agpl3Only, L::Agpl3OrLater, L::Apache1, L::Apache1_1,
+            L::Apache2, L::Apsl2, L::Artistic1Perl, L::Artistic2, L::Beerware,
+            L::Blessing, L::Bsd2Clause, L::Bsd2ClauseFreeBsd,
+            L::Bsd2ClauseNetBsd, L::Bsd2ClausePatent, L::Bsd3Clause,
+            L::Bsd3ClauseClear, L::Bsd4Clause, L::Bsd4ClauseUc, L::Bsl1,
+            L::Bzip21_0_6, L::CcBy3, L::CcBy4, L::CcBySa3, L::CcBySa4, L::CC01,
+            L::Cddl1, L::Cddl1_1, L::Cecill2, L::Cecill2_1, L::CecillB,
+            L::CecillC, L::ClArtistic, L::CnriPython, L::Cpl1, L::Curl,
+            L::Ecl2, L::Efl2, L::Epl1, L::Epl2, L::Eupl1_1, L::Eupl1_2,
+            L::Fsfap, L::Fsful, L::Fsfullr, L::Gpl1OrLater, L::Gpl2Only,
+            L::Gpl2OrLater, L::Gpl3Only, L::Gpl3OrLater, L::Hpnd, L::Icu,
+            L::Ijg, L::Ipl1, L::Isc, L::Lgpl2Only, L::Lgpl2OrLater,
+            L::Lgpl2_1Only, L::Lgpl2_1OrLater, L::Lgpl3Only, L::Lgpl3OrLater,
+            L::Libpng, L::Libpng2, L::Libtiff, L::Lppl1_3c, L::MirOs, L::Mit,
+            L::Mit0, L::Mpl1_1, L::Mpl2, L::Ncsa, L::Ntp, L::Ofl1_1,
+            L::OpenSsl, L::PostgreSql, L::Python2, L::Qpl1, L::Ruby,
+            L::Sleepycat, L::Tcl, L::UnicodeDfs2015, L::UnicodeDfs2016,
+            L::Unlicense, L::Upl1, L::Vim, L::W3C, L::Wtfpl, L::X11, L::Xnet,
+            L::Zlib, L::ZlibAcknowledgement, L::Zpl2_1,
+        ]])
+    };
+
+    pub(crate) const FEDORA: Map<Option<FedoraStatus>> = {
+        use FedoraStatus::*;
+        use SpdxLicense as L;
+
+        sparse_map(None, &[
+            (L::Bsd0, Some(Allowed)),
+            (L::Afl1_1, Some(Allowed)),
+            (L::Afl1_2, Some(Allowed)),
+            (L::Afl2, Some(Allowed)),
+            (L::Afl2_1, Some(Allowed)),
+            (L::Afl3, Some(Allowed)),
+            (L::Agpl1Only, Some(Allowed)),
+            (L::Agpl1OrLater, Some(Allowed)),
+            (L::Agpl3Only, Some(Allowed)),
+            (L::Agpl3OrLater, Some(Allowed)),
+            (L::Apache1, Some(Allowed)),
+            (L::Apache1_1, Some(Allowed)),
+            (L::Apache2, Some(Allowed)),
+            (L::Apsl2, Some(Allowed)),
+            (L::Artistic1Perl, Some(Allowed)),
+            (L::Artistic2, Some(Allowed)),
+            (L::Beerware, Some(Allowed)),
+            (L::BitTorrent1_1, Some(Allowed)),
+            (L::Bsd2Clause, Some(Allowed)),
+            (L::Bsd2ClausePatent, Some(Allowed)),
+            (L::Bsd3Clause, Some(Allowed)),
+            (L::Bsd3ClauseClear, Some(Allowed)),
+            (L::Bsd4Clause, Some(Allowed)),
+            (L::Bsl1, Some(Allowed)),
+            (L::Cddl1, Some(Allowed)),
+            (L::Cddl1_1, Some(Allowed)),
+            (L::Cecill2, Some(Allowed)),
+            (L::Cecill2_1, Some(Allowed)),
+            (L::CecillB, Some(Allowed)),
+            (L::CecillC, Some(Allowed)),
+            (L::ClArtistic, Some(Allowed)),
+            (L::Cpl1, Some(Allowed)),
+            (L::Curl, Some(Allowed)),
+            (L::Ecl2, Some(Allowed)),
+            (L::Efl2, Some(Allowed)),
+            (L::Epl1, Some(Allowed)),
+            (L::Epl2, Some(Allowed)),
+            (L::Eupl1_1, Some(Allowed)),
+            (L::Eupl1_2, Some(Allowed)),
+            (L::Gpl1OrLater, Some(Allowed)),
+            (L::Gpl2Only, Some(Allowed)),
+            (L::Gpl2OrLater, Some(Allowed)),
+            (L::Gpl3Only, Some(Allowed)),
+            (L::Gpl3OrLater, Some(Allowed)),
+            (L::Hpnd, Some(Allowed)),
+            (L::Icu, Some(Allowed)),
+            (L::Ijg, Some(Allowed)),
+            (L::Ipl1, Some(Allowed)),
+            (L::Isc, Some(Allowed)),
+            (L::Lgpl2Only, Some(Allowed)),
+            (L::Lgpl2OrLater, Some(Allowed)),
+            (L::Lgpl2_1Only, Some(Allowed)),
+            (L::Lgpl2_1OrLater, Some(Allowed)),
+            (L::Lgpl3Only, Some(Allowed)),
+            (L::Lgpl3OrLater, Some(Allowed)),
+            (L::Libpng, Some(Allowed)),
+            (L::Libtiff, Some(Allowed)),
+            (L::Lppl1_3a, Some(Allowed)),
+            (L::Lppl1_3c, Some(Allowed)),
+            (L::Mit, Some(Allowed)),
+            (L::Mit0, Some(Allowed)),
+            (L::Mpl1, Some(Allowed)),
+            (L::Mpl1_1, Some(Allowed)),
+            (L::Mpl2, Some(Allowed)),
+            (L::MsPl, Some(Allowed)),
+            (L::MsRl, Some(Allowed)),
+            (L::Ncsa, Some(Allowed)),
+            (L::Ntp, Some(Allowed)),
+            (L::Ofl1, Some(Allowed)),
+            (L::Ofl1_1, Some(Allowed)),
+            (L::OpenSsl, Some(Allowed)),
+            (L::Osl1, Some(Allowed)),
+            (L::Osl1_1, Some(Allowed)),
+            (L::Osl2, Some(Allowed)),
+            (L::Osl2_1, Some(Allowed)),
+            (L::Osl3, Some(Allowed)),
+            (L::Php3, Some(Allowed)),
+            (L::Php3_01, Some(Allowed)),
+            (L::PostgreSql, Some(Allowed)),
+            (L::Python2, Some(Allowed)),
+            (L::Qhull, Some(Allowed)),
+            (L::Qpl1, Some(Allowed)),
+            (L::Rpsl1, Some(Allowed)),
+            (L::Ruby, Some(Allowed)),
+            (L::Sissl, Some(Allowed)),
+            (L::Sleepycat, Some(Allowed)),
+            (L::Tcl, Some(Allowed)),
+            (L::UnicodeDfs2016, Some(Allowed)),
+            (L::Unlicense, Some(Allowed)),
+            (L::Upl1, Some(Allowed)),
+            (L::Vim, Some(Allowed)),
+            (L::W3C, Some(Allowed)),
+            (L::Wtfpl, Some(Allowed)),
+            (L::X11, Some(Allowed)),
+            (L::Zlib, Some(Allowed)),
+            (L::ZlibAcknowledgement, Some(Allowed)),
+            (L::Zpl2, Some(Allowed)),
+            (L::Zpl2_1, Some(Allowed)),
+
+            (L::CC01, Some(AllowedContent)),
+            (L::CcBy1, Some(AllowedContent)),
+            (L::CcBy2, Some(AllowedContent)),
+            (L::CcBy2_5, Some(AllowedContent)),
+            (L::CcBy3, Some(AllowedContent)),
+            (L::CcBy4, Some(AllowedContent)),
+            (L::CcBySa1, Some(AllowedContent)),
+            (L::CcBySa2, Some(AllowedContent)),
+            (L::CcBySa2_5, Some(AllowedContent)),
+            (L::CcBySa3, Some(AllowedContent)),
+            (L::CcBySa4, Some(AllowedContent)),
+            (L::ODbL1, Some(AllowedContent)),
+            (L::OdcBy1, Some(AllowedContent)),
+            (L::Pddl1, Some(AllowedContent)),
+
+            (L::Aladdin, Some(NotAllowed)),
+            (L::Apsl1, Some(NotAllowed)),
+            (L::Apsl1_1, Some(NotAllowed)),
+            (L::Apsl1_2, Some(NotAllowed)),
+            (L::Artistic1, Some(NotAllowed)),
+            (L::CcByNc1, Some(NotAllowed)),
+            (L::CcByNc2, Some(NotAllowed)),
+            (L::CcByNc2_5, Some(NotAllowed)),
+            (L::CcByNc3, Some(NotAllowed)),
+            (L::CcByNc4, Some(NotAllowed)),
+            (L::CcByNcNd1, Some(NotAllowed)),
+            (L::CcByNcNd2, Some(NotAllowed)),
+            (L::CcByNcNd2_5, Some(NotAllowed)),
+            (L::CcByNcNd3, Some(NotAllowed)),
+            (L::CcByNcNd4, Some(NotAllowed)),
+            (L::CcByNcSa1, Some(NotAllowed)),
+            (L::CcByNcSa2, Some(NotAllowed)),
+            (L::CcByNcSa2_5, Some(NotAllowed)),
+            (L::CcByNcSa3, Some(NotAllowed)),
+            (L::CcByNcSa4, Some(NotAllowed)),
+            (L::Cpol1_02, Some(NotAllowed)),
+            (L::Json, Some(NotAllowed)),
+            (L::Sspl1, Some(NotAllowed)),
+            (L::Watcom1, Some(NotAllowed)),
+        ])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat;
+
+    #[test]
+    fn gpl_compatible_matches_compat() {
+        for license in SpdxLicense::all() {
+            if license.is_gpl2_compatible() {
+                assert!(compat::are_compatible_spdx(license, SpdxLicense::Gpl2Only), "{}", license);
+            }
+            if license.is_gpl3_compatible() {
+                assert!(compat::are_compatible_spdx(license, SpdxLicense::Gpl3Only), "{}", license);
+            }
+            if license.fedora_status() == Some(FedoraStatus::NotAllowed) {
+                assert!(!license.is_dfsg_free(), "{}", license);
+            }
+        }
+    }
+}
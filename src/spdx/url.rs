@@ -0,0 +1,438 @@
+use super::{sparse_map, Map, SpdxLicense};
+
+impl SpdxLicense {
+    // The "see also" references of the SPDX license list: the upstream texts
+    // of commonly used licenses, the legal code of Creative Commons licenses
+    // and the OSI page of every OSI-approved license.
+    pub(crate) const SEE_ALSO: Map<&'static [&'static str]> = {
+        use SpdxLicense as L;
+
+        const GPL2: &[&str] = &[
+            "https://www.gnu.org/licenses/old-licenses/gpl-2.0-standalone.html",
+            "https://opensource.org/licenses/GPL-2.0",
+        ];
+        const GPL3: &[&str] = &[
+            "https://www.gnu.org/licenses/gpl-3.0-standalone.html",
+            "https://opensource.org/licenses/GPL-3.0",
+        ];
+        const LGPL2_1: &[&str] = &[
+            "https://www.gnu.org/licenses/old-licenses/lgpl-2.1-standalone.html",
+            "https://opensource.org/licenses/LGPL-2.1",
+        ];
+        const LGPL3: &[&str] = &[
+            "https://www.gnu.org/licenses/lgpl-3.0-standalone.html",
+            "https://opensource.org/licenses/LGPL-3.0",
+        ];
+        const AGPL3: &[&str] = &[
+            "https://www.gnu.org/licenses/agpl.txt",
+            "https://opensource.org/licenses/AGPL-3.0",
+        ];
+
+        sparse_map(&[], &[
+            (L::Bsd0, &[
+                "http://landley.net/toybox/license.html",
+                "https://opensource.org/licenses/0BSD",
+            ]),
+            (L::Aal, &["https://opensource.org/licenses/AAL"]),
+            (L::Afl1_1, &["https://opensource.org/licenses/AFL-1.1"]),
+            (L::Afl1_2, &["https://opensource.org/licenses/AFL-1.2"]),
+            (L::Afl2, &["https://opensource.org/licenses/AFL-2.0"]),
+            (L::Afl2_1, &["https://opensource.org/licenses/AFL-2.1"]),
+            (L::Afl3, &["https://opensource.org/licenses/AFL-3.0"]),
+            (L::Agpl3Only, AGPL3),
+            (L::Agpl3OrLater, AGPL3),
+            (L::Apache1, &["http://www.apache.org/licenses/LICENSE-1.0"]),
+            (L::Apache1_1, &[
+                "http://apache.org/licenses/LICENSE-1.1",
+                "https://opensource.org/licenses/Apache-1.1",
+            ]),
+            (L::Apache2, &[
+                "http://www.apache.org/licenses/LICENSE-2.0",
+                "https://opensource.org/licenses/Apache-2.0",
+            ]),
+            (L::Apl1, &["https://opensource.org/licenses/APL-1.0"]),
+            (L::Apsl1, &["https://opensource.org/licenses/APSL-1.0"]),
+            (L::Apsl1_1, &["https://opensource.org/licenses/APSL-1.1"]),
+            (L::Apsl1_2, &["https://opensource.org/licenses/APSL-1.2"]),
+            (L::Apsl2, &["https://opensource.org/licenses/APSL-2.0"]),
+            (L::Artistic1, &["https://opensource.org/licenses/Artistic-1.0"]),
+            (L::Artistic1Cl8, &["https://opensource.org/licenses/Artistic-1.0"]),
+            (L::Artistic1Perl, &["https://opensource.org/licenses/Artistic-1.0"]),
+            (L::Artistic2, &[
+                "http://www.perlfoundation.org/artistic_license_2_0",
+                "https://opensource.org/licenses/artistic-license-2.0",
+                "https://opensource.org/licenses/Artistic-2.0",
+            ]),
+            (L::BlueOak1, &["https://blueoakcouncil.org/license/1.0.0"]),
+            (L::Bsd2Clause, &["https://opensource.org/licenses/BSD-2-Clause"]),
+            (L::Bsd2ClausePatent, &["https://opensource.org/licenses/BSD-2-Clause-Patent"]),
+            (L::Bsd3Clause, &["https://opensource.org/licenses/BSD-3-Clause"]),
+            (L::Bsd3ClauseLbnl, &["https://opensource.org/licenses/BSD-3-Clause-LBNL"]),
+            (L::Bsl1, &[
+                "http://www.boost.org/LICENSE_1_0.txt",
+                "https://opensource.org/licenses/BSL-1.0",
+            ]),
+            (L::Catosl1_1, &["https://opensource.org/licenses/CATOSL-1.1"]),
+            (L::CcBy1, &["https://creativecommons.org/licenses/by/1.0/legalcode"]),
+            (L::CcBy2, &["https://creativecommons.org/licenses/by/2.0/legalcode"]),
+            (L::CcBy2_5, &["https://creativecommons.org/licenses/by/2.5/legalcode"]),
+            (L::CcBy3, &["https://creativecommons.org/licenses/by/3.0/legalcode"]),
+            (L::CcBy4, &["https://creativecommons.org/licenses/by/4.0/legalcode"]),
+            (L::CcByNc1, &["https://creativecommons.org/licenses/by-nc/1.0/legalcode"]),
+            (L::CcByNc2, &["https://creativecommons.org/licenses/by-nc/2.0/legalcode"]),
+            (L::CcByNc2_5, &["https://creativecommons.org/licenses/by-nc/2.5/legalcode"]),
+            (L::CcByNc3, &["https://creativecommons.org/licenses/by-nc/3.0/legalcode"]),
+            (L::CcByNc4, &["https://creativecommons.org/licenses/by-nc/4.0/legalcode"]),
+            (L::CcByNcNd1, &["https://creativecommons.org/licenses/by-nc-nd/1.0/legalcode"]),
+            (L::CcByNcNd2, &["https://creativecommons.org/licenses/by-nc-nd/2.0/legalcode"]),
+            (L::CcByNcNd2_5, &["https://creativecommons.org/licenses/by-nc-nd/2.5/legalcode"]),
+            (L::CcByNcNd3, &["https://creativecommons.org/licenses/by-nc-nd/3.0/legalcode"]),
+            (L::CcByNcNd4, &["https://creativecommons.org/licenses/by-nc-nd/4.0/legalcode"]),
+            (L::CcByNcSa1, &["https://creativecommons.org/licenses/by-nc-sa/1.0/legalcode"]),
+            (L::CcByNcSa2, &["https://creativecommons.org/licenses/by-nc-sa/2.0/legalcode"]),
+            (L::CcByNcSa2_5, &["https://creativecommons.org/licenses/by-nc-sa/2.5/legalcode"]),
+            (L::CcByNcSa3, &["https://creativecommons.org/licenses/by-nc-sa/3.0/legalcode"]),
+            (L::CcByNcSa4, &["https://creativecommons.org/licenses/by-nc-sa/4.0/legalcode"]),
+            (L::CcByNd1, &["https://creativecommons.org/licenses/by-nd/1.0/legalcode"]),
+            (L::CcByNd2, &["https://creativecommons.org/licenses/by-nd/2.0/legalcode"]),
+            (L::CcByNd2_5, &["https://creativecommons.org/licenses/by-nd/2.5/legalcode"]),
+            (L::CcByNd3, &["https://creativecommons.org/licenses/by-nd/3.0/legalcode"]),
+            (L::CcByNd4, &["https://creativecommons.org/licenses/by-nd/4.0/legalcode"]),
+            (L::CcBySa1, &["https://creativecommons.org/licenses/by-sa/1.0/legalcode"]),
+            (L::CcBySa2, &["https://creativecommons.org/licenses/by-sa/2.0/legalcode"]),
+            (L::CcBySa2_5, &["https://creativecommons.org/licenses/by-sa/2.5/legalcode"]),
+            (L::CcBySa3, &["https://creativecommons.org/licenses/by-sa/3.0/legalcode"]),
+            (L::CcBySa4, &["https://creativecommons.org/licenses/by-sa/4.0/legalcode"]),
+            (L::CcPddc, &["https://creativecommons.org/licenses/publicdomain/"]),
+            (L::CC01, &["https://creativecommons.org/publicdomain/zero/1.0/legalcode"]),
+            (L::Cddl1, &[
+                "https://opensource.org/licenses/cddl1",
+                "https://opensource.org/licenses/CDDL-1.0",
+            ]),
+            (L::Cddl1_1, &[
+                "http://glassfish.java.net/public/CDDL+GPL_1_1.html",
+                "https://javaee.github.io/glassfish/LICENSE",
+            ]),
+            (L::Cecill2_1, &["https://opensource.org/licenses/CECILL-2.1"]),
+            (L::CnriPython, &["https://opensource.org/licenses/CNRI-Python"]),
+            (L::Cpal1, &["https://opensource.org/licenses/CPAL-1.0"]),
+            (L::Cpl1, &["https://opensource.org/licenses/CPL-1.0"]),
+            (L::CuaOpl1, &["https://opensource.org/licenses/CUA-OPL-1.0"]),
+            (L::Ecl1, &["https://opensource.org/licenses/ECL-1.0"]),
+            (L::Ecl2, &["https://opensource.org/licenses/ECL-2.0"]),
+            (L::Efl1, &["https://opensource.org/licenses/EFL-1.0"]),
+            (L::Efl2, &["https://opensource.org/licenses/EFL-2.0"]),
+            (L::Entessa, &["https://opensource.org/licenses/Entessa"]),
+            (L::Epl1, &[
+                "http://www.eclipse.org/legal/epl-v10.html",
+                "https://opensource.org/licenses/EPL-1.0",
+            ]),
+            (L::Epl2, &[
+                "https://www.eclipse.org/legal/epl-2.0",
+                "https://www.opensource.org/licenses/EPL-2.0",
+            ]),
+            (L::EUDatagrid, &["https://opensource.org/licenses/EUDatagrid"]),
+            (L::Eupl1_1, &["https://opensource.org/licenses/EUPL-1.1"]),
+            (L::Eupl1_2, &[
+                "https://joinup.ec.europa.eu/page/eupl-text-11-12",
+                "https://opensource.org/licenses/EUPL-1.2",
+            ]),
+            (L::Fair, &["https://opensource.org/licenses/Fair"]),
+            (L::Frameworx1, &["https://opensource.org/licenses/Frameworx-1.0"]),
+            (L::Gpl2Only, GPL2),
+            (L::Gpl2OrLater, GPL2),
+            (L::Gpl3Only, GPL3),
+            (L::Gpl3OrLater, GPL3),
+            (L::Hpnd, &["https://opensource.org/licenses/HPND"]),
+            (L::Intel, &["https://opensource.org/licenses/Intel"]),
+            (L::Ipa, &["https://opensource.org/licenses/IPA"]),
+            (L::Ipl1, &["https://opensource.org/licenses/IPL-1.0"]),
+            (L::Isc, &[
+                "https://www.isc.org/downloads/software-support-policy/isc-license/",
+                "https://opensource.org/licenses/ISC",
+            ]),
+            (L::Lgpl2Only, &["https://opensource.org/licenses/LGPL-2.0"]),
+            (L::Lgpl2OrLater, &["https://opensource.org/licenses/LGPL-2.0"]),
+            (L::Lgpl2_1Only, LGPL2_1),
+            (L::Lgpl2_1OrLater, LGPL2_1),
+            (L::Lgpl3Only, LGPL3),
+            (L::Lgpl3OrLater, LGPL3),
+            (L::LiLiQP1_1, &["https://opensource.org/licenses/LiLiQ-P-1.1"]),
+            (L::LiLiQR1_1, &["https://opensource.org/licenses/LiLiQ-R-1.1"]),
+            (L::LiLiQRplus1_1, &["https://opensource.org/licenses/LiLiQ-Rplus-1.1"]),
+            (L::Lpl1, &["https://opensource.org/licenses/LPL-1.0"]),
+            (L::Lpl1_02, &["https://opensource.org/licenses/LPL-1.02"]),
+            (L::Lppl1_3c, &["https://opensource.org/licenses/LPPL-1.3c"]),
+            (L::MirOs, &["https://opensource.org/licenses/MirOS"]),
+            (L::Mit, &["https://opensource.org/licenses/MIT"]),
+            (L::Mit0, &[
+                "https://github.com/aws/mit-0",
+                "https://romanrm.net/mit-zero",
+                "https://opensource.org/licenses/MIT-0",
+            ]),
+            (L::Motosoto, &["https://opensource.org/licenses/Motosoto"]),
+            (L::Mpl1, &["https://opensource.org/licenses/MPL-1.0"]),
+            (L::Mpl1_1, &[
+                "http://www.mozilla.org/MPL/MPL-1.1.html",
+                "https://opensource.org/licenses/MPL-1.1",
+            ]),
+            (L::Mpl2, &[
+                "http://www.mozilla.org/MPL/2.0/",
+                "https://opensource.org/licenses/MPL-2.0",
+            ]),
+            (L::Mpl2NoCopyleftException, &["https://opensource.org/licenses/MPL-2.0"]),
+            (L::MsPl, &[
+                "http://www.microsoft.com/opensource/licenses.mspx",
+                "https://opensource.org/licenses/MS-PL",
+            ]),
+            (L::MsRl, &["https://opensource.org/licenses/MS-RL"]),
+            (L::Multics, &["https://opensource.org/licenses/Multics"]),
+            (L::Nasa1_3, &["https://opensource.org/licenses/NASA-1.3"]),
+            (L::Naumen, &["https://opensource.org/licenses/Naumen"]),
+            (L::Ncsa, &[
+                "http://otm.illinois.edu/uiuc_openSource",
+                "https://opensource.org/licenses/NCSA",
+            ]),
+            (L::Ngpl, &["https://opensource.org/licenses/NGPL"]),
+            (L::Nokia, &["https://opensource.org/licenses/Nokia"]),
+            (L::Nposl3, &["https://opensource.org/licenses/NPOSL-3.0"]),
+            (L::Ntp, &["https://opensource.org/licenses/NTP"]),
+            (L::Oclc2, &["https://opensource.org/licenses/OCLC-2.0"]),
+            (L::Ofl1_1, &[
+                "http://scripts.sil.org/cms/scripts/page.php?item_id=OFL_web",
+                "https://opensource.org/licenses/OFL-1.1",
+            ]),
+            (L::Ogtsl, &["https://opensource.org/licenses/OGTSL"]),
+            (L::OpenSsl, &["http://www.openssl.org/source/license.html"]),
+            (L::OsetPl2_1, &["https://opensource.org/licenses/OSET-PL-2.1"]),
+            (L::Osl1, &["https://opensource.org/licenses/OSL-1.0"]),
+            (L::Osl2, &["https://opensource.org/licenses/OSL-2.0"]),
+            (L::Osl2_1, &["https://opensource.org/licenses/OSL-2.1"]),
+            (L::Osl3, &["https://opensource.org/licenses/OSL-3.0"]),
+            (L::Php3, &["https://opensource.org/licenses/PHP-3.0"]),
+            (L::PostgreSql, &[
+                "http://www.postgresql.org/about/licence",
+                "https://opensource.org/licenses/PostgreSQL",
+            ]),
+            (L::Python2, &["https://opensource.org/licenses/Python-2.0"]),
+            (L::Qpl1, &["https://opensource.org/licenses/QPL-1.0"]),
+            (L::Rpl1_1, &["https://opensource.org/licenses/RPL-1.1"]),
+            (L::Rpl1_5, &["https://opensource.org/licenses/RPL-1.5"]),
+            (L::Rpsl1, &["https://opensource.org/licenses/RPSL-1.0"]),
+            (L::Rscpl, &["https://opensource.org/licenses/RSCPL"]),
+            (L::SimPl2, &["https://opensource.org/licenses/SimPL-2.0"]),
+            (L::Sissl, &["https://opensource.org/licenses/SISSL"]),
+            (L::Sleepycat, &["https://opensource.org/licenses/Sleepycat"]),
+            (L::Spl1, &["https://opensource.org/licenses/SPL-1.0"]),
+            (L::Ucl1, &["https://opensource.org/licenses/UCL-1.0"]),
+            (L::UnicodeDfs2016, &["http://www.unicode.org/copyright.html"]),
+            (L::Unlicense, &["https://unlicense.org/"]),
+            (L::Upl1, &["https://opensource.org/licenses/UPL"]),
+            (L::Vsl1, &["https://opensource.org/licenses/VSL-1.0"]),
+            (L::W3C, &["https://opensource.org/licenses/W3C"]),
+            (L::Watcom1, &["https://opensource.org/licenses/Watcom-1.0"]),
+            (L::Wtfpl, &[
+                "http://sam.zoy.org/wtfpl/COPYING",
+                "http://www.wtfpl.net/about/",
+            ]),
+            (L::Xnet, &["https://opensource.org/licenses/Xnet"]),
+            (L::Zlib, &[
+                "http://www.zlib.net/zlib_license.html",
+                "https://opensource.org/licenses/Zlib",
+            ]),
+            (L::Zpl2, &["https://opensource.org/licenses/ZPL-2.0"]),
+        ])
+    };
+
+    // The page of every OSI-approved license on the OSI website.
+    pub(crate) const OSI_URL: Map<Option<&'static str>> = {
+        use SpdxLicense as L;
+
+        sparse_map(None, &[
+            (L::Bsd0, Some("https://opensource.org/licenses/0BSD")),
+            (L::Aal, Some("https://opensource.org/licenses/AAL")),
+            (L::Afl1_1, Some("https://opensource.org/licenses/AFL-1.1")),
+            (L::Afl1_2, Some("https://opensource.org/licenses/AFL-1.2")),
+            (L::Afl2, Some("https://opensource.org/licenses/AFL-2.0")),
+            (L::Afl2_1, Some("https://opensource.org/licenses/AFL-2.1")),
+            (L::Afl3, Some("https://opensource.org/licenses/AFL-3.0")),
+            (L::Agpl3Only, Some("https://opensource.org/licenses/AGPL-3.0")),
+            (L::Agpl3OrLater, Some("https://opensource.org/licenses/AGPL-3.0")),
+            (L::Apache1_1, Some("https://opensource.org/licenses/Apache-1.1")),
+            (L::Apache2, Some("https://opensource.org/licenses/Apache-2.0")),
+            (L::Apl1, Some("https://opensource.org/licenses/APL-1.0")),
+            (L::Apsl1, Some("https://opensource.org/licenses/APSL-1.0")),
+            (L::Apsl1_1, Some("https://opensource.org/licenses/APSL-1.1")),
+            (L::Apsl1_2, Some("https://opensource.org/licenses/APSL-1.2")),
+            (L::Apsl2, Some("https://opensource.org/licenses/APSL-2.0")),
+            (L::Artistic1, Some("https://opensource.org/licenses/Artistic-1.0")),
+            (L::Artistic1Cl8, Some("https://opensource.org/licenses/Artistic-1.0")),
+            (L::Artistic1Perl, Some("https://opensource.org/licenses/Artistic-1.0")),
+            (L::Artistic2, Some("https://opensource.org/licenses/Artistic-2.0")),
+            (L::Bsd2Clause, Some("https://opensource.org/licenses/BSD-2-Clause")),
+            (L::Bsd2ClausePatent, Some("https://opensource.org/licenses/BSD-2-Clause-Patent")),
+            (L::Bsd3Clause, Some("https://opensource.org/licenses/BSD-3-Clause")),
+            (L::Bsd3ClauseLbnl, Some("https://opensource.org/licenses/BSD-3-Clause-LBNL")),
+            (L::Bsl1, Some("https://opensource.org/licenses/BSL-1.0")),
+            (L::Catosl1_1, Some("https://opensource.org/licenses/CATOSL-1.1")),
+            (L::Cddl1, Some("https://opensource.org/licenses/CDDL-1.0")),
+            (L::Cecill2_1, Some("https://opensource.org/licenses/CECILL-2.1")),
+            (L::CnriPython, Some("https://opensource.org/licenses/CNRI-Python")),
+            (L::Cpal1, Some("https://opensource.org/licenses/CPAL-1.0")),
+            (L::Cpl1, Some("https://opensource.org/licenses/CPL-1.0")),
+            (L::CuaOpl1, Some("https://opensource.org/licenses/CUA-OPL-1.0")),
+            (L::Ecl1, Some("https://opensource.org/licenses/ECL-1.0")),
+            (L::Ecl2, Some("https://opensource.org/licenses/ECL-2.0")),
+            (L::Efl1, Some("https://opensource.org/licenses/EFL-1.0")),
+            (L::Efl2, Some("https://opensource.org/licenses/EFL-2.0")),
+            (L::Entessa, Some("https://opensource.org/licenses/Entessa")),
+            (L::Epl1, Some("https://opensource.org/licenses/EPL-1.0")),
+            (L::Epl2, Some("https://opensource.org/licenses/EPL-2.0")),
+            (L::EUDatagrid, Some("https://opensource.org/licenses/EUDatagrid")),
+            (L::Eupl1_1, Some("https://opensource.org/licenses/EUPL-1.1")),
+            (L::Eupl1_2, Some("https://opensource.org/licenses/EUPL-1.2")),
+            (L::Fair, Some("https://opensource.org/licenses/Fair")),
+            (L::Frameworx1, Some("https://opensource.org/licenses/Frameworx-1.0")),
+            (L::Gpl2Only, Some("https://opensource.org/licenses/GPL-2.0")),
+            (L::Gpl2OrLater, Some("https://opensource.org/licenses/GPL-2.0")),
+            (L::Gpl3Only, Some("https://opensource.org/licenses/GPL-3.0")),
+            (L::Gpl3OrLater, Some("https://opensource.org/licenses/GPL-3.0")),
+            (L::Hpnd, Some("https://opensource.org/licenses/HPND")),
+            (L::Intel, Some("https://opensource.org/licenses/Intel")),
+            (L::Ipa, Some("https://opensource.org/licenses/IPA")),
+            (L::Ipl1, Some("https://opensource.org/licenses/IPL-1.0")),
+            (L::Isc, Some("https://opensource.org/licenses/ISC")),
+            (L::Lgpl2Only, Some("https://opensource.org/licenses/LGPL-2.0")),
+            (L::Lgpl2OrLater, Some("https://opensource.org/licenses/LGPL-2.0")),
+            (L::Lgpl2_1Only, Some("https://opensource.org/licenses/LGPL-2.1")),
+            (L::Lgpl2_1OrLater, Some("https://opensource.org/licenses/LGPL-2.1")),
+            (L::Lgpl3Only, Some("https://opensource.org/licenses/LGPL-3.0")),
+            (L::Lgpl3OrLater, Some("https://opensource.org/licenses/LGPL-3.0")),
+            (L::LiLiQP1_1, Some("https://opensource.org/licenses/LiLiQ-P-1.1")),
+            (L::LiLiQR1_1, Some("https://opensource.org/licenses/LiLiQ-R-1.1")),
+            (L::LiLiQRplus1_1, Some("https://opensource.org/licenses/LiLiQ-Rplus-1.1")),
+            (L::Lpl1, Some("https://opensource.org/licenses/LPL-1.0")),
+            (L::Lpl1_02, Some("https://opensource.org/licenses/LPL-1.02")),
+            (L::Lppl1_3c, Some("https://opensource.org/licenses/LPPL-1.3c")),
+            (L::MirOs, Some("https://opensource.org/licenses/MirOS")),
+            (L::Mit, Some("https://opensource.org/licenses/MIT")),
+            (L::Mit0, Some("https://opensource.org/licenses/MIT-0")),
+            (L::Motosoto, Some("https://opensource.org/licenses/Motosoto")),
+            (L::Mpl1, Some("https://opensource.org/licenses/MPL-1.0")),
+            (L::Mpl1_1, Some("https://opensource.org/licenses/MPL-1.1")),
+            (L::Mpl2, Some("https://opensource.org/licenses/MPL-2.0")),
+            (L::Mpl2NoCopyleftException, Some("https://opensource.org/licenses/MPL-2.0")),
+            (L::MsPl, Some("https://opensource.org/licenses/MS-PL")),
+            (L::MsRl, Some("https://opensource.org/licenses/MS-RL")),
+            (L::Multics, Some("https://opensource.org/licenses/Multics")),
+            (L::Nasa1_3, Some("https://opensource.org/licenses/NASA-1.3")),
+            (L::Naumen, Some("https://opensource.org/licenses/Naumen")),
+            (L::Ncsa, Some("https://opensource.org/licenses/NCSA")),
+            (L::Ngpl, Some("https://opensource.org/licenses/NGPL")),
+            (L::Nokia, Some("https://opensource.org/licenses/Nokia")),
+            (L::Nposl3, Some("https://opensource.org/licenses/NPOSL-3.0")),
+            (L::Ntp, Some("https://opensource.org/licenses/NTP")),
+            (L::Oclc2, Some("https://opensource.org/licenses/OCLC-2.0")),
+            (L::Ofl1_1, Some("https://opensource.org/licenses/OFL-1.1")),
+            (L::Ogtsl, Some("https://opensource.org/licenses/OGTSL")),
+            (L::OsetPl2_1, Some("https://opensource.org/licenses/OSET-PL-2.1")),
+            (L::Osl1, Some("https://opensource.org/licenses/OSL-1.0")),
+            (L::Osl2, Some("https://opensource.org/licenses/OSL-2.0")),
+            (L::Osl2_1, Some("https://opensource.org/licenses/OSL-2.1")),
+            (L::Osl3, Some("https://opensource.org/licenses/OSL-3.0")),
+            (L::Php3, Some("https://opensource.org/licenses/PHP-3.0")),
+            (L::PostgreSql, Some("https://opensource.org/licenses/PostgreSQL")),
+            (L::Python2, Some("https://opensource.org/licenses/Python-2.0")),
+            (L::Qpl1, Some("https://opensource.org/licenses/QPL-1.0")),
+            (L::Rpl1_1, Some("https://opensource.org/licenses/RPL-1.1")),
+            (L::Rpl1_5, Some("https://opensource.org/licenses/RPL-1.5")),
+            (L::Rpsl1, Some("https://opensource.org/licenses/RPSL-1.0")),
+            (L::Rscpl, Some("https://opensource.org/licenses/RSCPL")),
+            (L::SimPl2, Some("https://opensource.org/licenses/SimPL-2.0")),
+            (L::Sissl, Some("https://opensource.org/licenses/SISSL")),
+            (L::Sleepycat, Some("https://opensource.org/licenses/Sleepycat")),
+            (L::Spl1, Some("https://opensource.org/licenses/SPL-1.0")),
+            (L::Ucl1, Some("https://opensource.org/licenses/UCL-1.0")),
+            (L::Upl1, Some("https://opensource.org/licenses/UPL")),
+            (L::Vsl1, Some("https://opensource.org/licenses/VSL-1.0")),
+            (L::W3C, Some("https://opensource.org/licenses/W3C")),
+            (L::Watcom1, Some("https://opensource.org/licenses/Watcom-1.0")),
+            (L::Xnet, Some("https://opensource.org/licenses/Xnet")),
+            (L::Zlib, Some("https://opensource.org/licenses/Zlib")),
+            (L::Zpl2, Some("https://opensource.org/licenses/ZPL-2.0")),
+        ])
+    };
+}
+
+/// Returns `url` without its scheme, `www.` subdomain, trailing slash or file
+/// extension, so that equivalent URLs compare equal.
+pub(super) fn normalize(url: &str) -> &str {
+    let mut url = url.trim();
+    for prefix in &["https://", "http://", "www."] {
+        url = strip_prefix(url, prefix).unwrap_or(url);
+    }
+    url = url.trim_end_matches('/');
+    for extension in &[".html", ".htm", ".txt", ".php", ".json"] {
+        if let Some(stripped) = strip_suffix(url, extension) {
+            return stripped;
+        }
+    }
+    url
+}
+
+pub(super) fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    match s.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => Some(&s[prefix.len()..]),
+        _ => None,
+    }
+}
+
+fn strip_suffix<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+    let start = s.len().checked_sub(suffix.len())?;
+    match s.get(start..) {
+        Some(end) if end.eq_ignore_ascii_case(suffix) => Some(&s[..start]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_url() {
+        for license in SpdxLicense::all() {
+            assert_eq!(SpdxLicense::from_url(license.url()), Some(license));
+        }
+
+        let urls = [
+            ("http://www.apache.org/licenses/LICENSE-2.0.txt", SpdxLicense::Apache2),
+            ("https://opensource.org/licenses/mit", SpdxLicense::Mit),
+            ("https://opensource.org/license/bsd-3-clause/", SpdxLicense::Bsd3Clause),
+            ("https://www.eclipse.org/legal/epl-v10.html", SpdxLicense::Epl1),
+            ("https://spdx.org/licenses/0BSD.json", SpdxLicense::Bsd0),
+        ];
+        for &(url, license) in &urls {
+            assert_eq!(SpdxLicense::from_url(url), Some(license), "{}", url);
+        }
+        assert_eq!(SpdxLicense::from_url("https://example.com/LICENSE"), None);
+    }
+
+    #[test]
+    fn osi_url() {
+        for license in SpdxLicense::all() {
+            assert_eq!(license.osi_url().is_some(), license.is_osi_approved(), "{}", license);
+            let url = match license.osi_url() {
+                Some(url) => url,
+                None => continue,
+            };
+            let listed = license.see_also().iter().any(|u| normalize(u) == normalize(url));
+            assert!(listed, "{}", license);
+            assert_eq!(SpdxLicense::from_url(url).and_then(SpdxLicense::osi_url), Some(url));
+        }
+        assert_eq!(
+            SpdxLicense::from_url("https://opensource.org/licenses/LGPL-2.1"),
+            Some(SpdxLicense::Lgpl2_1Only),
+        );
+    }
+}
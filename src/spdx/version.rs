@@ -0,0 +1,233 @@
+use core::fmt;
+use alloc::vec::Vec;
+use super::{sparse_map, Map, SpdxLicense};
+
+/// A version of the [SPDX license list](https://spdx.org/licenses), such as
+/// `3.7`.
+///
+/// Versions are ordered, so that `version >= ListVersion::new(3, 0)` checks
+/// for version 3.0 or later.
+///
+/// See [`SpdxLicense::since`](enum.SpdxLicense.html#method.since) and
+/// [`SpdxLicense::deprecated_in`](enum.SpdxLicense.html#method.deprecated_in).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ListVersion {
+    /// The major version, such as `3` in `3.7`.
+    pub major: u8,
+    /// The minor version, such as `7` in `3.7`.
+    pub minor: u8,
+}
+
+impl fmt::Display for ListVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl ListVersion {
+    /// The version that [`SpdxLicense`](enum.SpdxLicense.html) is based on.
+    pub const CURRENT: Self = Self::new(3, 7);
+
+    /// Creates a version from its parts.
+    #[inline]
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+
+    /// Parses a version in the form `3.7` or `v3.7`, as used by the
+    /// `LicenseListVersion` field of SPDX documents.
+    ///
+    /// ```
+    /// use linfo::spdx::ListVersion;
+    ///
+    /// assert_eq!(ListVersion::parse("3.7"), Some(ListVersion::new(3, 7)));
+    /// assert_eq!(ListVersion::parse("v3.10"), Some(ListVersion::new(3, 10)));
+    /// assert_eq!(ListVersion::parse("3"), None);
+    /// ```
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.trim().trim_start_matches('v').split('.');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(major), Some(minor), None) => {
+                Some(Self::new(major.parse().ok()?, minor.parse().ok()?))
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the licenses added and deprecated after `from`, up to and
+    /// including `to`.
+    ///
+    /// ```
+    /// use linfo::spdx::{ListVersion, SpdxLicense};
+    ///
+    /// let changes = ListVersion::changes(ListVersion::new(3, 5), ListVersion::new(3, 7));
+    /// assert!(changes.added.contains(&SpdxLicense::BlueOak1));
+    /// assert!(changes.deprecated.is_empty());
+    ///
+    /// let changes = ListVersion::changes(ListVersion::CURRENT, ListVersion::new(3, 10));
+    /// assert!(changes.added.is_empty());
+    /// assert_eq!(changes.to_string(), "3.7 to 3.10: deprecated BSD-2-Clause-NetBSD, \
+    ///                                  BSD-2-Clause-FreeBSD");
+    /// ```
+    pub fn changes(from: Self, to: Self) -> Changes {
+        let changed = |version: Option<Self>| version.is_some_and(|v| from < v && v <= to);
+
+        let mut added: Vec<SpdxLicense> = SpdxLicense::all()
+            .filter(|l| changed(Some(l.since())))
+            .collect();
+        let mut deprecated: Vec<SpdxLicense> = SpdxLicense::all()
+            .filter(|l| changed(l.deprecated_in()))
+            .collect();
+        added.sort_by_key(|l| l.since());
+        deprecated.sort_by_key(|l| l.deprecated_in());
+
+        Changes { from, to, added, deprecated }
+    }
+
+    /// Used for comparisons in `const fn`s.
+    pub(crate) const fn as_u16(self) -> u16 {
+        (self.major as u16) << 8 | self.minor as u16
+    }
+}
+
+/// The result of [`ListVersion::changes`](struct.ListVersion.html#method.changes).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Changes {
+    /// The earlier version.
+    pub from: ListVersion,
+    /// The later version.
+    pub to: ListVersion,
+    /// The licenses added in between, in the order they were added.
+    pub added: Vec<SpdxLicense>,
+    /// The licenses deprecated in between, in the order they were deprecated.
+    pub deprecated: Vec<SpdxLicense>,
+}
+
+impl fmt::Display for Changes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} to {}:", self.from, self.to)?;
+        let mut separator = " ";
+        for (label, licenses) in &[("added", &self.added), ("deprecated", &self.deprecated)] {
+            if licenses.is_empty() {
+                continue;
+            }
+            write!(f, "{}{}", separator, label)?;
+            for (i, license) in licenses.iter().enumerate() {
+                f.write_str(if i == 0 { " " } else { ", " })?;
+                license.fmt(f)?;
+            }
+            separator = "; ";
+        }
+        if separator == " " {
+            f.write_str(" no changes")?;
+        }
+        Ok(())
+    }
+}
+
+impl SpdxLicense {
+    // Licenses not listed here are recorded as added in 1.0, meaning that they
+    // were added in 2.5 or earlier.
+    pub(crate) const SINCE: Map<ListVersion> = {
+        use SpdxLicense as L;
+
+        const V2_6: ListVersion = ListVersion::new(2, 6);
+        const V3_0: ListVersion = ListVersion::new(3, 0);
+        const V3_1: ListVersion = ListVersion::new(3, 1);
+        const V3_2: ListVersion = ListVersion::new(3, 2);
+        const V3_4: ListVersion = ListVersion::new(3, 4);
+        const V3_5: ListVersion = ListVersion::new(3, 5);
+        const V3_6: ListVersion = ListVersion::new(3, 6);
+        const V3_7: ListVersion = ListVersion::new(3, 7);
+
+        sparse_map(ListVersion::new(1, 0), &[
+            (L::Bsd0, V2_6),
+            (L::Bsd2ClausePatent, V2_6),
+
+            // Replaced the GNU ids without a suffix, such as `GPL-2.0`
+            (L::Agpl3Only, V3_0),
+            (L::Agpl3OrLater, V3_0),
+            (L::Gfdl1_1Only, V3_0),
+            (L::Gfdl1_1OrLater, V3_0),
+            (L::Gfdl1_2Only, V3_0),
+            (L::Gfdl1_2OrLater, V3_0),
+            (L::Gfdl1_3Only, V3_0),
+            (L::Gfdl1_3OrLater, V3_0),
+            (L::Gpl1Only, V3_0),
+            (L::Gpl1OrLater, V3_0),
+            (L::Gpl2Only, V3_0),
+            (L::Gpl2OrLater, V3_0),
+            (L::Gpl3Only, V3_0),
+            (L::Gpl3OrLater, V3_0),
+            (L::Lgpl2Only, V3_0),
+            (L::Lgpl2OrLater, V3_0),
+            (L::Lgpl2_1Only, V3_0),
+            (L::Lgpl2_1OrLater, V3_0),
+            (L::Lgpl3Only, V3_0),
+            (L::Lgpl3OrLater, V3_0),
+            (L::CdlaPermissive1, V3_0),
+            (L::CdlaSharing1, V3_0),
+
+            (L::Agpl1Only, V3_1),
+            (L::Agpl1OrLater, V3_1),
+
+            (L::Libpng2, V3_2),
+            (L::LinuxOpenIb, V3_2),
+            (L::Mit0, V3_2),
+
+            (L::OglCanada2, V3_4),
+            (L::Parity6, V3_4),
+
+            (L::Etalab2, V3_5),
+            (L::Shl0_5, V3_5),
+            (L::Shl0_51, V3_5),
+            (L::Sspl1, V3_5),
+
+            (L::BlueOak1, V3_6),
+            (L::HpndSellVariant, V3_6),
+
+            (L::MulanPsl1, V3_7),
+            (L::Ucl1, V3_7),
+        ])
+    };
+
+    // Licenses deprecated in later versions of the list, which still include
+    // them for use by existing documents.
+    pub(crate) const DEPRECATED: Map<Option<ListVersion>> = {
+        use SpdxLicense as L;
+
+        sparse_map(None, &[
+            (L::Bsd2ClauseNetBsd, Some(ListVersion::new(3, 9))),
+            (L::Bsd2ClauseFreeBsd, Some(ListVersion::new(3, 10))),
+        ])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_in() {
+        for license in SpdxLicense::all() {
+            assert!(license.since() <= ListVersion::CURRENT, "{}", license);
+            assert!(license.is_valid_in(ListVersion::CURRENT), "{}", license);
+            if let Some(deprecated_in) = license.deprecated_in() {
+                assert!(license.since() < deprecated_in, "{}", license);
+                assert!(license.is_valid_in(deprecated_in), "{}", license);
+                assert!(license.is_deprecated_in(deprecated_in), "{}", license);
+            }
+        }
+
+        let v2_6 = ListVersion::new(2, 6);
+        let changes = ListVersion::changes(v2_6, ListVersion::new(3, 0));
+        assert!(changes.added.contains(&SpdxLicense::Gpl2Only));
+        assert!(!changes.added.contains(&SpdxLicense::Bsd0));
+
+        let changes = ListVersion::changes(v2_6, ListVersion::CURRENT);
+        for license in SpdxLicense::all() {
+            assert_eq!(changes.added.contains(&license), !license.is_valid_in(v2_6), "{}", license);
+        }
+        assert!(ListVersion::changes(ListVersion::CURRENT, v2_6).added.is_empty());
+    }
+}